@@ -1,28 +1,362 @@
 use async_trait::async_trait;
-use crate::github::RepoData;
+use chrono::{DateTime, Months, Utc};
+use crate::codeowners::{self, CodeOwners};
+use crate::config::Thresholds;
+use crate::github::{ContributorStats, DataSource, RepoData};
+use crate::growth::ContributorGrowth;
+use crate::truck_factor::FileOwnership;
 use crate::Result;
-use super::{Analyzer, AnalysisResult, Finding};
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding, ReportTable};
 
-pub struct BusFactorAnalyzer;
+/// Recent windows, in months, used when none are configured
+pub const DEFAULT_WINDOWS: &[u32] = &[6, 12];
+
+/// Share of files CODEOWNERS should cover
+const CODEOWNERS_COVERAGE_TARGET: f64 = 80.0;
+
+/// Directories reported per CODEOWNERS finding kind
+const MAX_CODEOWNERS_DIRS_REPORTED: usize = 5;
+
+/// Share of first-time contributors expected to come back in a later quarter
+const RETENTION_TARGET: f64 = 25.0;
+
+/// Quarters listed in the contributor growth table
+const MAX_QUARTERS_REPORTED: usize = 8;
+
+/// How knowledge concentration is measured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BusFactorAlgorithm {
+    /// Fewest contributors accounting for half of the commits
+    #[default]
+    Commits,
+    /// Avelino et al. truck factor from per-file degree of authorship; needs a local checkout
+    TruckFactor,
+}
+
+impl std::str::FromStr for BusFactorAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "commits" => Ok(BusFactorAlgorithm::Commits),
+            "truck-factor" => Ok(BusFactorAlgorithm::TruckFactor),
+            _ => Err(format!("unknown bus factor algorithm `{}` (expected commits or truck-factor)", s)),
+        }
+    }
+}
+
+pub struct BusFactorAnalyzer {
+    /// Recent windows in months; the longest one drives the score
+    windows: Vec<u32>,
+    algorithm: BusFactorAlgorithm,
+}
+
+impl Default for BusFactorAnalyzer {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOWS.to_vec())
+    }
+}
 
 impl BusFactorAnalyzer {
-    fn calculate_bus_factor(&self, contributors: &[crate::github::ContributorStats]) -> (usize, Vec<(String, i64, f64)>) {
-        if contributors.is_empty() {
+    pub fn new(mut windows: Vec<u32>) -> Self {
+        windows.retain(|&months| months > 0);
+        windows.sort_unstable();
+        windows.dedup();
+        Self { windows, algorithm: BusFactorAlgorithm::default() }
+    }
+
+    pub fn with_algorithm(mut self, algorithm: BusFactorAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    fn analyze_truck_factor(&self, ownership: &FileOwnership, thresholds: &Thresholds) -> AnalysisResult {
+        let mut findings = Vec::new();
+        let truck_factor = ownership.truck_factor();
+        let files = ownership.authors.len();
+
+        findings.push(Finding::positive(format!(
+            "Truck factor: {} (authors whose departure orphans more than half of {} files)",
+            truck_factor.value, files
+        )).with_rule(&rules::CONCENTRATION));
+        if !truck_factor.key_authors.is_empty() {
+            let message = format!(
+                "Key authors: {} ({} files orphaned without them)",
+                truck_factor.key_authors.join(", "), truck_factor.orphaned_files
+            );
+            let finding = if truck_factor.value <= 1 { Finding::warning(message) } else { Finding::positive(message) };
+            let score = thresholds.bus_factor_score(truck_factor.value);
+            findings.push(finding.with_rule(&rules::CONCENTRATION).scored(score, 100.0));
+        }
+
+        let mut table = ReportTable::new(
+            "File ownership by directory",
+            &["Directory", "Files", "Authors", "Main author", "Files authored"],
+        );
+        for directory in ownership.by_directory() {
+            let (main_author, authored) = match &directory.top_author {
+                Some((author, count)) => (author.clone(), format!("{} ({:.0}%)", count, *count as f64 / directory.files as f64 * 100.0)),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            if directory.authors == 1 && directory.files >= 3 {
+                findings.push(Finding::warning(format!(
+                    "{} is authored by {} alone ({} files)",
+                    directory.directory, main_author, directory.files
+                )).with_rule(&rules::SINGLE_AUTHOR_DIRECTORY).at(directory.directory.clone(), None));
+            }
+            table.push_row(vec![
+                format!("`{}`", directory.directory),
+                directory.files.to_string(),
+                directory.authors.to_string(),
+                main_author,
+                authored,
+            ]);
+        }
+
+        let details = match truck_factor.value {
+            0 => "No file history available".to_string(),
+            1 => "Critical: One author's departure would orphan most files".to_string(),
+            2 => "Low: Two authors hold most file knowledge".to_string(),
+            3..=4 => "Moderate: File knowledge held by 3-4 authors".to_string(),
+            _ => "Healthy: File knowledge is well distributed".to_string(),
+        };
+
+        AnalysisResult {
+            score: thresholds.bus_factor_score(truck_factor.value),
+            details: format!("{} (truck factor)", details),
+            findings,
+            tables: vec![table],
+            assessment: Assessment::Scored,
+        }
+    }
+
+    fn analyze_commits(&self, repo_data: &RepoData, mut findings: Vec<Finding>) -> AnalysisResult {
+        if let Some(reason) = repo_data.availability.gap(DataSource::Contributors) {
+            findings.push(
+                Finding::unknown(format!("Contributor statistics could not be fetched ({})", reason))
+                    .with_rule(&rules::CONCENTRATION)
+            );
+            return AnalysisResult::unknown("Unable to calculate bus factor - contributor statistics unavailable", findings);
+        }
+
+        if repo_data.contributors.is_empty() {
+            findings.push(Finding::not_applicable("No commits by human contributors"));
+            return AnalysisResult::not_applicable("No contributors to measure", findings);
+        }
+
+        let identities = &repo_data.identities;
+        if !identities.bots.is_empty() {
+            findings.push(Finding::positive(format!(
+                "Excluded {} bot account(s): {}",
+                identities.bots.len(), identities.bots.join(", ")
+            )));
+        }
+        for (alias, canonical) in &identities.merged {
+            findings.push(Finding::positive(format!("Counted {} as {}", alias, canonical)));
+        }
+
+        let windows = self.windows(&repo_data.contributors, Utc::now());
+        let lifetime = windows.last().expect("lifetime window is always present");
+
+        // Score today's risk: the longest recent window with activity. Without any, nobody is
+        // left to lose, so past contributors don't count towards the score.
+        let recent = windows.iter().rev().skip(1).find(|w| w.total() > 0);
+        let inactive = !self.windows.is_empty() && recent.is_none();
+        let scored = recent.unwrap_or(lifetime);
+        let bus_factor = if inactive { 0 } else { scored.bus_factor };
+        let score = if inactive { 0.0 } else { repo_data.config.thresholds.bus_factor_score(bus_factor) };
+
+        let longest_window = self.windows.last().copied().unwrap_or_default();
+        if inactive {
+            findings.push(Finding::warning(format!(
+                "No commits in the last {} months; nobody is actively maintaining the project",
+                longest_window
+            )).with_rule(&rules::RECENT_ACTIVITY).scored(score, 100.0));
+        }
+
+        for window in &windows {
+            findings.push(Finding::positive(format!(
+                "Bus factor ({}): {} (minimum contributors accounting for 50% of {} commits)",
+                window.label.to_lowercase(), window.bus_factor, window.total()
+            )).with_rule(&rules::CONCENTRATION));
+        }
+
+        // Add information about top contributors
+        if !scored.top.is_empty() {
+            findings.push(Finding::positive(format!(
+                "Total contributors: {} ({} active in {})",
+                repo_data.contributors.len(), scored.commits.len(), scored.label.to_lowercase()
+            )));
+
+            for (idx, (login, commits, percentage)) in scored.top.iter().enumerate() {
+                let status = if idx == 0 && *percentage > 70.0 {
+                    let finding = Finding::warning(format!(
+                        "{}: {} commits ({:.1}%) - High concentration of ownership",
+                        login, commits, percentage
+                    )).with_rule(&rules::CONCENTRATION);
+                    if inactive { finding } else { finding.scored(score, 100.0) }
+                } else {
+                    Finding::positive(format!(
+                        "{}: {} commits ({:.1}%)",
+                        login, commits, percentage
+                    )).with_rule(&rules::CONCENTRATION)
+                };
+                findings.push(status);
+            }
+        }
+
+        let mut table = ReportTable::new(
+            "Bus factor by period",
+            &["Period", "Commits", "Active contributors", "Bus factor", "Top contributor"],
+        );
+        for window in &windows {
+            table.push_row(vec![
+                window.label.clone(),
+                window.total().to_string(),
+                window.commits.len().to_string(),
+                window.bus_factor.to_string(),
+                window.top.first()
+                    .map(|(login, _, share)| format!("{} ({:.1}%)", login, share))
+                    .unwrap_or_else(|| "-".to_string()),
+            ]);
+        }
+
+        let details = match bus_factor {
+            0 if inactive => format!("Inactive: no commits in the last {} months", longest_window),
+            0 => "No commit data available".to_string(),
+            1 => "Critical: Single person controls >50% of commits".to_string(),
+            2 => "Low: Two people control >50% of commits".to_string(),
+            3..=4 => "Moderate: Small team of 3-4 core contributors".to_string(),
+            _ => "Healthy: Well-distributed contributions".to_string(),
+        };
+
+        AnalysisResult {
+            score,
+            details: if inactive { details } else { format!("{} ({})", details, scored.label.to_lowercase()) },
+            findings,
+            tables: vec![table],
+            assessment: Assessment::Scored,
+        }
+    }
+
+    /// Whether the contributor base is growing and keeps its newcomers.
+    fn add_growth(&self, growth: &ContributorGrowth, result: &mut AnalysisResult) {
+        if growth.historical == 0 {
+            return;
+        }
+
+        result.findings.push(Finding::positive(format!(
+            "Active contributors: {} of {} ({:.0}%) committed in the last 12 months",
+            growth.active, growth.historical, growth.active_percent()
+        )).with_rule(&rules::SHRINKING_COMMUNITY));
+        if growth.is_shrinking() {
+            result.findings.push(Finding::warning(format!(
+                "Contributor base is shrinking: {} active in the last 12 months, down from {} the year before",
+                growth.active, growth.previously_active
+            )).with_rule(&rules::SHRINKING_COMMUNITY));
+        }
+
+        let (recent, previous) = growth.new_contributors_trend();
+        let message = format!("New contributors: {} in the last 4 quarters ({} in the 4 before)", recent, previous);
+        let finding = if recent < previous { Finding::warning(message) } else { Finding::positive(message) };
+        result.findings.push(finding.with_rule(&rules::NEW_CONTRIBUTORS));
+
+        if let Some(retention) = growth.retention_percent() {
+            let message = format!(
+                "First-time contributor retention: {} of {} ({:.0}%) returned in a later quarter",
+                growth.retained, growth.first_timers, retention
+            );
+            let finding = if retention < RETENTION_TARGET { Finding::warning(message) } else { Finding::positive(message) };
+            result.findings.push(finding.with_rule(&rules::RETENTION));
+        }
+
+        let mut table = ReportTable::new("Contributors by quarter", &["Quarter", "Active", "New"]);
+        let skip = growth.quarters.len().saturating_sub(MAX_QUARTERS_REPORTED);
+        for quarter in growth.quarters.iter().skip(skip) {
+            table.push_row(vec![quarter.label(), quarter.active.to_string(), quarter.new.to_string()]);
+        }
+        result.tables.push(table);
+    }
+
+    /// Which files CODEOWNERS leaves unowned and where review rests on one person.
+    fn add_code_ownership(&self, owners: &CodeOwners, repo_data: &RepoData, result: &mut AnalysisResult) {
+        let coverage = owners.coverage(&repo_data.tree);
+        if coverage.files == 0 {
+            return;
+        }
+
+        let percent = coverage.owned_files as f64 / coverage.files as f64 * 100.0;
+        let message = format!(
+            "{} assigns owners to {} of {} files ({:.0}%)",
+            owners.path, coverage.owned_files, coverage.files, percent
+        );
+        let finding = if percent >= CODEOWNERS_COVERAGE_TARGET { Finding::positive(message) } else { Finding::warning(message) };
+        result.findings.push(finding.with_rule(&rules::CODEOWNERS_COVERAGE).at(owners.path.clone(), None));
+
+        let teams = coverage.owners.iter().filter(|(owner, _)| codeowners::is_team(owner)).count();
+        result.findings.push(Finding::positive(format!(
+            "Code owners: {} team(s), {} individual(s)",
+            teams, coverage.owners.len() - teams
+        )));
+
+        for (directory, unowned, files) in coverage.unowned_dirs.iter().take(MAX_CODEOWNERS_DIRS_REPORTED) {
+            result.findings.push(Finding::warning(format!(
+                "No code owner for {} of {} files in {}", unowned, files, directory
+            )).with_rule(&rules::UNOWNED_DIRECTORY).at(directory.clone(), None));
+        }
+        for (directory, owner, files) in coverage.single_owner_dirs.iter().take(MAX_CODEOWNERS_DIRS_REPORTED) {
+            result.findings.push(Finding::warning(format!(
+                "{} is owned by {} alone ({} files)", directory, owner, files
+            )).with_rule(&rules::SINGLE_OWNER_DIRECTORY).at(directory.clone(), None));
+        }
+
+        let mut table = ReportTable::new("Code owners", &["Owner", "Type", "Files", "Share"]);
+        for (owner, files) in &coverage.owners {
+            table.push_row(vec![
+                owner.clone(),
+                if codeowners::is_team(owner) { "Team" } else { "Individual" }.to_string(),
+                files.to_string(),
+                format!("{:.1}%", *files as f64 / coverage.files as f64 * 100.0),
+            ]);
+        }
+        if !table.rows.is_empty() {
+            result.tables.push(table);
+        }
+    }
+
+    /// Commits per contributor since `since`, from the weekly stats.
+    fn commits_since(&self, contributors: &[ContributorStats], since: DateTime<Utc>) -> Vec<(String, i64)> {
+        contributors.iter()
+            .map(|c| {
+                let commits = c.weeks.iter()
+                    .filter(|week| week.w >= since.timestamp())
+                    .map(|week| week.c)
+                    .sum();
+                (c.author.login.clone(), commits)
+            })
+            .filter(|(_, commits)| *commits > 0)
+            .collect()
+    }
+
+    fn lifetime_commits(&self, contributors: &[ContributorStats]) -> Vec<(String, i64)> {
+        contributors.iter().map(|c| (c.author.login.clone(), c.total)).collect()
+    }
+
+    fn calculate_bus_factor(&self, commits: &[(String, i64)]) -> (usize, Vec<(String, i64, f64)>) {
+        if commits.is_empty() {
             return (0, Vec::new());
         }
 
         // Calculate total commits
-        let total_commits: i64 = contributors.iter().map(|c| c.total).sum();
+        let total_commits: i64 = commits.iter().map(|(_, c)| c).sum();
 
         if total_commits == 0 {
             return (0, Vec::new());
         }
 
         // Sort contributors by commit count (descending)
-        let mut sorted_contributors: Vec<_> = contributors.iter()
-            .map(|c| (c.author.login.clone(), c.total))
-            .collect();
-        sorted_contributors.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut sorted_contributors = commits.to_vec();
+        sorted_contributors.sort_by_key(|c| std::cmp::Reverse(c.1));
 
         // Calculate bus factor: minimum number of contributors accounting for 50% of commits
         let target_commits = total_commits / 2;
@@ -48,14 +382,35 @@ impl BusFactorAnalyzer {
         (bus_factor, top_contributors)
     }
 
-    fn score_bus_factor(&self, bus_factor: usize) -> f64 {
-        match bus_factor {
-            0 => 0.0,
-            1 => 10.0,
-            2 => 40.0,
-            3..=4 => 70.0,
-            _ => 100.0,
-        }
+    /// Bus factor for each recent window, shortest first, followed by the project's lifetime.
+    fn windows(&self, contributors: &[ContributorStats], now: DateTime<Utc>) -> Vec<Window> {
+        let mut windows: Vec<Window> = self.windows.iter()
+            .map(|&months| {
+                let since = now.checked_sub_months(Months::new(months)).unwrap_or(now);
+                let commits = self.commits_since(contributors, since);
+                let (bus_factor, top) = self.calculate_bus_factor(&commits);
+                Window { label: format!("Last {} months", months), commits, bus_factor, top }
+            })
+            .collect();
+
+        let commits = self.lifetime_commits(contributors);
+        let (bus_factor, top) = self.calculate_bus_factor(&commits);
+        windows.push(Window { label: "Lifetime".to_string(), commits, bus_factor, top });
+
+        windows
+    }
+}
+
+struct Window {
+    label: String,
+    commits: Vec<(String, i64)>,
+    bus_factor: usize,
+    top: Vec<(String, i64, f64)>,
+}
+
+impl Window {
+    fn total(&self) -> i64 {
+        self.commits.iter().map(|(_, c)| c).sum()
     }
 }
 
@@ -65,6 +420,14 @@ impl Analyzer for BusFactorAnalyzer {
         "Bus Factor"
     }
 
+    fn id(&self) -> &str {
+        "bus-factor"
+    }
+
+    fn description(&self) -> &str {
+        "Knowledge concentration among contributors, contributor growth and code ownership"
+    }
+
     fn weight(&self) -> f64 {
         0.15
     }
@@ -72,58 +435,92 @@ impl Analyzer for BusFactorAnalyzer {
     async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult> {
         let mut findings = Vec::new();
 
-        if repo_data.contributors.is_empty() {
-            findings.push(Finding::warning("No contributor data available"));
-            return Ok(AnalysisResult {
-                score: 50.0, // Neutral score when data unavailable
-                details: "Unable to calculate bus factor - no contributor data".to_string(),
-                findings,
-            });
+        let mut result = match (self.algorithm, &repo_data.file_ownership) {
+            (BusFactorAlgorithm::TruckFactor, Some(ownership)) => self.analyze_truck_factor(ownership, &repo_data.config.thresholds),
+            (BusFactorAlgorithm::TruckFactor, None) => {
+                findings.push(Finding::warning(
+                    "Truck factor needs a local checkout (--local); using commit counts instead",
+                ));
+                self.analyze_commits(repo_data, findings)
+            }
+            (BusFactorAlgorithm::Commits, _) => self.analyze_commits(repo_data, findings),
+        };
+
+        if !repo_data.contributors.is_empty() {
+            self.add_growth(&ContributorGrowth::from_stats(&repo_data.contributors, Utc::now()), &mut result);
         }
 
-        let (bus_factor, top_contributors) = self.calculate_bus_factor(&repo_data.contributors);
-        let score = self.score_bus_factor(bus_factor);
+        if let Some(owners) = CodeOwners::find(&repo_data.tree, &repo_data.file_contents) {
+            self.add_code_ownership(&owners, repo_data, &mut result);
+        }
 
-        findings.push(Finding::positive(format!(
-            "Bus factor: {} (minimum contributors accounting for 50% of commits)",
-            bus_factor
-        )));
+        Ok(result)
+    }
+}
 
-        // Add information about top contributors
-        if !top_contributors.is_empty() {
-            findings.push(Finding::positive(format!(
-                "Total contributors: {}",
-                repo_data.contributors.len()
-            )));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::github::{Author, ContributorWeek};
 
-            for (idx, (login, commits, percentage)) in top_contributors.iter().enumerate() {
-                let status = if idx == 0 && *percentage > 70.0 {
-                    Finding::warning(format!(
-                        "{}: {} commits ({:.1}%) - High concentration of ownership",
-                        login, commits, percentage
-                    ))
-                } else {
-                    Finding::positive(format!(
-                        "{}: {} commits ({:.1}%)",
-                        login, commits, percentage
-                    ))
-                };
-                findings.push(status);
-            }
+    fn contributor(login: &str, weeks: &[(DateTime<Utc>, i64)]) -> ContributorStats {
+        ContributorStats {
+            author: Author { login: login.to_string(), account_type: None },
+            total: weeks.iter().map(|(_, c)| c).sum(),
+            weeks: weeks.iter().map(|(w, c)| ContributorWeek { w: w.timestamp(), a: 0, d: 0, c: *c }).collect(),
         }
+    }
 
-        let details = match bus_factor {
-            0 => "No commit data available".to_string(),
-            1 => "Critical: Single person controls >50% of commits".to_string(),
-            2 => "Low: Two people control >50% of commits".to_string(),
-            3..=4 => "Moderate: Small team of 3-4 core contributors".to_string(),
-            _ => "Healthy: Well-distributed contributions".to_string(),
-        };
+    #[test]
+    fn test_recent_windows() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let long_ago = Utc.with_ymd_and_hms(2020, 1, 6, 0, 0, 0).unwrap();
+        let recent = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let last_year = Utc.with_ymd_and_hms(2025, 9, 1, 0, 0, 0).unwrap();
 
-        Ok(AnalysisResult {
-            score,
-            details,
-            findings,
-        })
+        let contributors = vec![
+            contributor("founder", &[(long_ago, 500)]),
+            contributor("alice", &[(recent, 10), (last_year, 10)]),
+            contributor("bob", &[(recent, 10)]),
+            contributor("carol", &[(last_year, 30)]),
+        ];
+
+        let analyzer = BusFactorAnalyzer::default();
+        let windows = analyzer.windows(&contributors, now);
+        let summary: Vec<(&str, i64, usize)> = windows.iter()
+            .map(|w| (w.label.as_str(), w.total(), w.bus_factor))
+            .collect();
+
+        assert_eq!(summary, vec![
+            ("Last 6 months", 20, 1),
+            ("Last 12 months", 60, 1),
+            ("Lifetime", 560, 1),
+        ]);
+        assert_eq!(windows[0].commits.len(), 2);
+        assert_eq!(windows[1].top[0].0, "carol");
+        assert_eq!(windows[2].top[0].0, "founder");
+    }
+
+    #[tokio::test]
+    async fn test_no_recent_commits_scores_zero() {
+        let long_ago = Utc.with_ymd_and_hms(2020, 1, 6, 0, 0, 0).unwrap();
+        let mut repo_data = RepoData::for_tests(Vec::new(), &[]);
+        repo_data.contributors = vec![
+            contributor("alice", &[(long_ago, 40)]),
+            contributor("bob", &[(long_ago, 30)]),
+            contributor("carol", &[(long_ago, 30)]),
+        ];
+
+        let result = BusFactorAnalyzer::default().analyze(&repo_data).await.unwrap();
+        assert_eq!(result.score, 0.0);
+        assert!(result.is_scored());
+        assert_eq!(result.details, "Inactive: no commits in the last 12 months");
+        assert!(result.findings.iter().any(|f| f.is_rule(&rules::RECENT_ACTIVITY) && f.status.is_failure()));
+    }
+
+    #[test]
+    fn test_new_normalizes_windows() {
+        assert_eq!(BusFactorAnalyzer::new(vec![12, 0, 3, 12]).windows, vec![3, 12]);
     }
 }
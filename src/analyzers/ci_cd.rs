@@ -1,35 +1,96 @@
 use async_trait::async_trait;
+use crate::ci::{self, MatrixCoverage};
 use crate::github::RepoData;
 use crate::Result;
-use super::{Analyzer, AnalysisResult, Finding};
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding};
 
 pub struct CiCdAnalyzer;
 
 impl CiCdAnalyzer {
-    fn detect_ci_configs(&self, tree: &[crate::github::TreeEntry]) -> Vec<String> {
-        let mut configs = Vec::new();
+    fn detect_ci_configs(&self, ci_files: &[ci::CiFile]) -> Vec<String> {
+        ci_files.iter()
+            .map(|file| match file.provider_id.as_str() {
+                "github-actions" => format!("GitHub Actions: {}", file.path),
+                _ => format!("{} ({})", file.provider, file.path),
+            })
+            .collect()
+    }
+
+    /// Sub-score (max 15) for the operating systems, runtimes and features CI exercises.
+    fn score_matrix(&self, repo_data: &RepoData, findings: &mut Vec<Finding>) -> f64 {
+        let configs = ci::parse_configs(&repo_data.ci_files, &repo_data.file_contents);
+        if configs.is_empty() {
+            return 0.0;
+        }
+
+        let coverage = MatrixCoverage::from_configs(&configs);
+        let mut score = 0.0;
+
+        match coverage.operating_systems.len() {
+            0 => {}
+            1 => findings.push(Finding::warning(format!(
+                "CI runs on a single OS ({})",
+                coverage.operating_systems.iter().next().map(String::as_str).unwrap_or_default()
+            )).with_rule(&rules::CI_OPERATING_SYSTEMS).scored(0.0, 5.0)),
+            _ => {
+                score += 5.0;
+                findings.push(Finding::positive(format!(
+                    "CI covers multiple operating systems: {}",
+                    Self::join(&coverage.operating_systems)
+                )).with_rule(&rules::CI_OPERATING_SYSTEMS));
+            }
+        }
 
-        for entry in tree {
-            if entry.entry_type != "blob" {
-                continue;
+        for (runtime, versions) in [("Rust", &coverage.rust), ("Node.js", &coverage.node), ("Python", &coverage.python)] {
+            if !versions.is_empty() {
+                findings.push(Finding::positive(format!("{} versions tested: {}", runtime, Self::join(versions))));
+            }
+        }
+
+        if !coverage.feature_sets.is_empty() {
+            findings.push(Finding::positive(format!(
+                "Feature combinations tested: {}",
+                Self::join(&coverage.feature_sets)
+            )));
+        }
+
+        let msrv = repo_data.file_contents.get("Cargo.toml").and_then(|m| ci::declared_rust_version(m));
+        let multiple_versions = [&coverage.rust, &coverage.node, &coverage.python]
+            .iter()
+            .any(|versions| versions.len() > 1);
+
+        match msrv {
+            Some(msrv) if !coverage.has_msrv_job(&msrv) => {
+                findings.push(Finding::warning(format!(
+                    "Cargo.toml declares rust-version {} but no CI job tests the MSRV",
+                    msrv
+                )).with_rule(&rules::CI_MSRV).at("Cargo.toml", None).scored(0.0, 5.0));
+            }
+            Some(msrv) => {
+                score += 5.0;
+                findings.push(Finding::positive(format!("MSRV {} is tested in CI", msrv)).with_rule(&rules::CI_MSRV));
             }
+            None if multiple_versions => score += 5.0,
+            None => {}
+        }
 
-            if entry.path.starts_with(".github/workflows/") && entry.path.ends_with(".yml") {
-                configs.push(format!("GitHub Actions: {}", entry.path));
-            } else if entry.path == ".circleci/config.yml" {
-                configs.push("CircleCI".to_string());
-            } else if entry.path == ".travis.yml" {
-                configs.push("Travis CI".to_string());
-            } else if entry.path == "Jenkinsfile" {
-                configs.push("Jenkins".to_string());
-            } else if entry.path == ".gitlab-ci.yml" {
-                configs.push("GitLab CI".to_string());
-            } else if entry.path == "azure-pipelines.yml" {
-                configs.push("Azure Pipelines".to_string());
+        if coverage.has_runtimes() {
+            let eol = coverage.eol_versions(chrono::Utc::now().date_naive());
+            if eol.is_empty() {
+                score += 5.0;
+            } else {
+                findings.push(Finding::warning(format!(
+                    "CI matrix includes end-of-life runtimes: {}",
+                    eol.join(", ")
+                )).with_rule(&rules::CI_EOL_RUNTIMES).scored(0.0, 5.0));
             }
         }
 
-        configs
+        score
+    }
+
+    fn join(values: &std::collections::BTreeSet<String>) -> String {
+        values.iter().map(String::as_str).collect::<Vec<_>>().join(", ")
     }
 }
 
@@ -39,6 +100,14 @@ impl Analyzer for CiCdAnalyzer {
         "CI/CD"
     }
 
+    fn id(&self) -> &str {
+        "ci-cd"
+    }
+
+    fn description(&self) -> &str {
+        "CI configuration and the platforms, runtimes and features it builds"
+    }
+
     fn weight(&self) -> f64 {
         0.20
     }
@@ -47,22 +116,40 @@ impl Analyzer for CiCdAnalyzer {
         let mut score = 0.0;
         let mut findings = Vec::new();
 
-        let ci_configs = self.detect_ci_configs(&repo_data.tree);
+        // The job matrix and MSRV come from file contents; don't score them as absent
+        let needed = repo_data.ci_files.iter().map(|f| f.path.as_str()).chain(["Cargo.toml"]);
+        let unfetched = repo_data.availability.unfetched(needed);
+        if !unfetched.is_empty() {
+            findings.push(
+                Finding::unknown(format!("CI configuration could not be fetched: {}", unfetched.join(", ")))
+                    .with_rule(&rules::CI_CONFIGURED)
+            );
+            return Ok(AnalysisResult::unknown("Unable to assess CI/CD - configuration files unavailable", findings));
+        }
+
+        let ci_configs = self.detect_ci_configs(&repo_data.ci_files);
 
         if ci_configs.is_empty() {
-            findings.push(Finding::missing("No CI/CD configuration detected"));
+            findings.push(
+                Finding::missing("No CI/CD configuration detected")
+                    .with_rule(&rules::CI_CONFIGURED)
+                    .scored(0.0, 100.0)
+            );
         } else {
             // GitHub Actions workflow
             let has_github_actions = ci_configs.iter().any(|c| c.contains("GitHub Actions"));
             if has_github_actions {
                 score += 50.0;
-                findings.push(Finding::positive("GitHub Actions configured"));
+                findings.push(Finding::positive("GitHub Actions configured").with_rule(&rules::CI_CONFIGURED));
 
                 // Count workflows
                 let workflow_count = ci_configs.iter().filter(|c| c.contains("GitHub Actions")).count();
                 if workflow_count > 1 {
                     score += 15.0;
-                    findings.push(Finding::positive(format!("Multiple workflows configured ({})", workflow_count)));
+                    findings.push(
+                        Finding::positive(format!("Multiple workflows configured ({})", workflow_count))
+                            .with_rule(&rules::CI_CONFIGURED)
+                    );
                 }
             }
 
@@ -71,11 +158,11 @@ impl Analyzer for CiCdAnalyzer {
             if other_ci && !has_github_actions {
                 score += 40.0;
                 for config in &ci_configs {
-                    findings.push(Finding::positive(format!("CI configured: {}", config)));
+                    findings.push(Finding::positive(format!("CI configured: {}", config)).with_rule(&rules::CI_CONFIGURED));
                 }
             } else if other_ci {
                 for config in ci_configs.iter().filter(|c| !c.contains("GitHub Actions")) {
-                    findings.push(Finding::positive(format!("Additional CI: {}", config)));
+                    findings.push(Finding::positive(format!("Additional CI: {}", config)).with_rule(&rules::CI_CONFIGURED));
                 }
             }
 
@@ -83,8 +170,10 @@ impl Analyzer for CiCdAnalyzer {
             // For MVP, we'll give points just for having CI configured
             if !ci_configs.is_empty() {
                 score += 20.0;
-                findings.push(Finding::positive("CI/CD pipeline established"));
+                findings.push(Finding::positive("CI/CD pipeline established").with_rule(&rules::CI_CONFIGURED));
             }
+
+            score += self.score_matrix(repo_data, &mut findings);
         }
 
         let details = if ci_configs.is_empty() {
@@ -94,9 +183,28 @@ impl Analyzer for CiCdAnalyzer {
         };
 
         Ok(AnalysisResult {
-            score,
+            score: score.min(100.0),
             details,
             findings,
+            tables: Vec::new(),
+            assessment: Assessment::Scored,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::TreeEntry;
+
+    #[tokio::test]
+    async fn test_unfetched_workflow_is_unknown() {
+        let tree = vec![TreeEntry::blob(".github/workflows/ci.yml")];
+        let mut repo_data = RepoData::for_tests(tree, &[]);
+        repo_data.availability.record_unfetched_files(vec![".github/workflows/ci.yml".to_string()], 1);
+
+        let result = CiCdAnalyzer.analyze(&repo_data).await.unwrap();
+        assert_eq!(result.assessment, Assessment::Unknown);
+        assert!(result.findings[0].message.contains(".github/workflows/ci.yml"));
+    }
+}
@@ -0,0 +1,274 @@
+use async_trait::async_trait;
+use crate::codeowners::CODEOWNERS_PATHS;
+use crate::community;
+use crate::github::{CommunityProfileFile, CommunityProfileFiles, RepoData};
+use crate::Result;
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding, Rule};
+
+/// The entry for a file in GitHub's community profile, which also reports organization defaults.
+type ProfileFile = fn(&CommunityProfileFiles) -> Option<&CommunityProfileFile>;
+
+/// Single-file community health checks: file name stem, points, description, rule and
+/// community profile entry.
+const COMMUNITY_FILES: &[(&str, f64, &str, Rule, Option<ProfileFile>)] = &[
+    ("CODE_OF_CONDUCT", 20.0, "Code of conduct", rules::CODE_OF_CONDUCT, Some(|files| files.code_of_conduct_file.as_ref())),
+    ("CONTRIBUTING", 15.0, "Contributing guide", rules::CONTRIBUTING, Some(|files| files.contributing.as_ref())),
+    ("SUPPORT", 10.0, "Support guide", rules::SUPPORT, None),
+];
+
+pub struct CommunityAnalyzer;
+
+impl CommunityAnalyzer {
+    fn check_issue_templates(&self, repo_data: &RepoData, findings: &mut Vec<Finding>) -> f64 {
+        let templates = community::issue_templates(&repo_data.tree);
+
+        if templates.is_empty() {
+            // Organization defaults can't be inspected for forms, so they count in full
+            if let Some(owner) = repo_data.inherited_community_file(|files| files.issue_template.as_ref()) {
+                findings.push(
+                    Finding::positive(format!("Issue templates inherited from {}/.github", owner))
+                        .with_rule(&rules::ISSUE_TEMPLATES)
+                );
+                return 15.0;
+            }
+
+            findings.push(Finding::missing("No issue templates").with_rule(&rules::ISSUE_TEMPLATES).scored(0.0, 15.0));
+            return 0.0;
+        }
+
+        let mut score = 10.0;
+        if !templates.forms.is_empty() {
+            score += 5.0;
+            findings.push(Finding::positive(format!("{} issue form(s)", templates.forms.len())).with_rule(&rules::ISSUE_FORMS));
+        }
+        if !templates.markdown.is_empty() {
+            findings.push(
+                Finding::positive(format!("{} Markdown issue template(s)", templates.markdown.len()))
+                    .with_rule(&rules::ISSUE_TEMPLATES)
+            );
+        }
+        if let Some(path) = &templates.legacy {
+            findings.push(
+                Finding::positive(format!("Issue template ({})", path))
+                    .with_rule(&rules::ISSUE_TEMPLATES)
+                    .at(path.to_string(), None)
+            );
+        }
+        if templates.forms.is_empty() {
+            findings.push(
+                Finding::warning("No YAML issue forms; forms enforce required fields")
+                    .with_rule(&rules::ISSUE_FORMS)
+                    .scored(0.0, 5.0)
+            );
+        }
+
+        score
+    }
+
+    fn check_pull_request_templates(&self, repo_data: &RepoData, findings: &mut Vec<Finding>) -> f64 {
+        let templates = community::pull_request_templates(&repo_data.tree);
+        let inherited = repo_data.inherited_community_file(|files| files.pull_request_template.as_ref());
+
+        match (templates.as_slice(), inherited) {
+            ([], Some(owner)) => {
+                findings.push(
+                    Finding::positive(format!("Pull request template inherited from {}/.github", owner))
+                        .with_rule(&rules::PULL_REQUEST_TEMPLATE)
+                );
+                15.0
+            }
+            ([], None) => {
+                findings.push(
+                    Finding::missing("No pull request template")
+                        .with_rule(&rules::PULL_REQUEST_TEMPLATE)
+                        .scored(0.0, 15.0)
+                );
+                0.0
+            }
+            ([path], _) => {
+                findings.push(
+                    Finding::positive(format!("Pull request template ({})", path))
+                        .with_rule(&rules::PULL_REQUEST_TEMPLATE)
+                        .at(path.to_string(), None)
+                );
+                15.0
+            }
+            _ => {
+                findings.push(
+                    Finding::positive(format!("{} pull request templates", templates.len()))
+                        .with_rule(&rules::PULL_REQUEST_TEMPLATE)
+                );
+                15.0
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Analyzer for CommunityAnalyzer {
+    fn name(&self) -> &str {
+        "Community"
+    }
+
+    fn id(&self) -> &str {
+        "community"
+    }
+
+    fn description(&self) -> &str {
+        "Code of conduct, issue and pull request templates, CODEOWNERS, governance and funding"
+    }
+
+    fn weight(&self) -> f64 {
+        0.05
+    }
+
+    async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult> {
+        let mut score = 0.0;
+        let mut findings = Vec::new();
+        let tree = &repo_data.tree;
+
+        for (stem, points, description, rule, profile_file) in COMMUNITY_FILES {
+            let inherited = profile_file.and_then(|file| repo_data.inherited_community_file(file));
+            match (community::find_file(tree, stem), inherited) {
+                (Some(path), _) => {
+                    score += points;
+                    findings.push(
+                        Finding::positive(format!("{} ({})", description, path))
+                            .with_rule(rule)
+                            .at(path.to_string(), None)
+                    );
+                }
+                (None, Some(owner)) => {
+                    score += points;
+                    findings.push(
+                        Finding::positive(format!("{} inherited from {}/.github", description, owner)).with_rule(rule)
+                    );
+                }
+                (None, None) => findings.push(
+                    Finding::missing(format!("No {} ({}.md)", description.to_lowercase(), stem))
+                        .with_rule(rule)
+                        .scored(0.0, *points)
+                ),
+            }
+        }
+
+        score += self.check_issue_templates(repo_data, &mut findings);
+        score += self.check_pull_request_templates(repo_data, &mut findings);
+
+        // CODEOWNERS has no extension and its name is case-sensitive
+        let codeowners = CODEOWNERS_PATHS.iter()
+            .find(|path| tree.iter().any(|e| e.path == **path));
+        match codeowners {
+            Some(path) => {
+                score += 10.0;
+                findings.push(
+                    Finding::positive(format!("Code owners defined ({})", path))
+                        .with_rule(&rules::CODEOWNERS)
+                        .at(path.to_string(), None)
+                );
+            }
+            None => findings.push(Finding::missing("No CODEOWNERS file").with_rule(&rules::CODEOWNERS).scored(0.0, 10.0)),
+        }
+
+        match community::find_file(tree, "GOVERNANCE").or_else(|| community::find_file(tree, "MAINTAINERS")) {
+            Some(path) => {
+                score += 10.0;
+                findings.push(
+                    Finding::positive(format!("Governance documented ({})", path))
+                        .with_rule(&rules::GOVERNANCE)
+                        .at(path.to_string(), None)
+                );
+            }
+            None => findings.push(
+                Finding::missing("No GOVERNANCE or MAINTAINERS file").with_rule(&rules::GOVERNANCE).scored(0.0, 10.0)
+            ),
+        }
+
+        // GitHub only reads the funding file from .github/
+        if tree.iter().any(|e| e.path == ".github/FUNDING.yml") {
+            score += 5.0;
+            findings.push(
+                Finding::positive("Funding links configured (.github/FUNDING.yml)")
+                    .with_rule(&rules::FUNDING)
+                    .at(".github/FUNDING.yml", None)
+            );
+        } else {
+            findings.push(Finding::missing("No .github/FUNDING.yml").with_rule(&rules::FUNDING).scored(0.0, 5.0));
+        }
+
+        let present = findings.iter().filter(|f| matches!(f.status, super::FindingStatus::Positive)).count();
+        let details = format!("{} community health files and templates found", present);
+
+        Ok(AnalysisResult {
+            score,
+            details,
+            findings,
+            tables: Vec::new(),
+            assessment: Assessment::Scored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::TreeEntry;
+
+    fn blobs(paths: &[&str]) -> Vec<TreeEntry> {
+        paths.iter().copied().map(TreeEntry::blob).collect()
+    }
+
+    #[tokio::test]
+    async fn test_scoring() {
+        let complete = RepoData::for_tests(blobs(&[
+            "CODE_OF_CONDUCT.md",
+            ".github/CONTRIBUTING.md",
+            "docs/support.md",
+            ".github/ISSUE_TEMPLATE/bug.yml",
+            ".github/pull_request_template.md",
+            ".github/CODEOWNERS",
+            "MAINTAINERS",
+            ".github/FUNDING.yml",
+        ]), &[]);
+        let result = CommunityAnalyzer.analyze(&complete).await.unwrap();
+        assert_eq!(result.score, 100.0);
+        assert!(result.findings.iter().all(|f| !f.status.is_failure()));
+
+        let partial = RepoData::for_tests(blobs(&["CONTRIBUTING.md", ".github/ISSUE_TEMPLATE/bug.md", "FUNDING.yml"]), &[]);
+        let result = CommunityAnalyzer.analyze(&partial).await.unwrap();
+        // Contributing guide and Markdown issue templates; funding links only count in .github/
+        assert_eq!(result.score, 15.0 + 10.0);
+        let failed: Vec<&str> = result.findings.iter()
+            .filter(|f| f.status.is_failure())
+            .filter_map(|f| f.rule_id.as_deref())
+            .collect();
+        assert_eq!(failed, ["COM001", "COM003", "COM005", "COM006", "COM007", "COM008", "COM009"]);
+        let lost: f64 = result.findings.iter().map(|f| f.max_points - f.points).sum();
+        assert_eq!(result.score + lost, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_inherited_files() {
+        let mut repo_data = RepoData::for_tests(Vec::new(), &[]);
+        let missing = CommunityAnalyzer.analyze(&repo_data).await.unwrap();
+        assert_eq!(missing.score, 0.0);
+
+        repo_data.tree = blobs(&[".github/ISSUE_TEMPLATE/bug.md"]);
+        repo_data.community_profile = Some(serde_json::from_str(r#"{
+            "health_percentage": 85,
+            "files": {
+                "code_of_conduct_file": {"html_url": "https://github.com/Owner/.github/blob/main/CODE_OF_CONDUCT.md"},
+                "issue_template": {"html_url": "https://github.com/owner/.github/blob/main/.github/ISSUE_TEMPLATE"},
+                "pull_request_template": {"html_url": "https://github.com/owner/.github/blob/main/.github/pull_request_template.md"}
+            }
+        }"#).unwrap());
+        let result = CommunityAnalyzer.analyze(&repo_data).await.unwrap();
+
+        // Code of conduct and pull request template inherited; the repository's own issue template
+        // takes precedence over the organization's, without forms
+        assert_eq!(result.score, 20.0 + 10.0 + 15.0);
+        assert!(result.findings.iter().any(|f| f.message == "Code of conduct inherited from owner/.github"));
+        assert!(result.findings.iter().any(|f| f.message == "Pull request template inherited from owner/.github"));
+        assert!(result.findings.iter().any(|f| f.is_rule(&rules::ISSUE_FORMS) && f.status.is_failure()));
+    }
+}
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use crate::github::RepoData;
+use crate::languages::LanguageBreakdown;
 use crate::Result;
-use super::{Analyzer, AnalysisResult, Finding};
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding};
 
 pub struct DependenciesAnalyzer;
 
@@ -31,7 +32,7 @@ impl DependenciesAnalyzer {
         files
     }
 
-    fn estimate_dependency_count(&self, tree: &[crate::github::TreeEntry]) -> usize {
+    fn estimate_dependency_count(&self, tree: &[crate::github::TreeEntry], languages: &LanguageBreakdown) -> usize {
         // Simple heuristic: assume projects with dep files have dependencies
         let dep_files = self.find_dependency_files(tree);
 
@@ -42,13 +43,7 @@ impl DependenciesAnalyzer {
         // For now, we'll use a rough estimate based on project size
         // In a full implementation, we'd fetch and parse these files
         let code_files: usize = tree.iter()
-            .filter(|e| {
-                e.entry_type == "blob" && (
-                    e.path.ends_with(".rs") || e.path.ends_with(".js") ||
-                    e.path.ends_with(".ts") || e.path.ends_with(".py") ||
-                    e.path.ends_with(".go") || e.path.ends_with(".java")
-                )
-            })
+            .filter(|e| e.entry_type == "blob" && languages.language_of(&e.path).is_some())
             .count();
 
         // Rough estimate: small project < 50 files, medium < 200, large > 200
@@ -68,6 +63,14 @@ impl Analyzer for DependenciesAnalyzer {
         "Dependencies"
     }
 
+    fn id(&self) -> &str {
+        "dependencies"
+    }
+
+    fn description(&self) -> &str {
+        "Dependency manifests and maintenance status"
+    }
+
     fn weight(&self) -> f64 {
         0.20
     }
@@ -79,15 +82,19 @@ impl Analyzer for DependenciesAnalyzer {
         let dep_files = self.find_dependency_files(&repo_data.tree);
 
         if dep_files.is_empty() {
-            findings.push(Finding::missing("No dependency files detected"));
+            findings.push(
+                Finding::missing("No dependency files detected")
+                    .with_rule(&rules::DEPENDENCY_MANIFEST)
+                    .scored(0.0, 100.0)
+            );
         } else {
             score += 20.0;
             findings.push(Finding::positive(format!(
                 "Dependency management: {}",
                 dep_files.join(", ")
-            )));
+            )).with_rule(&rules::DEPENDENCY_MANIFEST));
 
-            let dep_count = self.estimate_dependency_count(&repo_data.tree);
+            let dep_count = self.estimate_dependency_count(&repo_data.tree, &repo_data.languages);
             if dep_count > 0 {
                 score += 20.0;
                 findings.push(Finding::positive(format!(
@@ -107,9 +114,13 @@ impl Analyzer for DependenciesAnalyzer {
             let is_archived = repo_data.repository.archived.unwrap_or(false);
             if !is_archived {
                 score += 40.0;
-                findings.push(Finding::positive("Repository is actively maintained"));
+                findings.push(Finding::positive("Repository is actively maintained").with_rule(&rules::ARCHIVED));
             } else {
-                findings.push(Finding::warning("Repository is archived - dependencies may be outdated"));
+                findings.push(
+                    Finding::warning("Repository is archived - dependencies may be outdated")
+                        .with_rule(&rules::ARCHIVED)
+                        .scored(0.0, 40.0)
+                );
             }
         }
 
@@ -123,6 +134,8 @@ impl Analyzer for DependenciesAnalyzer {
             score,
             details,
             findings,
+            tables: Vec::new(),
+            assessment: Assessment::Scored,
         })
     }
 }
@@ -1,16 +1,360 @@
 use async_trait::async_trait;
-use crate::github::RepoData;
+use crate::community;
+use crate::docs_site::{self, DocsFramework};
+use crate::github::{DataSource, RepoData};
+use crate::languages::Language;
+use crate::links::{self, BrokenLink};
+use crate::markdown::{Document, Heading};
+use crate::{rust_crates, rust_docs};
 use crate::Result;
-use super::{Analyzer, AnalysisResult, Finding};
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding, ReportTable};
+
+/// README sections readers look for, with the heading words that count as each
+/// and the points (out of 15) they are worth.
+const README_SECTIONS: &[(&str, &[&str], f64)] = &[
+    ("Installation", &["install", "setup", "set up", "build from source", "building", "download"], 4.0),
+    ("Usage", &["usage", "quick start", "quickstart", "getting started", "how to use", "tutorial", "running"], 4.0),
+    ("Examples", &["example", "demo", "sample", "showcase"], 2.0),
+    ("Configuration", &["config", "option", "setting", "environment variable", "customiz"], 1.0),
+    ("Contributing", &["contribut", "development", "developing", "hacking"], 2.0),
+    ("License", &["license", "licence", "licensing", "copyright"], 2.0),
+];
+
+const MAX_BROKEN_LINKS_REPORTED: usize = 10;
+/// Share of documented public items considered well documented
+const API_DOC_TARGET: f64 = 80.0;
 
 pub struct DocumentationAnalyzer;
 
+impl DocumentationAnalyzer {
+    /// Points (max 20) for documentation pages, a site generator, and building and publishing it.
+    fn score_docs_site(&self, repo_data: &RepoData, findings: &mut Vec<Finding>) -> f64 {
+        let sites = docs_site::detect(&repo_data.tree, &repo_data.file_contents);
+        let pages = docs_site::count_pages(&repo_data.tree, &sites, &repo_data.config.paths);
+        let mut score = 0.0;
+
+        match pages {
+            0 => findings.push(
+                Finding::missing("No documentation pages beyond the README")
+                    .with_rule(&rules::DOC_PAGES)
+                    .scored(0.0, 10.0)
+            ),
+            _ => {
+                score += if pages >= 10 { 10.0 } else if pages >= 3 { 6.0 } else { 3.0 };
+                findings.push(Finding::positive(format!("{} documentation page(s)", pages)).with_rule(&rules::DOC_PAGES));
+            }
+        }
+
+        if sites.is_empty() {
+            return score;
+        }
+        score += 5.0;
+        for site in &sites {
+            findings.push(Finding::positive(format!("{} documentation configured in {}", site.framework.name(), site.config)));
+        }
+
+        let configs = crate::ci::parse_configs(&repo_data.ci_files, &repo_data.file_contents);
+        let built = sites.iter().find_map(|site| Some((site, docs_site::built_in_ci(&configs, site.framework)?)));
+        match built {
+            Some((site, config)) => {
+                score += 3.0;
+                findings.push(
+                    Finding::positive(format!("{} docs are built in CI ({})", site.framework.name(), config.path))
+                        .with_rule(&rules::DOCS_BUILT_IN_CI)
+                        .at(config.path.clone(), None)
+                );
+            }
+            None => findings.push(
+                Finding::warning("Documentation site is not built in CI")
+                    .with_rule(&rules::DOCS_BUILT_IN_CI)
+                    .scored(0.0, 3.0)
+            ),
+        }
+
+        let repository = &repo_data.repository;
+        let published = if let Some(config) = docs_site::deployed_in_ci(&configs) {
+            Some(format!("deployed by {}", config.path))
+        } else if repository.has_pages == Some(true) {
+            Some("GitHub Pages is enabled".to_string())
+        } else if let Some(homepage) = repository.homepage.as_deref()
+            .filter(|h| !h.trim().is_empty() && !h.contains("github.com/")) {
+            Some(format!("homepage {}", homepage))
+        } else if sites.iter().any(|s| s.framework == DocsFramework::Rustdoc) {
+            Some("API docs published on docs.rs".to_string())
+        } else {
+            None
+        };
+        match published {
+            Some(how) => {
+                score += 2.0;
+                findings.push(
+                    Finding::positive(format!("Documentation is published: {}", how))
+                        .with_rule(&rules::DOCS_PUBLISHED)
+                );
+            }
+            None => findings.push(
+                Finding::warning("No published documentation site found")
+                    .with_rule(&rules::DOCS_PUBLISHED)
+                    .scored(0.0, 2.0)
+            ),
+        }
+
+        score
+    }
+
+    /// Report public-item documentation coverage, crate docs and `missing_docs` enforcement per Rust crate.
+    fn check_rust_api_docs(&self, repo_data: &RepoData, findings: &mut Vec<Finding>, tables: &mut Vec<ReportTable>) {
+        let crates = rust_crates::discover_crates(&repo_data.tree, &repo_data.file_contents);
+        let coverage = rust_docs::doc_coverage(&crates, &repo_data.file_contents);
+        if coverage.is_empty() {
+            return;
+        }
+
+        let mut table = ReportTable::new(
+            "Rust API documentation by crate",
+            &["Crate", "Public items", "Documented", "Coverage", "Crate docs", "missing_docs denied"],
+        );
+
+        for (name, docs) in &coverage {
+            if let Some(percent) = docs.percent() {
+                let message = format!(
+                    "Crate `{}`: {}/{} public items documented ({:.1}%)",
+                    name, docs.documented_items, docs.public_items, percent
+                );
+                let finding = if percent >= API_DOC_TARGET { Finding::positive(message) } else { Finding::warning(message) };
+                findings.push(finding.with_rule(&rules::API_DOCS));
+            }
+            if !docs.crate_docs {
+                findings.push(
+                    Finding::warning(format!("Crate `{}` has no crate-level `//!` documentation", name))
+                        .with_rule(&rules::CRATE_DOCS)
+                );
+            }
+            if docs.deny_missing_docs {
+                findings.push(Finding::positive(format!("Crate `{}` denies `missing_docs`", name)).with_rule(&rules::API_DOCS));
+            }
+            if docs.unparsed_files > 0 {
+                findings.push(Finding::warning(format!(
+                    "Crate `{}`: {} source file(s) could not be parsed",
+                    name, docs.unparsed_files
+                )).with_rule(&rules::API_DOCS));
+            }
+
+            table.push_row(vec![
+                format!("`{}`", name),
+                docs.public_items.to_string(),
+                docs.documented_items.to_string(),
+                docs.percent().map(|p| format!("{:.1}%", p)).unwrap_or_else(|| "-".to_string()),
+                if docs.crate_docs { "yes" } else { "no" }.to_string(),
+                if docs.deny_missing_docs { "yes" } else { "no" }.to_string(),
+            ]);
+        }
+
+        tables.push(table);
+    }
+
+    /// Reconcile GitHub's community profile with tree-based detection and explain disagreements.
+    fn check_community_profile(&self, repo_data: &RepoData, findings: &mut Vec<Finding>, tables: &mut Vec<ReportTable>) {
+        let Some(profile) = &repo_data.community_profile else { return };
+        let owner = repo_data.owner();
+        let tree = &repo_data.tree;
+        let files = &profile.files;
+
+        let message = format!("GitHub community profile health: {}%", profile.health_percentage);
+        let finding = if profile.health_percentage >= 80 { Finding::positive(message) } else { Finding::warning(message) };
+        findings.push(finding.with_rule(&rules::COMMUNITY_PROFILE));
+
+        let checks = [
+            ("README", files.readme.as_ref(), repo_data.readme_content.is_some()),
+            ("License", files.license.as_ref(), repo_data.has_license),
+            ("Code of conduct", files.code_of_conduct_file.as_ref(), community::find_file(tree, "CODE_OF_CONDUCT").is_some()),
+            ("Contributing guide", files.contributing.as_ref(), community::find_file(tree, "CONTRIBUTING").is_some()),
+            ("Issue templates", files.issue_template.as_ref(), !community::issue_templates(tree).is_empty()),
+            ("Pull request template", files.pull_request_template.as_ref(), !community::pull_request_templates(tree).is_empty()),
+        ];
+
+        let mut table = ReportTable::new("GitHub community profile", &["File", "GitHub", "repo-health"]);
+        for (name, github, detected) in checks {
+            let inherited = github.is_some_and(|f| f.is_inherited(owner));
+            let github_state = match github {
+                Some(_) if inherited => format!("inherited from {}/.github", owner),
+                Some(_) => "found".to_string(),
+                None => "not found".to_string(),
+            };
+            table.push_row(vec![
+                name.to_string(),
+                github_state,
+                if detected { "found" } else { "not found" }.to_string(),
+            ]);
+
+            match (github.is_some(), detected) {
+                (true, false) if inherited => findings.push(Finding::positive(format!(
+                    "{} is inherited from the {}/.github repository, outside this repository's tree",
+                    name, owner
+                )).with_rule(&rules::COMMUNITY_PROFILE)),
+                (true, false) => findings.push(Finding::warning(format!(
+                    "GitHub lists a {} that repo-health did not find in the tree",
+                    name.to_lowercase()
+                )).with_rule(&rules::COMMUNITY_PROFILE)),
+                (false, true) => findings.push(Finding::warning(format!(
+                    "repo-health found a {} that GitHub's community profile does not recognise",
+                    name.to_lowercase()
+                )).with_rule(&rules::COMMUNITY_PROFILE)),
+                _ => {}
+            }
+        }
+
+        tables.push(table);
+    }
+
+    /// Warn about relative links and anchors that don't resolve, and failed external links.
+    fn check_links(&self, repo_data: &RepoData, findings: &mut Vec<Finding>, tables: &mut Vec<ReportTable>) {
+        let documents = links::documents(
+            &repo_data.tree, repo_data.readme_content.as_deref(), &repo_data.file_contents, &repo_data.config.paths,
+        );
+        if documents.is_empty() {
+            return;
+        }
+
+        let internal = links::check_internal(&documents, &repo_data.tree);
+        let external = repo_data.broken_external_links.as_deref().unwrap_or_default();
+        let link_count: usize = documents.values().map(|d| d.links.len() + d.images.len()).sum();
+
+        if internal.is_empty() {
+            findings.push(Finding::positive(format!(
+                "All relative links resolve in {} documentation file(s)",
+                documents.len()
+            )).with_rule(&rules::BROKEN_LINK));
+        }
+        if repo_data.broken_external_links.is_some() && external.is_empty() {
+            findings.push(Finding::positive("All checked external links resolve").with_rule(&rules::BROKEN_LINK));
+        }
+
+        let broken: Vec<&BrokenLink> = internal.iter().chain(external).collect();
+        if broken.is_empty() {
+            return;
+        }
+
+        for link in broken.iter().take(MAX_BROKEN_LINKS_REPORTED) {
+            findings.push(Finding::warning(format!(
+                "Broken link \"{}\" in {}:{}: {}",
+                link.text, link.file, link.line, link.reason
+            )).with_rule(&rules::BROKEN_LINK).at(link.file.clone(), Some(link.line)));
+        }
+        if broken.len() > MAX_BROKEN_LINKS_REPORTED {
+            findings.push(Finding::warning(format!(
+                "{} more broken links ({} links checked)",
+                broken.len() - MAX_BROKEN_LINKS_REPORTED, link_count
+            )).with_rule(&rules::BROKEN_LINK));
+        }
+
+        let mut table = ReportTable::new("Broken documentation links", &["File", "Line", "Text", "Target", "Problem"]);
+        for link in broken {
+            table.push_row(vec![
+                link.file.clone(),
+                link.line.to_string(),
+                link.text.clone(),
+                format!("`{}`", link.destination),
+                link.reason.clone(),
+            ]);
+        }
+        tables.push(table);
+    }
+
+    /// Points (max 20) for expected README sections, code examples and a clean heading hierarchy.
+    fn score_readme_structure(&self, readme: &str, findings: &mut Vec<Finding>, tables: &mut Vec<ReportTable>) -> f64 {
+        let document = Document::parse(readme);
+        let mut score = 0.0;
+
+        let mut table = ReportTable::new("README sections", &["Section", "Heading"]);
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+
+        // Each heading counts towards the first section it names
+        let section_of = |heading: &Heading| {
+            let text = heading.text.to_lowercase();
+            README_SECTIONS.iter().position(|(_, synonyms, _)| synonyms.iter().any(|s| starts_word(&text, s)))
+        };
+
+        for (index, (section, _, points)) in README_SECTIONS.iter().enumerate() {
+            let heading = document.headings.iter().find(|h| section_of(h) == Some(index));
+
+            match heading {
+                Some(heading) => {
+                    score += points;
+                    found.push(*section);
+                    table.push_row(vec![section.to_string(), format!("{} (line {})", heading.text, heading.line)]);
+                }
+                None => {
+                    missing.push((*section, *points));
+                    table.push_row(vec![section.to_string(), "—".to_string()]);
+                }
+            }
+        }
+        tables.push(table);
+
+        if !found.is_empty() {
+            findings.push(
+                Finding::positive(format!("README sections found: {}", found.join(", ")))
+                    .with_rule(&rules::README_SECTION)
+            );
+        }
+        for (section, points) in missing {
+            findings.push(
+                Finding::warning(format!("README has no {} section", section))
+                    .with_rule(&rules::README_SECTION)
+                    .scored(0.0, points)
+            );
+        }
+
+        if document.code_blocks.is_empty() {
+            findings.push(Finding::warning("README has no code blocks").with_rule(&rules::README_CODE_BLOCKS).scored(0.0, 3.0));
+        } else {
+            score += 3.0;
+            findings.push(
+                Finding::positive(format!("README has {} code block(s)", document.code_blocks.len()))
+                    .with_rule(&rules::README_CODE_BLOCKS)
+            );
+        }
+
+        let problems = document.hierarchy_problems();
+        if document.headings.is_empty() {
+            findings.push(
+                Finding::warning("README lacks structured sections")
+                    .with_rule(&rules::README_STRUCTURE)
+                    .scored(0.0, 2.0)
+            );
+        } else if problems.is_empty() {
+            score += 2.0;
+        }
+        // Checks reported by several findings share their points
+        let share = 2.0 / problems.len().max(1) as f64;
+        for problem in problems {
+            findings.push(
+                Finding::warning(format!("README heading hierarchy: {}", problem))
+                    .with_rule(&rules::README_STRUCTURE)
+                    .scored(0.0, share)
+            );
+        }
+
+        score
+    }
+}
+
 #[async_trait]
 impl Analyzer for DocumentationAnalyzer {
     fn name(&self) -> &str {
         "Documentation"
     }
 
+    fn id(&self) -> &str {
+        "documentation"
+    }
+
+    fn description(&self) -> &str {
+        "README structure, documentation site, API docs, links and license"
+    }
+
     fn weight(&self) -> f64 {
         0.20
     }
@@ -18,62 +362,54 @@ impl Analyzer for DocumentationAnalyzer {
     async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult> {
         let mut score = 0.0;
         let mut findings = Vec::new();
+        let mut tables = Vec::new();
+        let availability = &repo_data.availability;
+
+        // The README carries most of the score; without it the category can't be judged
+        if let Some(reason) = availability.gap(DataSource::Readme) {
+            findings.push(Finding::unknown(format!("README could not be fetched ({})", reason)).with_rule(&rules::README));
+            return Ok(AnalysisResult::unknown("Unable to assess documentation - README unavailable", findings));
+        }
 
         // Check README exists and quality
         if let Some(readme) = &repo_data.readme_content {
             score += 40.0;
-            findings.push(Finding::positive("README.md exists"));
-
-            // Check README length
-            if readme.len() > 500 {
-                score += 10.0;
-                findings.push(Finding::positive("README has substantial content (>500 chars)"));
-            } else {
-                findings.push(Finding::warning("README is quite short (<500 chars)"));
-            }
+            findings.push(Finding::positive("README.md exists").with_rule(&rules::README));
 
-            // Check for sections (markdown headers)
-            if readme.contains("##") {
-                score += 10.0;
-                findings.push(Finding::positive("README has sections"));
-            } else {
-                findings.push(Finding::warning("README lacks structured sections"));
-            }
+            score += self.score_readme_structure(readme, &mut findings, &mut tables);
         } else {
-            findings.push(Finding::missing("README.md not found"));
+            findings.push(Finding::missing("README.md not found").with_rule(&rules::README).scored(0.0, 60.0));
         }
 
-        // Check for docs folder
-        let has_docs = repo_data.tree.iter().any(|entry| {
-            entry.path.starts_with("docs/") || entry.path.starts_with("documentation/")
-        });
+        self.check_links(repo_data, &mut findings, &mut tables);
 
-        if has_docs {
-            score += 20.0;
-            findings.push(Finding::positive("Documentation directory exists"));
-        } else {
-            findings.push(Finding::missing("No dedicated documentation directory"));
+        if repo_data.languages.is_present(Language::Rust) {
+            self.check_rust_api_docs(repo_data, &mut findings, &mut tables);
         }
 
-        // Check for LICENSE
-        if repo_data.has_license {
+        // Check for documentation beyond the README
+        score += self.score_docs_site(repo_data, &mut findings);
+
+        // Check for LICENSE; when its status is unknown, score out of the remaining points.
+        // The contributing guide is a community file, scored by the Community analyzer.
+        let mut max_score = 90.0;
+        if let Some(reason) = availability.gap(DataSource::License) {
+            max_score -= 10.0;
+            findings.push(Finding::unknown(format!("License could not be checked ({})", reason)).with_rule(&rules::LICENSE));
+        } else if repo_data.has_license {
             score += 10.0;
-            findings.push(Finding::positive("LICENSE file exists"));
+            findings.push(Finding::positive("LICENSE file exists").with_rule(&rules::LICENSE));
         } else {
-            findings.push(Finding::missing("LICENSE file not found"));
+            findings.push(Finding::missing("LICENSE file not found").with_rule(&rules::LICENSE).scored(0.0, 10.0));
         }
 
-        // Check for CONTRIBUTING.md
-        let has_contributing = repo_data.tree.iter().any(|entry| {
-            entry.path.eq_ignore_ascii_case("CONTRIBUTING.md")
-        });
-
-        if has_contributing {
-            score += 10.0;
-            findings.push(Finding::positive("CONTRIBUTING.md exists"));
-        } else {
-            findings.push(Finding::missing("CONTRIBUTING.md not found"));
+        if let Some(reason) = availability.gap(DataSource::CommunityProfile) {
+            findings.push(
+                Finding::unknown(format!("GitHub community profile could not be fetched ({})", reason))
+                    .with_rule(&rules::COMMUNITY_PROFILE)
+            );
         }
+        self.check_community_profile(repo_data, &mut findings, &mut tables);
 
         let details = format!(
             "Found {} documentation elements. README quality: {}.",
@@ -81,10 +417,98 @@ impl Analyzer for DocumentationAnalyzer {
             if repo_data.readme_content.is_some() { "Present" } else { "Missing" }
         );
 
+        // Scored checks are worth their share of the points that could be assessed
+        for finding in &mut findings {
+            finding.points *= 100.0 / max_score;
+            finding.max_points *= 100.0 / max_score;
+        }
+
         Ok(AnalysisResult {
-            score,
+            score: score / max_score * 100.0,
             details,
             findings,
+            tables,
+            assessment: Assessment::Scored,
         })
     }
 }
+
+/// Whether `word` appears in `text` at the start of a word, so "install" matches
+/// "Installing" but not "Uninstall".
+fn starts_word(text: &str, word: &str) -> bool {
+    text.match_indices(word)
+        .any(|(i, _)| !text[..i].chars().next_back().is_some_and(char::is_alphanumeric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::FindingStatus;
+    use crate::github::TreeEntry;
+
+    #[test]
+    fn test_readme_structure() {
+        let readme = "# app\n\n## Quick Start\n\n```sh\napp run\n```\n\n## Installing\n\n## Licence\n\nMIT\n";
+        let mut findings = Vec::new();
+        let mut tables = Vec::new();
+
+        let score = DocumentationAnalyzer.score_readme_structure(readme, &mut findings, &mut tables);
+
+        // Installation, Usage, License, code block, clean hierarchy
+        assert_eq!(score, 4.0 + 4.0 + 2.0 + 3.0 + 2.0);
+        assert_eq!(findings[0].message, "README sections found: Installation, Usage, License");
+        assert!(findings.iter().any(|f| f.message == "README has no Examples section"));
+        assert_eq!(tables[0].rows[1], vec!["Usage".to_string(), "Quick Start (line 3)".to_string()]);
+    }
+
+    #[test]
+    fn test_readme_section_matching() {
+        let readme = "# app
+
+## Getting Started
+
+## Uninstalling
+
+## Reconfiguring
+";
+        let mut findings = Vec::new();
+        let mut tables = Vec::new();
+
+        DocumentationAnalyzer.score_readme_structure(readme, &mut findings, &mut tables);
+
+        assert_eq!(findings[0].message, "README sections found: Usage");
+        assert_eq!(tables[0].rows[0], vec!["Installation".to_string(), "—".to_string()]);
+        assert!(starts_word("building from source", "build"));
+        assert!(!starts_word("rebuild", "build"));
+    }
+
+    #[test]
+    fn test_community_profile_reconciliation() {
+        let tree = vec![TreeEntry::blob("CODE_OF_CONDUCT.md")];
+        let mut repo_data = RepoData::for_tests(tree, &[]);
+        repo_data.community_profile = Some(serde_json::from_str(r#"{
+            "health_percentage": 57,
+            "files": {
+                "contributing": {"html_url": "https://github.com/owner/.github/blob/main/CONTRIBUTING.md"},
+                "pull_request_template": {"html_url": "https://github.com/owner/repo/blob/main/.gitlab/pr.md"}
+            }
+        }"#).unwrap());
+        let mut findings = Vec::new();
+        let mut tables = Vec::new();
+
+        DocumentationAnalyzer.check_community_profile(&repo_data, &mut findings, &mut tables);
+
+        let messages: Vec<(FindingStatus, &str)> = findings.iter().map(|f| (f.status.clone(), f.message.as_str())).collect();
+        assert_eq!(messages, vec![
+            (FindingStatus::Warning, "GitHub community profile health: 57%"),
+            (FindingStatus::Warning, "repo-health found a code of conduct that GitHub's community profile does not recognise"),
+            (FindingStatus::Positive, "Contributing guide is inherited from the owner/.github repository, outside this repository's tree"),
+            (FindingStatus::Warning, "GitHub lists a pull request template that repo-health did not find in the tree"),
+        ]);
+        assert_eq!(tables[0].rows[3], vec![
+            "Contributing guide".to_string(),
+            "inherited from owner/.github".to_string(),
+            "not found".to_string(),
+        ]);
+    }
+}
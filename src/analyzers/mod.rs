@@ -3,12 +3,20 @@ mod tests;
 mod ci_cd;
 mod dependencies;
 mod bus_factor;
+mod workflow_security;
+mod community;
+mod registry;
+pub mod rules;
 
 pub use documentation::DocumentationAnalyzer;
 pub use tests::TestsAnalyzer;
 pub use ci_cd::CiCdAnalyzer;
 pub use dependencies::DependenciesAnalyzer;
-pub use bus_factor::BusFactorAnalyzer;
+pub use bus_factor::{BusFactorAnalyzer, BusFactorAlgorithm, DEFAULT_WINDOWS as DEFAULT_BUS_FACTOR_WINDOWS};
+pub use workflow_security::WorkflowSecurityAnalyzer;
+pub use community::CommunityAnalyzer;
+pub use registry::{AnalyzerRegistry, CategoryResult};
+pub use rules::{Rule, Severity};
 
 use async_trait::async_trait;
 use serde::{Serialize, Deserialize};
@@ -17,7 +25,12 @@ use crate::Result;
 
 #[async_trait]
 pub trait Analyzer: Send + Sync {
+    /// Display name used in reports
     fn name(&self) -> &str;
+    /// Stable identifier used on the command line, e.g. `bus-factor`
+    fn id(&self) -> &str;
+    /// One-line summary of what the analyzer checks
+    fn description(&self) -> &str;
     async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult>;
     fn weight(&self) -> f64;
 }
@@ -27,40 +40,221 @@ pub struct AnalysisResult {
     pub score: f64,
     pub details: String,
     pub findings: Vec<Finding>,
+    /// Supporting data rendered as tables below the findings
+    #[serde(default)]
+    pub tables: Vec<ReportTable>,
+    /// Whether `score` means anything; unscored categories are left out of the overall score
+    #[serde(default)]
+    pub assessment: Assessment,
+}
+
+/// Whether a category could be scored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Assessment {
+    #[default]
+    Scored,
+    /// The data the category depends on could not be fetched
+    Unknown,
+    /// The category does not apply to this repository
+    NotApplicable,
+}
+
+impl Assessment {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Assessment::Scored => "Scored",
+            Assessment::Unknown => "Unknown",
+            Assessment::NotApplicable => "Not applicable",
+        }
+    }
+}
+
+impl AnalysisResult {
+    /// A category whose data could not be fetched.
+    pub fn unknown(details: impl Into<String>, findings: Vec<Finding>) -> Self {
+        Self { score: 0.0, details: details.into(), findings, tables: Vec::new(), assessment: Assessment::Unknown }
+    }
+
+    /// A category that does not apply to this repository.
+    pub fn not_applicable(details: impl Into<String>, findings: Vec<Finding>) -> Self {
+        Self { score: 0.0, details: details.into(), findings, tables: Vec::new(), assessment: Assessment::NotApplicable }
+    }
+
+    pub fn is_scored(&self) -> bool {
+        self.assessment == Assessment::Scored
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportTable {
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ReportTable {
+    pub fn new(title: impl Into<String>, headers: &[&str]) -> Self {
+        Self {
+            title: title.into(),
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Finding {
     pub status: FindingStatus,
     pub message: String,
+    /// Stable ID of the check, e.g. `DOC001`; see [`rules`]
+    #[serde(default)]
+    pub rule_id: Option<String>,
+    #[serde(default)]
+    pub severity: Severity,
+    /// ID of the analyzer that reported it
+    #[serde(default)]
+    pub category: Option<String>,
+    /// File or directory the finding is about, relative to the repository root
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub remediation: Option<String>,
+    #[serde(default)]
+    pub docs_url: Option<String>,
+    /// Points the check earned out of `max_points` in its category's score of 100
+    #[serde(default)]
+    pub points: f64,
+    #[serde(default)]
+    pub max_points: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FindingStatus {
     Positive,
     Warning,
     Missing,
+    /// Could not be checked because the data was unavailable
+    Unknown,
+    /// Does not apply to this repository
+    NotApplicable,
+}
+
+impl FindingStatus {
+    /// Whether the finding points at something to fix.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, FindingStatus::Warning | FindingStatus::Missing)
+    }
 }
 
 impl Finding {
-    pub fn positive(message: impl Into<String>) -> Self {
+    fn new(status: FindingStatus, message: impl Into<String>) -> Self {
+        let severity = match status {
+            FindingStatus::Missing => Severity::Medium,
+            FindingStatus::Warning => Severity::Low,
+            _ => Severity::Info,
+        };
         Self {
-            status: FindingStatus::Positive,
+            status,
             message: message.into(),
+            rule_id: None,
+            severity,
+            category: None,
+            path: None,
+            line: None,
+            remediation: None,
+            docs_url: None,
+            points: 0.0,
+            max_points: 0.0,
         }
     }
 
+    pub fn positive(message: impl Into<String>) -> Self {
+        Self::new(FindingStatus::Positive, message)
+    }
+
     pub fn warning(message: impl Into<String>) -> Self {
-        Self {
-            status: FindingStatus::Warning,
-            message: message.into(),
-        }
+        Self::new(FindingStatus::Warning, message)
     }
 
     pub fn missing(message: impl Into<String>) -> Self {
-        Self {
-            status: FindingStatus::Missing,
-            message: message.into(),
+        Self::new(FindingStatus::Missing, message)
+    }
+
+    pub fn unknown(message: impl Into<String>) -> Self {
+        Self::new(FindingStatus::Unknown, message)
+    }
+
+    pub fn not_applicable(message: impl Into<String>) -> Self {
+        Self::new(FindingStatus::NotApplicable, message)
+    }
+
+    /// Tags the finding with the check that produced it. Failed checks also
+    /// take the rule's severity and remediation.
+    pub fn with_rule(mut self, rule: &Rule) -> Self {
+        self.rule_id = Some(rule.id.to_string());
+        self.category = Some(rule.category.to_string());
+        self.docs_url = Some(rule.docs_url());
+        if self.status.is_failure() {
+            self.severity = rule.severity;
+            self.remediation = Some(rule.remediation.to_string());
         }
+        self
+    }
+
+    pub fn is_rule(&self, rule: &Rule) -> bool {
+        self.rule_id.as_deref() == Some(rule.id)
+    }
+
+    /// Points the finding at a file or directory, optionally a line in it.
+    pub fn at(mut self, path: impl Into<String>, line: Option<usize>) -> Self {
+        self.path = Some(path.into());
+        self.line = line;
+        self
+    }
+
+    /// Records what the check contributes to the category score, so that
+    /// suppressing the finding can leave the check out of it.
+    pub fn scored(mut self, points: f64, max_points: f64) -> Self {
+        self.points = points;
+        self.max_points = max_points;
+        self
+    }
+
+    /// `path` or `path:line`, if the finding has a location.
+    pub fn location(&self) -> Option<String> {
+        let path = self.path.as_deref()?;
+        Some(match self.line {
+            Some(line) => format!("{}:{}", path, line),
+            None => path.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod finding_tests {
+    use super::*;
+
+    #[test]
+    fn test_with_rule_sets_remediation_for_failures_only() {
+        let missing = Finding::missing("No LICENSE file").with_rule(&rules::LICENSE);
+        assert_eq!(missing.rule_id.as_deref(), Some("DOC011"));
+        assert_eq!(missing.category.as_deref(), Some("documentation"));
+        assert_eq!(missing.severity, Severity::High);
+        assert!(missing.remediation.is_some());
+
+        let positive = Finding::positive("LICENSE file present").with_rule(&rules::LICENSE);
+        assert_eq!(positive.rule_id.as_deref(), Some("DOC011"));
+        assert_eq!(positive.severity, Severity::Info);
+        assert!(positive.remediation.is_none());
+
+        let located = Finding::warning("Broken link").at("docs/guide.md", Some(12));
+        assert_eq!(located.severity, Severity::Low);
+        assert_eq!(located.location().as_deref(), Some("docs/guide.md:12"));
     }
 }
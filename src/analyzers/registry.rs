@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use async_trait::async_trait;
+use crate::github::RepoData;
+use crate::suppressions::SuppressedFinding;
+use crate::{Result, RepoHealthError};
+use super::{
+    Analyzer, AnalysisResult,
+    DocumentationAnalyzer, TestsAnalyzer, CiCdAnalyzer, DependenciesAnalyzer,
+    BusFactorAnalyzer, WorkflowSecurityAnalyzer, CommunityAnalyzer,
+};
+
+/// An analyzer's result together with the metadata the report and score need.
+#[derive(Debug, Clone)]
+pub struct CategoryResult {
+    pub id: String,
+    pub name: String,
+    pub weight: f64,
+    pub result: AnalysisResult,
+    /// Findings accepted by a suppression, no longer in `result`
+    pub suppressed: Vec<SuppressedFinding>,
+}
+
+impl CategoryResult {
+    /// Findings without a category are attributed to `analyzer`.
+    pub fn new(analyzer: &dyn Analyzer, mut result: AnalysisResult) -> Self {
+        for finding in &mut result.findings {
+            finding.category.get_or_insert_with(|| analyzer.id().to_string());
+        }
+        Self {
+            id: analyzer.id().to_string(),
+            name: analyzer.name().to_string(),
+            weight: analyzer.weight(),
+            result,
+            suppressed: Vec::new(),
+        }
+    }
+}
+
+/// An analyzer whose weight has been overridden.
+struct Weighted {
+    inner: Box<dyn Analyzer>,
+    weight: f64,
+}
+
+#[async_trait]
+impl Analyzer for Weighted {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult> {
+        self.inner.analyze(repo_data).await
+    }
+}
+
+/// The analyzers that make up a health check, in report order.
+pub struct AnalyzerRegistry {
+    analyzers: Vec<Box<dyn Analyzer>>,
+}
+
+impl Default for AnalyzerRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl AnalyzerRegistry {
+    pub fn builtin() -> Self {
+        Self {
+            analyzers: vec![
+                Box::new(DocumentationAnalyzer),
+                Box::new(TestsAnalyzer),
+                Box::new(CiCdAnalyzer),
+                Box::new(DependenciesAnalyzer),
+                Box::new(BusFactorAnalyzer::default()),
+                Box::new(WorkflowSecurityAnalyzer),
+                Box::new(CommunityAnalyzer),
+            ],
+        }
+    }
+
+    /// Add an analyzer, replacing one with the same ID in place.
+    pub fn register(&mut self, analyzer: Box<dyn Analyzer>) {
+        match self.analyzers.iter().position(|a| a.id() == analyzer.id()) {
+            Some(index) => self.analyzers[index] = analyzer,
+            None => self.analyzers.push(analyzer),
+        }
+    }
+
+    pub fn analyzers(&self) -> &[Box<dyn Analyzer>] {
+        &self.analyzers
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn Analyzer> {
+        self.analyzers.iter().find(|a| a.id() == id).map(|a| a.as_ref())
+    }
+
+    /// Keep only the `only` analyzers (all when empty), minus the `skip` ones.
+    pub fn select(mut self, only: &[String], skip: &[String]) -> Result<Self> {
+        if let Some(unknown) = only.iter().chain(skip).find(|id| self.get(id).is_none()) {
+            return Err(RepoHealthError::UnknownAnalyzer(unknown.clone()));
+        }
+
+        self.analyzers.retain(|a| {
+            (only.is_empty() || only.iter().any(|id| id == a.id())) && !skip.iter().any(|id| id == a.id())
+        });
+        Ok(self)
+    }
+
+    /// Override weights by analyzer ID, then scale all weights to sum to 1.
+    pub fn with_weights(mut self, weights: &BTreeMap<String, f64>) -> Result<Self> {
+        if let Some(unknown) = weights.keys().find(|id| self.get(id).is_none()) {
+            return Err(RepoHealthError::UnknownAnalyzer(unknown.clone()));
+        }
+
+        let overrides: Vec<Option<f64>> = self.analyzers.iter().map(|a| weights.get(a.id()).copied()).collect();
+        let total: f64 = self.analyzers.iter().zip(&overrides).map(|(a, w)| w.unwrap_or(a.weight())).sum();
+        if total <= 0.0 {
+            return Err(RepoHealthError::InvalidConfig("at least one selected analyzer needs a positive weight".to_string()));
+        }
+
+        self.analyzers = self.analyzers.into_iter()
+            .zip(overrides)
+            .map(|(inner, weight)| {
+                let weight = weight.unwrap_or(inner.weight()) / total;
+                Box::new(Weighted { inner, weight }) as Box<dyn Analyzer>
+            })
+            .collect();
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(registry: &AnalyzerRegistry) -> Vec<&str> {
+        registry.analyzers().iter().map(|a| a.id()).collect()
+    }
+
+    #[test]
+    fn test_builtin_weights_sum_to_one() {
+        let registry = AnalyzerRegistry::builtin().with_weights(&BTreeMap::new()).unwrap();
+        let total: f64 = registry.analyzers().iter().map(|a| a.weight()).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_select() {
+        let only = vec!["tests".to_string(), "bus-factor".to_string()];
+        let registry = AnalyzerRegistry::builtin().select(&only, &[]).unwrap();
+        assert_eq!(ids(&registry), ["tests", "bus-factor"]);
+
+        let skip = vec!["community".to_string()];
+        let registry = AnalyzerRegistry::builtin().select(&[], &skip).unwrap();
+        assert_eq!(registry.analyzers().len(), 6);
+        assert!(registry.get("community").is_none());
+
+        let unknown = vec!["coverage".to_string()];
+        assert!(matches!(
+            AnalyzerRegistry::builtin().select(&unknown, &[]),
+            Err(RepoHealthError::UnknownAnalyzer(id)) if id == "coverage"
+        ));
+    }
+
+    #[test]
+    fn test_weights_are_renormalised() {
+        let only = vec!["tests".to_string(), "ci-cd".to_string()];
+        let weights = BTreeMap::from([("tests".to_string(), 0.60)]);
+        let registry = AnalyzerRegistry::builtin().select(&only, &[]).unwrap().with_weights(&weights).unwrap();
+
+        let weights: Vec<f64> = registry.analyzers().iter().map(|a| a.weight()).collect();
+        assert!((weights[0] - 0.75).abs() < 1e-9);
+        assert!((weights[1] - 0.25).abs() < 1e-9);
+        assert_eq!(registry.analyzers()[1].name(), "CI/CD");
+
+        let zero = BTreeMap::from([("tests".to_string(), 0.0), ("ci-cd".to_string(), 0.0)]);
+        let registry = AnalyzerRegistry::builtin().select(&only, &[]).unwrap();
+        assert!(matches!(registry.with_weights(&zero), Err(RepoHealthError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_register_replaces_by_id() {
+        let mut registry = AnalyzerRegistry::builtin();
+        registry.register(Box::new(BusFactorAnalyzer::new(vec![3])));
+        assert_eq!(registry.analyzers().len(), 7);
+        assert_eq!(ids(&registry)[4], "bus-factor");
+    }
+}
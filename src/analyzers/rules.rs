@@ -0,0 +1,199 @@
+//! Catalogue of the checks analyzers report, with stable IDs.
+//!
+//! IDs never change meaning once released; retired checks keep their ID unused.
+//! Each rule is documented in `docs/rules.md` under a heading named after its ID.
+
+use serde::{Deserialize, Serialize};
+
+const RULES_DOCS_URL: &str = "https://github.com/kasaiarashi/repo-health/blob/main/docs/rules.md";
+
+/// How much a failed check matters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    /// Informational; nothing to fix
+    #[default]
+    Info,
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub id: &'static str,
+    /// ID of the analyzer that reports it
+    pub category: &'static str,
+    /// Severity when the check fails
+    pub severity: Severity,
+    pub title: &'static str,
+    pub remediation: &'static str,
+}
+
+impl Rule {
+    pub fn docs_url(&self) -> String {
+        format!("{}#{}", RULES_DOCS_URL, self.id.to_lowercase())
+    }
+}
+
+macro_rules! rules {
+    ($($name:ident: $id:literal, $category:literal, $severity:ident, $title:literal, $remediation:literal;)*) => {
+        $(
+            pub const $name: Rule = Rule {
+                id: $id,
+                category: $category,
+                severity: Severity::$severity,
+                title: $title,
+                remediation: $remediation,
+            };
+        )*
+
+        /// Every rule, in ID order within each category.
+        pub const ALL: &[Rule] = &[$($name),*];
+    };
+}
+
+rules! {
+    README: "DOC001", "documentation", High, "README",
+        "Add a README.md that explains what the project does, how to install it and how to use it.";
+    README_SECTION: "DOC002", "documentation", Low, "README sections",
+        "Add the missing section with a `##` heading, e.g. `## Installation` or `## Usage`.";
+    README_CODE_BLOCKS: "DOC003", "documentation", Low, "README examples",
+        "Show usage in fenced code blocks so readers can copy working commands.";
+    README_STRUCTURE: "DOC004", "documentation", Low, "README structure",
+        "Organise the README under `##` headings without skipping levels.";
+    BROKEN_LINK: "DOC005", "documentation", Medium, "Broken documentation links",
+        "Fix or remove the link; relative links must point at files in the repository and anchors at existing headings.";
+    DOC_PAGES: "DOC006", "documentation", Medium, "Documentation pages",
+        "Add documentation beyond the README, e.g. guides in `docs/` or a documentation site.";
+    DOCS_BUILT_IN_CI: "DOC007", "documentation", Low, "Documentation built in CI",
+        "Build the documentation site in CI so broken pages are caught before release.";
+    DOCS_PUBLISHED: "DOC008", "documentation", Low, "Published documentation",
+        "Publish the documentation, e.g. with GitHub Pages, and link it from the repository homepage.";
+    API_DOCS: "DOC009", "documentation", Medium, "API documentation coverage",
+        "Add `///` doc comments to public items; `#![warn(missing_docs)]` lists the undocumented ones.";
+    CRATE_DOCS: "DOC010", "documentation", Low, "Crate-level documentation",
+        "Add `//!` documentation at the top of the crate root describing what the crate is for.";
+    LICENSE: "DOC011", "documentation", High, "License",
+        "Add a LICENSE file so others know how they may use the code.";
+    COMMUNITY_PROFILE: "DOC013", "documentation", Low, "GitHub community profile",
+        "Compare the files GitHub recognises with those found in the tree; GitHub only looks in the root, `.github/` and `docs/`.";
+
+    TEST_FILES: "TST001", "tests", High, "Test files",
+        "Add automated tests next to the code or in a `tests/` directory.";
+    TEST_DIRECTORY: "TST002", "tests", Low, "Test directory",
+        "Keep tests in a conventional directory such as `tests/`, or declare their location under `[paths]` in .repo-health.toml.";
+    TEST_RATIO: "TST003", "tests", Medium, "Test-to-source ratio",
+        "Add tests for the untested parts of the code base until the ratio reaches the ecosystem's target.";
+    COVERAGE_REPORT: "TST004", "tests", Low, "Coverage report",
+        "Make sure the committed or supplied coverage report covers the project's source files.";
+    LINE_COVERAGE: "TST005", "tests", Medium, "Line coverage",
+        "Add tests for uncovered code; the per-directory table shows where coverage is lowest.";
+    BRANCH_COVERAGE: "TST006", "tests", Low, "Branch coverage",
+        "Test both outcomes of conditionals in the least-covered code.";
+    DIRECTORY_COVERAGE: "TST007", "tests", Low, "Directory coverage",
+        "Add tests for the directory with the lowest coverage.";
+    CRATE_TESTS: "TST008", "tests", Medium, "Rust crate tests",
+        "Add unit tests (`#[cfg(test)]`), doc tests or integration tests to the crate.";
+    CI_TESTS: "TST009", "tests", High, "Tests run in CI",
+        "Run the test suite in CI, e.g. a `cargo test` or `npm test` step.";
+    PR_TESTS: "TST010", "tests", Medium, "Tests on pull requests",
+        "Trigger the test workflow on `pull_request` so changes are tested before merging.";
+    UNTESTED_DIRECTORY: "TST011", "tests", Low, "Untested directories",
+        "Add tests for the directory, next to its sources or in a nearby `tests/` directory.";
+
+    CI_CONFIGURED: "CI001", "ci-cd", High, "CI configuration",
+        "Set up CI, e.g. a GitHub Actions workflow in `.github/workflows/`, to build and test every change.";
+    CI_OPERATING_SYSTEMS: "CI002", "ci-cd", Low, "Operating systems",
+        "Add the other operating systems your users run to the CI matrix.";
+    CI_MSRV: "CI003", "ci-cd", Low, "Minimum supported Rust version",
+        "Add a CI job that builds with the `rust-version` declared in Cargo.toml.";
+    CI_EOL_RUNTIMES: "CI004", "ci-cd", Medium, "End-of-life runtimes",
+        "Replace end-of-life runtime versions in the CI matrix with supported ones.";
+
+    DEPENDENCY_MANIFEST: "DEP001", "dependencies", Medium, "Dependency manifest",
+        "Declare dependencies in a manifest (Cargo.toml, package.json, ...) with a lockfile for applications.";
+    ARCHIVED: "DEP002", "dependencies", High, "Archived repository",
+        "The repository is archived and no longer maintained; consider a maintained fork.";
+
+    CONCENTRATION: "BUS001", "bus-factor", High, "Knowledge concentration",
+        "Spread knowledge through code review, pairing and documentation so no single person is irreplaceable.";
+    SINGLE_AUTHOR_DIRECTORY: "BUS002", "bus-factor", Medium, "Single-author directories",
+        "Have other contributors review and change this directory.";
+    RECENT_ACTIVITY: "BUS003", "bus-factor", Medium, "Recent activity",
+        "There were no recent commits; check whether the project is still maintained.";
+    SHRINKING_COMMUNITY: "BUS004", "bus-factor", Medium, "Shrinking contributor base",
+        "Invite contributors back, label good first issues and make reviews quick to reverse the decline.";
+    NEW_CONTRIBUTORS: "BUS005", "bus-factor", Low, "New contributors",
+        "Lower the barrier to a first contribution with good first issues and a contributing guide.";
+    RETENTION: "BUS006", "bus-factor", Low, "First-time contributor retention",
+        "Review first contributions promptly and suggest follow-up work to keep newcomers involved.";
+    CODEOWNERS_COVERAGE: "BUS007", "bus-factor", Low, "CODEOWNERS coverage",
+        "Add CODEOWNERS patterns so every file has a reviewer.";
+    UNOWNED_DIRECTORY: "BUS008", "bus-factor", Low, "Unowned directories",
+        "Add a CODEOWNERS pattern for the directory.";
+    SINGLE_OWNER_DIRECTORY: "BUS009", "bus-factor", Medium, "Single-owner directories",
+        "Add a second owner or a team to the directory's CODEOWNERS pattern.";
+
+    WORKFLOW_PERMISSIONS: "WFS001", "workflow-security", Medium, "Workflow permissions",
+        "Declare a top-level `permissions:` block with the least privilege the workflow needs, e.g. `contents: read`.";
+    UNPINNED_ACTION: "WFS002", "workflow-security", Medium, "Unpinned actions",
+        "Pin third-party actions to a full commit SHA and let Dependabot or Renovate update them.";
+    PR_TARGET_CHECKOUT: "WFS003", "workflow-security", High, "pull_request_target checkout",
+        "Don't check out pull request code in `pull_request_target` workflows; use `pull_request` instead.";
+    SCRIPT_INJECTION: "WFS004", "workflow-security", High, "Script injection",
+        "Pass untrusted event data through an `env:` variable instead of interpolating `${{ }}` into `run:`.";
+    SELF_HOSTED_RUNNER: "WFS005", "workflow-security", High, "Self-hosted runners",
+        "Use GitHub-hosted runners for public repositories, or restrict self-hosted runners to trusted workflows.";
+
+    CODE_OF_CONDUCT: "COM001", "community", Medium, "Code of conduct",
+        "Add CODE_OF_CONDUCT.md, e.g. the Contributor Covenant, and explain how to report problems.";
+    CONTRIBUTING: "COM002", "community", Medium, "Contributing guide",
+        "Add CONTRIBUTING.md explaining how to build, test and submit changes.";
+    SUPPORT: "COM003", "community", Low, "Support guide",
+        "Add SUPPORT.md pointing users to the right place for questions.";
+    ISSUE_TEMPLATES: "COM004", "community", Low, "Issue templates",
+        "Add issue templates in `.github/ISSUE_TEMPLATE/` for bug reports and feature requests.";
+    ISSUE_FORMS: "COM005", "community", Low, "Issue forms",
+        "Convert Markdown issue templates to YAML issue forms with required fields.";
+    PULL_REQUEST_TEMPLATE: "COM006", "community", Low, "Pull request template",
+        "Add `.github/pull_request_template.md` with a checklist for contributors.";
+    CODEOWNERS: "COM007", "community", Low, "CODEOWNERS",
+        "Add `.github/CODEOWNERS` so pull requests request review from the right people.";
+    GOVERNANCE: "COM008", "community", Low, "Governance",
+        "Document who maintains the project and how decisions are made in GOVERNANCE.md or MAINTAINERS.md.";
+    FUNDING: "COM009", "community", Low, "Funding",
+        "Add `.github/FUNDING.yml` so users can sponsor the project.";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rule_ids_are_unique() {
+        let ids: HashSet<&str> = ALL.iter().map(|rule| rule.id).collect();
+        assert_eq!(ids.len(), ALL.len());
+        assert_eq!(LICENSE.docs_url(), format!("{}#doc011", RULES_DOCS_URL));
+    }
+
+    #[test]
+    fn test_every_rule_is_documented() {
+        let docs = include_str!("../../docs/rules.md");
+        for rule in ALL {
+            assert!(docs.contains(&format!("### {}\n", rule.id)), "{} missing from docs/rules.md", rule.id);
+            assert!(docs.contains(rule.remediation), "{} remediation differs from docs/rules.md", rule.id);
+        }
+    }
+}
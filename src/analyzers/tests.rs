@@ -1,44 +1,322 @@
 use async_trait::async_trait;
+use crate::ci::CiConfig;
+use crate::config::Thresholds;
+use crate::coverage::CoverageReport;
 use crate::github::RepoData;
+use crate::languages::{self, Language};
+use crate::rust_crates;
+use crate::test_layout::TestLayout;
 use crate::Result;
-use super::{Analyzer, AnalysisResult, Finding};
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding, ReportTable};
+
+/// Smallest source directory worth flagging when it has no nearby tests
+const UNTESTED_DIR_MIN_FILES: usize = 3;
+const MAX_UNTESTED_DIRS_REPORTED: usize = 5;
+/// Smallest directory (in coverable lines) worth flagging for low coverage
+const COVERAGE_DIR_MIN_LINES: u64 = 20;
+const MAX_LOW_COVERAGE_DIRS_REPORTED: usize = 3;
+
+/// Points for a coverage badge whose percentage could not be resolved, out of 10
+const UNRESOLVED_BADGE_POINTS: f64 = 5.0;
 
 pub struct TestsAnalyzer;
 
 impl TestsAnalyzer {
-    fn is_test_file(&self, path: &str) -> bool {
-        // Rust
-        if path.starts_with("tests/") || path.contains("_test.rs") || path.contains("/test_") {
-            return true;
+    /// Points (max 30) for test-to-source file ratios, averaged over languages by source file count.
+    fn score_ratios(&self, layout: &TestLayout, thresholds: &Thresholds, findings: &mut Vec<Finding>) -> f64 {
+        let total = layout.total();
+        if total.test_files == 0 {
+            findings.push(Finding::missing("No test files detected").with_rule(&rules::TEST_FILES).scored(0.0, 30.0));
+            return 0.0;
         }
 
-        // JavaScript/TypeScript
-        if path.contains(".test.") || path.contains(".spec.") || path.contains("__tests__") {
-            return true;
+        let mut weighted_points = 0.0;
+        let mut weighted_files = 0usize;
+        let source_files: usize = layout.by_language.values().map(|s| s.source_files).sum();
+
+        for (language, stats) in layout.by_language.iter().filter(|(_, s)| s.source_files > 0) {
+            let (good, excellent) = Self::ratio_targets(*language, thresholds);
+            let ratio = stats.file_ratio();
+
+            let points = if ratio >= excellent {
+                30.0
+            } else if ratio >= good {
+                20.0 + 10.0 * (ratio - good) / (excellent - good)
+            } else {
+                20.0 * ratio / good
+            };
+            weighted_points += points * stats.source_files as f64;
+            weighted_files += stats.source_files;
+
+            let message = format!(
+                "{}: {} test files for {} source files (ratio {:.2}, target {:.2})",
+                language.name(), stats.test_files, stats.source_files, ratio, good
+            );
+            let finding = if ratio >= good { Finding::positive(message) } else { Finding::warning(message) };
+            let share = stats.source_files as f64 / source_files as f64;
+            findings.push(finding.with_rule(&rules::TEST_RATIO).scored(points * share, 30.0 * share));
         }
 
-        // Python
-        if path.starts_with("test_") || path.ends_with("_test.py") || path.contains("/test/") {
-            return true;
+        if weighted_files == 0 {
+            // Only test files, e.g. a test suite repository
+            return 30.0;
+        }
+
+        weighted_points / weighted_files as f64
+    }
+
+    /// Good and excellent test-to-source ratios, from the configuration or the ecosystem's defaults.
+    fn ratio_targets(language: Language, thresholds: &Thresholds) -> (f64, f64) {
+        thresholds.test_ratio
+            .map(|ratio| (ratio.good, ratio.excellent))
+            .unwrap_or_else(|| language.test_ratio_targets())
+    }
+
+    /// Points (max 40) for measured line coverage, with branch and per-directory findings.
+    fn score_coverage(
+        &self,
+        report: &CoverageReport,
+        thresholds: &Thresholds,
+        findings: &mut Vec<Finding>,
+        tables: &mut Vec<ReportTable>,
+    ) -> f64 {
+        let Some(line_percent) = report.line_percent() else {
+            findings.push(
+                Finding::warning(format!("Coverage report {} contains no coverable lines", report.source))
+                    .with_rule(&rules::COVERAGE_REPORT)
+            );
+            return 0.0;
+        };
+
+        let (covered, total) = report.lines();
+        let message = format!(
+            "Line coverage {:.1}% ({}/{} lines, {} report {})",
+            line_percent, covered, total, report.format.name(), report.source
+        );
+        let finding = if line_percent >= thresholds.coverage { Finding::positive(message) } else { Finding::warning(message) };
+        let points = 40.0 * (line_percent / thresholds.coverage).min(1.0);
+        findings.push(finding.with_rule(&rules::LINE_COVERAGE).scored(points, 40.0));
+
+        if let Some(branch_percent) = report.branch_percent() {
+            let (covered, total) = report.branches();
+            let message = format!("Branch coverage {:.1}% ({}/{} branches)", branch_percent, covered, total);
+            let finding = if branch_percent >= thresholds.branch_coverage { Finding::positive(message) } else { Finding::warning(message) };
+            findings.push(finding.with_rule(&rules::BRANCH_COVERAGE));
         }
 
-        // Go
-        if path.ends_with("_test.go") {
-            return true;
+        for (dir, covered, total, percent) in report.lowest_directories(COVERAGE_DIR_MIN_LINES, MAX_LOW_COVERAGE_DIRS_REPORTED) {
+            if percent < thresholds.coverage {
+                findings.push(Finding::warning(format!(
+                    "Low coverage in {}/: {:.1}% ({}/{} lines)",
+                    dir, percent, covered, total
+                )).with_rule(&rules::DIRECTORY_COVERAGE).at(dir.to_string(), None));
+            }
         }
 
-        // Java
-        if path.contains("/test/") && path.ends_with(".java") {
-            return true;
+        let mut table = ReportTable::new("Line coverage by directory", &["Directory", "Covered lines", "Lines", "Coverage"]);
+        for (dir, (covered, total)) in report.by_directory() {
+            if total > 0 {
+                table.push_row(vec![
+                    format!("`{}`", dir),
+                    covered.to_string(),
+                    total.to_string(),
+                    format!("{:.1}%", covered as f64 / total as f64 * 100.0),
+                ]);
+            }
         }
+        tables.push(table);
 
-        false
+        points
     }
 
-    fn has_test_directory(&self, tree: &[crate::github::TreeEntry]) -> bool {
-        tree.iter().any(|entry| {
+    /// Points (max 10) for coverage reported by README badges, or for their presence when unresolved.
+    fn score_badges(&self, repo_data: &RepoData, findings: &mut Vec<Finding>) -> f64 {
+        let best = repo_data.coverage_badges.iter()
+            .filter_map(|badge| Some((badge, badge.percent?)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let target = repo_data.config.thresholds.coverage;
+        if let Some((badge, percent)) = best {
+            let message = format!("{} badge reports {:.1}% coverage", badge.service.name(), percent);
+            let finding = if percent >= target { Finding::positive(message) } else { Finding::warning(message) };
+            let points = 10.0 * (percent / target).min(1.0);
+            findings.push(finding.with_rule(&rules::LINE_COVERAGE).scored(points, 10.0));
+            return points;
+        }
+
+        // A badge whose percentage can't be read says coverage is measured, not how much
+        match repo_data.coverage_badges.first() {
+            Some(badge) => {
+                findings.push(Finding::unknown(format!(
+                    "{} coverage badge found in README (percentage unavailable)",
+                    badge.service.name()
+                )).with_rule(&rules::LINE_COVERAGE).scored(UNRESOLVED_BADGE_POINTS, 10.0));
+                UNRESOLVED_BADGE_POINTS
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Count Rust inline unit tests, doc tests, integration tests and benches per crate.
+    fn check_rust_tests(
+        &self,
+        repo_data: &RepoData,
+        layout: &mut TestLayout,
+        findings: &mut Vec<Finding>,
+        tables: &mut Vec<ReportTable>,
+    ) {
+        let crates = rust_crates::discover_crates(&repo_data.tree, &repo_data.file_contents);
+        if crates.is_empty() {
+            return;
+        }
+
+        for path in rust_crates::inline_test_files(&repo_data.file_contents) {
+            layout.add_inline_tests(path, Language::Rust);
+        }
+
+        let rust_sources = repo_data.tree.iter()
+            .filter(|e| e.entry_type == "blob" && e.path.ends_with(".rs") && !languages::is_vendored(&e.path))
+            .count();
+        let inspected = repo_data.file_contents.keys().filter(|p| p.ends_with(".rs")).count();
+
+        let counts = rust_crates::count_tests(&crates, &repo_data.tree, &repo_data.file_contents);
+        let mut table = ReportTable::new(
+            "Rust tests by crate",
+            &["Crate", "Unit tests", "Files with unit tests", "Doc tests", "Integration tests", "Bench files"],
+        );
+
+        for (name, counts) in &counts {
+            let total = counts.unit_tests + counts.doc_tests + counts.integration_tests;
+            let message = format!(
+                "Crate `{}`: {} unit tests, {} doc tests, {} integration tests",
+                name, counts.unit_tests, counts.doc_tests, counts.integration_tests
+            );
+            let finding = if total > 0 {
+                Finding::positive(message)
+            } else if inspected == 0 {
+                Finding::unknown(message)
+            } else {
+                Finding::warning(message)
+            };
+            findings.push(finding.with_rule(&rules::CRATE_TESTS));
+
+            table.push_row(vec![
+                format!("`{}`", name),
+                counts.unit_tests.to_string(),
+                counts.files_with_unit_tests.to_string(),
+                counts.doc_tests.to_string(),
+                counts.integration_tests.to_string(),
+                counts.bench_files.to_string(),
+            ]);
+        }
+        tables.push(table);
+
+        if inspected == 0 {
+            findings.push(Finding::unknown(format!(
+                "Rust sources were not scanned for inline tests ({} files); pass a token or --scan-sources",
+                rust_sources
+            )).with_rule(&rules::CRATE_TESTS));
+        } else if inspected < rust_sources {
+            findings.push(Finding::warning(format!(
+                "Inline tests counted in {} of {} Rust source files",
+                inspected, rust_sources
+            )).with_rule(&rules::CRATE_TESTS));
+        }
+    }
+
+    fn layout_tables(&self, layout: &TestLayout, thresholds: &Thresholds) -> Vec<ReportTable> {
+        if layout.total().test_files == 0 {
+            return Vec::new();
+        }
+
+        let mut languages = ReportTable::new(
+            "Test-to-source ratios by language",
+            &["Language", "Source files", "Test files", "File ratio", "Byte ratio", "Target"],
+        );
+        for (language, stats) in &layout.by_language {
+            languages.push_row(vec![
+                language.name().to_string(),
+                stats.source_files.to_string(),
+                stats.test_files.to_string(),
+                format!("{:.2}", stats.file_ratio()),
+                format!("{:.2}", stats.byte_ratio()),
+                format!("{:.2}", Self::ratio_targets(*language, thresholds).0),
+            ]);
+        }
+
+        let mut modules = ReportTable::new(
+            "Tests by top-level module",
+            &["Module", "Source files", "Test files", "File ratio"],
+        );
+        for (module, stats) in &layout.by_module {
+            modules.push_row(vec![
+                format!("`{}`", module),
+                stats.source_files.to_string(),
+                stats.test_files.to_string(),
+                format!("{:.2}", stats.file_ratio()),
+            ]);
+        }
+
+        vec![languages, modules]
+    }
+
+    /// Award points for test invocations found in CI configuration.
+    fn check_ci_tests(&self, configs: &[CiConfig], findings: &mut Vec<Finding>) -> f64 {
+        if configs.is_empty() {
+            return 0.0;
+        }
+
+        let mut score = 0.0;
+        let mut runs_tests = false;
+        let mut runs_on_pull_request = false;
+
+        for config in configs {
+            for (job, commands) in config.test_jobs() {
+                if let Some(reason) = job.test_skip_reason() {
+                    findings.push(Finding::warning(format!(
+                        "Tests skipped in {} job `{}`: {}",
+                        config.path, job.name, reason
+                    )).with_rule(&rules::CI_TESTS).at(config.path.clone(), None));
+                    continue;
+                }
+
+                runs_tests = true;
+                runs_on_pull_request |= config.runs_on_pull_request && !job.excludes_pull_requests;
+                findings.push(Finding::positive(format!(
+                    "CI runs tests: {} ({} job `{}`)",
+                    commands.join(", "), config.path, job.name
+                )).with_rule(&rules::CI_TESTS).at(config.path.clone(), None));
+            }
+        }
+
+        if runs_tests {
+            score += 15.0;
+
+            if runs_on_pull_request {
+                score += 5.0;
+                findings.push(Finding::positive("Tests run on pull requests").with_rule(&rules::PR_TESTS));
+            } else {
+                findings.push(
+                    Finding::warning("CI tests do not run on pull requests")
+                        .with_rule(&rules::PR_TESTS)
+                        .scored(0.0, 5.0)
+                );
+            }
+        } else {
+            findings.push(Finding::warning(format!(
+                "CI configured in {} file(s) but no test invocation found",
+                configs.len()
+            )).with_rule(&rules::CI_TESTS).scored(0.0, 20.0));
+        }
+
+        score
+    }
+
+    fn has_test_directory(&self, repo_data: &RepoData) -> bool {
+        repo_data.tree.iter().any(|entry| {
             entry.path == "tests" || entry.path == "test"
             || entry.path == "__tests__" || entry.path == "spec"
+            || repo_data.config.paths.is_test(&entry.path)
         })
     }
 }
@@ -49,6 +327,14 @@ impl Analyzer for TestsAnalyzer {
         "Tests"
     }
 
+    fn id(&self) -> &str {
+        "tests"
+    }
+
+    fn description(&self) -> &str {
+        "Test-to-source ratios, tests run in CI and measured coverage"
+    }
+
     fn weight(&self) -> f64 {
         0.25
     }
@@ -56,67 +342,133 @@ impl Analyzer for TestsAnalyzer {
     async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult> {
         let mut score = 0.0;
         let mut findings = Vec::new();
+        let thresholds = &repo_data.config.thresholds;
 
         // Check for test directory
-        if self.has_test_directory(&repo_data.tree) {
+        if self.has_test_directory(repo_data) {
             score += 40.0;
-            findings.push(Finding::positive("Test directory exists"));
+            findings.push(Finding::positive("Test directory exists").with_rule(&rules::TEST_DIRECTORY));
         } else {
-            findings.push(Finding::missing("No dedicated test directory found"));
+            findings.push(
+                Finding::missing("No dedicated test directory found")
+                    .with_rule(&rules::TEST_DIRECTORY)
+                    .scored(0.0, 40.0)
+            );
         }
 
-        // Count test files
-        let test_file_count = repo_data.tree.iter()
-            .filter(|entry| entry.entry_type == "blob" && self.is_test_file(&entry.path))
-            .count();
-
-        if test_file_count >= 5 {
-            score += 20.0;
-            findings.push(Finding::positive(format!("Found {} test files", test_file_count)));
+        // Score test files relative to source files, per ecosystem
+        let mut layout = TestLayout::from_tree(&repo_data.tree, &repo_data.languages, &repo_data.config.paths);
+        let mut tables = Vec::new();
+        if repo_data.languages.is_present(Language::Rust) {
+            self.check_rust_tests(repo_data, &mut layout, &mut findings, &mut tables);
+        }
+        let total = layout.total();
+        let ratio_points = self.score_ratios(&layout, thresholds, &mut findings);
 
-            if test_file_count >= 10 {
-                score += 10.0;
-                findings.push(Finding::positive("Extensive test coverage (10+ test files)"));
+        let untested = layout.untested_dirs(UNTESTED_DIR_MIN_FILES);
+        if total.test_files > 0 {
+            for (dir, files) in untested.iter().take(MAX_UNTESTED_DIRS_REPORTED) {
+                findings.push(
+                    Finding::warning(format!("No tests near {}/ ({} source files)", dir, files))
+                        .with_rule(&rules::UNTESTED_DIRECTORY)
+                        .at(dir.to_string(), None)
+                );
+            }
+            if untested.len() > MAX_UNTESTED_DIRS_REPORTED {
+                findings.push(Finding::warning(format!(
+                    "{} more source directories have no nearby tests",
+                    untested.len() - MAX_UNTESTED_DIRS_REPORTED
+                )).with_rule(&rules::UNTESTED_DIRECTORY));
             }
-        } else if test_file_count > 0 {
-            findings.push(Finding::warning(format!("Only {} test files found", test_file_count)));
-        } else {
-            findings.push(Finding::missing("No test files detected"));
         }
 
-        // Check for CI test execution (simplified check)
-        let has_ci = repo_data.tree.iter().any(|entry| {
-            entry.path.starts_with(".github/workflows/") ||
-            entry.path == ".circleci/config.yml" ||
-            entry.path == ".travis.yml"
-        });
+        // Check that CI actually runs the tests
+        let ci_configs = crate::ci::parse_configs(&repo_data.ci_files, &repo_data.file_contents);
+        score += self.check_ci_tests(&ci_configs, &mut findings);
 
-        if has_ci {
-            score += 20.0;
-            findings.push(Finding::positive("CI configured (likely includes tests)"));
-        }
+        // Measured coverage replaces the file ratio and README badge heuristics
+        let mut coverage_tables = Vec::new();
+        let coverage_points = match &repo_data.coverage {
+            Some(report) => {
+                // File ratios no longer count towards the score
+                for finding in findings.iter_mut().filter(|f| f.is_rule(&rules::TEST_RATIO) || f.is_rule(&rules::TEST_FILES)) {
+                    finding.points = 0.0;
+                    finding.max_points = 0.0;
+                }
+                Some(self.score_coverage(report, thresholds, &mut findings, &mut coverage_tables))
+            }
+            None => {
+                score += ratio_points;
 
-        // Check for coverage badge in README
-        if let Some(readme) = &repo_data.readme_content {
-            if readme.contains("coverage") && (readme.contains("badge") || readme.contains("shields.io")) {
-                score += 10.0;
-                findings.push(Finding::positive("Coverage badge found in README"));
+                score += self.score_badges(repo_data, &mut findings);
+                None
             }
-        }
+        };
+        score += coverage_points.unwrap_or(0.0);
+
+        let summary = match (coverage_points, repo_data.coverage.as_ref().and_then(|r| r.line_percent())) {
+            (Some(points), Some(percent)) => format!(
+                "{:.1}% line coverage. {}",
+                percent,
+                if points >= 40.0 { "Excellent test coverage" }
+                else if points >= 30.0 { "Good test coverage" }
+                else { "Limited test coverage" }
+            ),
+            _ => String::from(
+                if ratio_points >= 30.0 { "Excellent test coverage" }
+                else if ratio_points >= 20.0 { "Good test coverage" }
+                else if total.test_files > 0 { "Limited test coverage" }
+                else { "No tests found" }
+            ),
+        };
 
         let details = format!(
-            "Detected {} test files across the repository. {}",
-            test_file_count,
-            if test_file_count >= 10 { "Excellent test coverage" }
-            else if test_file_count >= 5 { "Good test coverage" }
-            else if test_file_count > 0 { "Limited test coverage" }
-            else { "No tests found" }
+            "Detected {} test files for {} source files (ratio {:.2}). {}",
+            total.test_files,
+            total.source_files,
+            total.file_ratio(),
+            summary
         );
 
         Ok(AnalysisResult {
             score,
             details,
             findings,
+            tables: [coverage_tables, self.layout_tables(&layout, thresholds), tables].concat(),
+            assessment: Assessment::Scored,
         })
     }
 }
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::analyzers::FindingStatus;
+    use crate::coverage::{BadgeService, CoverageBadge};
+
+    fn badge(percent: Option<f64>) -> CoverageBadge {
+        CoverageBadge {
+            service: BadgeService::Codecov,
+            image_url: "https://codecov.io/gh/owner/repo/graph/badge.svg".to_string(),
+            route: "/codecov/c/github/owner/repo".to_string(),
+            percent,
+        }
+    }
+
+    #[test]
+    fn test_score_badges() {
+        let mut repo_data = RepoData::for_tests(Vec::new(), &[]);
+        let mut findings = Vec::new();
+        assert_eq!(TestsAnalyzer.score_badges(&repo_data, &mut findings), 0.0);
+
+        repo_data.coverage_badges = vec![badge(None)];
+        assert_eq!(TestsAnalyzer.score_badges(&repo_data, &mut findings), 5.0);
+        assert_eq!(findings[0].status, FindingStatus::Unknown);
+
+        let target = repo_data.config.thresholds.coverage;
+        repo_data.coverage_badges = vec![badge(None), badge(Some(target / 2.0))];
+        assert_eq!(TestsAnalyzer.score_badges(&repo_data, &mut findings), 5.0);
+        repo_data.coverage_badges = vec![badge(Some(target))];
+        assert_eq!(TestsAnalyzer.score_badges(&repo_data, &mut findings), 10.0);
+    }
+}
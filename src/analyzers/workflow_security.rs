@@ -0,0 +1,311 @@
+use async_trait::async_trait;
+use crate::ci::{self, CiConfig, CiJob};
+use crate::github::RepoData;
+use crate::Result;
+use super::{rules, Analyzer, AnalysisResult, Assessment, Finding};
+
+pub struct WorkflowSecurityAnalyzer;
+
+impl WorkflowSecurityAnalyzer {
+    /// Whether an action reference is pinned to a full-length commit SHA.
+    fn is_pinned(uses: &str) -> bool {
+        // Local actions and docker images are not fetched from a mutable tag
+        if uses.starts_with("./") || uses.starts_with("docker://") {
+            return true;
+        }
+
+        uses.split_once('@')
+            .is_some_and(|(_, reference)| reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+
+    fn unpinned_actions(job: &CiJob) -> Vec<&str> {
+        job.uses.iter()
+            .chain(job.steps.iter().filter_map(|s| s.uses.as_ref()))
+            .map(String::as_str)
+            .filter(|uses| !Self::is_pinned(uses))
+            .collect()
+    }
+
+    /// Whether the job checks out the untrusted pull request head.
+    fn checks_out_pr_head(job: &CiJob) -> bool {
+        job.steps.iter().any(|step| {
+            step.uses.as_deref().is_some_and(|u| u.starts_with("actions/checkout"))
+                && step.with.get("ref").is_some_and(|r| {
+                    r.contains("github.event.pull_request.head") || r.contains("github.head_ref")
+                })
+        })
+    }
+
+    /// Attacker-controllable expressions interpolated directly into `run:` scripts.
+    fn injected_expressions(job: &CiJob) -> Vec<String> {
+        let mut expressions = Vec::new();
+
+        for command in job.steps.iter().filter_map(|s| s.command.as_deref()) {
+            let mut rest = command;
+            while let Some(start) = rest.find("${{") {
+                let Some(len) = rest[start..].find("}}") else { break };
+                let expr = rest[start + 3..start + len].trim();
+                if (expr.starts_with("github.event.") || expr == "github.head_ref")
+                    && !expressions.iter().any(|e| e == expr)
+                {
+                    expressions.push(expr.to_string());
+                }
+                rest = &rest[start + len + 2..];
+            }
+        }
+
+        expressions
+    }
+
+    fn uses_self_hosted(job: &CiJob) -> bool {
+        job.runs_on.iter().any(|label| label == "self-hosted")
+    }
+
+    fn audit(&self, config: &CiConfig, is_public: bool, issues: &mut Issues, findings: &mut Vec<Finding>) {
+        let pr_target = config.triggers.iter().any(|t| t == "pull_request_target");
+
+        if !config.declares_permissions && !config.jobs.iter().all(|j| j.declares_permissions) {
+            issues.missing_permissions = true;
+            findings.push(Finding::warning(format!(
+                "{}: no top-level `permissions:` block, GITHUB_TOKEN may default to write-all",
+                config.path
+            )).with_rule(&rules::WORKFLOW_PERMISSIONS).at(config.path.clone(), None));
+        }
+
+        for job in &config.jobs {
+            let unpinned = Self::unpinned_actions(job);
+            if !unpinned.is_empty() {
+                issues.unpinned = true;
+                findings.push(Finding::warning(format!(
+                    "{} job `{}`: actions not pinned to a commit SHA: {}",
+                    config.path, job.id, unpinned.join(", ")
+                )).with_rule(&rules::UNPINNED_ACTION).at(config.path.clone(), None));
+            }
+
+            if pr_target && Self::checks_out_pr_head(job) {
+                issues.pr_target_checkout = true;
+                findings.push(Finding::warning(format!(
+                    "{} job `{}`: `pull_request_target` workflow checks out the pull request head",
+                    config.path, job.id
+                )).with_rule(&rules::PR_TARGET_CHECKOUT).at(config.path.clone(), None));
+            }
+
+            let injected = Self::injected_expressions(job);
+            if !injected.is_empty() {
+                issues.script_injection = true;
+                findings.push(Finding::warning(format!(
+                    "{} job `{}`: untrusted input interpolated into `run:` script: {}",
+                    config.path, job.id, injected.join(", ")
+                )).with_rule(&rules::SCRIPT_INJECTION).at(config.path.clone(), None));
+            }
+
+            if is_public && Self::uses_self_hosted(job) {
+                issues.self_hosted = true;
+                findings.push(Finding::warning(format!(
+                    "{} job `{}`: self-hosted runner used by a public repository",
+                    config.path, job.id
+                )).with_rule(&rules::SELF_HOSTED_RUNNER).at(config.path.clone(), None));
+            }
+        }
+    }
+}
+
+/// Which classes of issue were found in any workflow.
+#[derive(Default)]
+struct Issues {
+    unpinned: bool,
+    missing_permissions: bool,
+    pr_target_checkout: bool,
+    script_injection: bool,
+    self_hosted: bool,
+}
+
+#[async_trait]
+impl Analyzer for WorkflowSecurityAnalyzer {
+    fn name(&self) -> &str {
+        "Workflow Security"
+    }
+
+    fn id(&self) -> &str {
+        "workflow-security"
+    }
+
+    fn description(&self) -> &str {
+        "GitHub Actions pinning, permissions and script injection risks"
+    }
+
+    fn weight(&self) -> f64 {
+        0.10
+    }
+
+    async fn analyze(&self, repo_data: &RepoData) -> Result<AnalysisResult> {
+        let mut findings = Vec::new();
+
+        let needed = repo_data.ci_files.iter()
+            .filter(|f| f.provider_id == "github-actions")
+            .map(|f| f.path.as_str());
+        let unfetched = repo_data.availability.unfetched(needed);
+        if !unfetched.is_empty() {
+            findings.push(Finding::unknown(format!("Workflows could not be fetched: {}", unfetched.join(", "))));
+            return Ok(AnalysisResult::unknown("Unable to audit workflows - workflow files unavailable", findings));
+        }
+
+        let workflows: Vec<CiConfig> = ci::parse_configs(&repo_data.ci_files, &repo_data.file_contents)
+            .into_iter()
+            .filter(|c| c.provider_id == "github-actions")
+            .collect();
+
+        if workflows.is_empty() {
+            findings.push(Finding::not_applicable("No GitHub Actions workflows to audit"));
+            return Ok(AnalysisResult::not_applicable("No GitHub Actions workflows found", findings));
+        }
+
+        let is_public = !repo_data.repository.private.unwrap_or(false);
+        let mut issues = Issues::default();
+
+        for workflow in &workflows {
+            self.audit(workflow, is_public, &mut issues, &mut findings);
+        }
+
+        let mut score: f64 = 100.0;
+        let checks = [
+            (issues.unpinned, 20.0, rules::UNPINNED_ACTION, "All actions pinned to commit SHAs"),
+            (issues.missing_permissions, 20.0, rules::WORKFLOW_PERMISSIONS, "Workflow token permissions are restricted"),
+            (issues.pr_target_checkout, 30.0, rules::PR_TARGET_CHECKOUT, "No `pull_request_target` checkout of untrusted code"),
+            (issues.script_injection, 30.0, rules::SCRIPT_INJECTION, "No script injection via event context"),
+            (issues.self_hosted, 20.0, rules::SELF_HOSTED_RUNNER, "No self-hosted runners exposed to public pull requests"),
+        ];
+
+        for (failed, penalty, rule, message) in checks {
+            if failed {
+                score -= penalty;
+                // Every workflow that fails the check shares its penalty
+                let failures = findings.iter().filter(|f| f.is_rule(&rule)).count();
+                for finding in findings.iter_mut().filter(|f| f.is_rule(&rule)) {
+                    finding.max_points = penalty / failures as f64;
+                }
+            } else {
+                findings.push(Finding::positive(message).with_rule(&rule));
+            }
+        }
+
+        let failed = checks.iter().filter(|(failed, _, _, _)| *failed).count();
+        let details = format!(
+            "Audited {} workflow(s); {} of {} hardening checks failed",
+            workflows.len(), failed, checks.len()
+        );
+
+        Ok(AnalysisResult {
+            score: score.max(0.0),
+            details,
+            findings,
+            tables: Vec::new(),
+            assessment: Assessment::Scored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::rules::Rule;
+    use crate::ci::CiStep;
+    use crate::github::TreeEntry;
+
+    #[test]
+    fn test_is_pinned() {
+        assert!(WorkflowSecurityAnalyzer::is_pinned("actions/checkout@b4ffde65f46336ab88eb53be808477a3936bae11"));
+        assert!(WorkflowSecurityAnalyzer::is_pinned("./.github/actions/setup"));
+        assert!(!WorkflowSecurityAnalyzer::is_pinned("actions/checkout@v4"));
+        assert!(!WorkflowSecurityAnalyzer::is_pinned("dtolnay/rust-toolchain@stable"));
+    }
+
+    #[test]
+    fn test_injected_expressions() {
+        let job = CiJob {
+            steps: vec![CiStep {
+                command: Some("echo \"${{ github.event.issue.title }}\" && echo ${{ matrix.os }}".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(WorkflowSecurityAnalyzer::injected_expressions(&job), vec!["github.event.issue.title"]);
+    }
+
+    const PINNED_CHECKOUT: &str = "actions/checkout@b4ffde65f46336ab88eb53be808477a3936bae11";
+
+    async fn audit_workflow(workflow: &str, private: bool) -> AnalysisResult {
+        let tree = vec![TreeEntry::blob(".github/workflows/ci.yml").with_size(workflow.len() as u64)];
+        let mut repo_data = RepoData::for_tests(tree, &[(".github/workflows/ci.yml", workflow)]);
+        repo_data.repository.private = Some(private);
+
+        WorkflowSecurityAnalyzer.analyze(&repo_data).await.unwrap()
+    }
+
+    fn failures<'a>(result: &'a AnalysisResult, rule: &Rule) -> Vec<&'a Finding> {
+        result.findings.iter().filter(|f| f.is_rule(rule) && f.status.is_failure()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_no_workflows_is_not_applicable() {
+        let repo_data = RepoData::for_tests(Vec::new(), &[]);
+        let result = WorkflowSecurityAnalyzer.analyze(&repo_data).await.unwrap();
+        assert_eq!(result.assessment, Assessment::NotApplicable);
+    }
+
+    #[tokio::test]
+    async fn test_permissions() {
+        let unrestricted = format!(
+            "on: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: {}\n",
+            PINNED_CHECKOUT
+        );
+        let result = audit_workflow(&unrestricted, false).await;
+        assert_eq!(failures(&result, &rules::WORKFLOW_PERMISSIONS).len(), 1);
+        assert_eq!(result.score, 80.0);
+
+        let restricted = format!("permissions:\n  contents: read\n{}", unrestricted);
+        let result = audit_workflow(&restricted, false).await;
+        assert!(failures(&result, &rules::WORKFLOW_PERMISSIONS).is_empty());
+        assert_eq!(result.score, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_target_checkout() {
+        let workflow = format!(
+            "on: pull_request_target\npermissions:\n  contents: read\njobs:\n  test:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: {}\n        with:\n          ref: ${{{{ github.event.pull_request.head.sha }}}}\n",
+            PINNED_CHECKOUT
+        );
+        let result = audit_workflow(&workflow, false).await;
+        assert_eq!(failures(&result, &rules::PR_TARGET_CHECKOUT).len(), 1);
+        assert_eq!(result.score, 70.0);
+
+        // The same checkout is harmless on `pull_request`, which runs without secrets
+        let result = audit_workflow(&workflow.replace("pull_request_target", "pull_request"), false).await;
+        assert!(failures(&result, &rules::PR_TARGET_CHECKOUT).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_self_hosted_runner() {
+        let workflow = format!(
+            "on: pull_request\npermissions:\n  contents: read\njobs:\n  test:\n    runs-on: [self-hosted, linux]\n    steps:\n      - uses: {}\n",
+            PINNED_CHECKOUT
+        );
+        let result = audit_workflow(&workflow, false).await;
+        assert_eq!(failures(&result, &rules::SELF_HOSTED_RUNNER).len(), 1);
+        assert_eq!(result.score, 80.0);
+
+        let result = audit_workflow(&workflow, true).await;
+        assert!(failures(&result, &rules::SELF_HOSTED_RUNNER).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unfetched_workflow_is_unknown() {
+        let tree = vec![TreeEntry::blob(".github/workflows/ci.yml")];
+        let mut repo_data = RepoData::for_tests(tree, &[]);
+        repo_data.availability.record_unfetched_files(vec![".github/workflows/ci.yml".to_string()], 1);
+
+        let result = WorkflowSecurityAnalyzer.analyze(&repo_data).await.unwrap();
+        assert_eq!(result.assessment, Assessment::Unknown);
+    }
+}
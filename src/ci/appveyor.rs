@@ -0,0 +1,86 @@
+use serde_yaml::Value;
+use super::{CiJob, CiStep, ParsedConfig, scalar_list};
+
+const SCRIPT_PHASES: &[&str] = &["install", "before_build", "build_script", "before_test", "test_script"];
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let images = scalar_list(yaml.get("image").or_else(|| yaml.get("os")));
+    let runs_on = if images.is_empty() {
+        // AppVeyor defaults to a Windows build worker
+        vec!["windows".to_string()]
+    } else {
+        images.iter().map(|image| worker_os(image).to_string()).collect()
+    };
+
+    let tests_off = yaml.get("test").and_then(Value::as_str) == Some("off")
+        || yaml.get("test").and_then(Value::as_bool) == Some(false);
+
+    let steps = SCRIPT_PHASES.iter()
+        .filter_map(|phase| Some((*phase, yaml.get(*phase)?)))
+        .flat_map(|(phase, value)| commands(value).into_iter().map(move |c| (phase, c)))
+        .map(|(phase, command)| CiStep {
+            command: Some(command),
+            disabled: tests_off && matches!(phase, "before_test" | "test_script"),
+            ..Default::default()
+        })
+        .collect();
+
+    let job = CiJob {
+        id: "default".to_string(),
+        name: "default".to_string(),
+        steps,
+        runs_on,
+        ..Default::default()
+    };
+
+    // AppVeyor builds pull requests by default
+    ParsedConfig { runs_on_pull_request: true, jobs: vec![job], ..Default::default() }
+}
+
+/// Script entries are strings or `cmd:`/`ps:`/`sh:` mappings.
+fn commands(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Sequence(items) => items.iter().flat_map(commands).collect(),
+        Value::Mapping(m) => m.values().filter_map(|v| v.as_str().map(String::from)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn worker_os(image: &str) -> &'static str {
+    let image = image.to_ascii_lowercase();
+    if image.contains("ubuntu") || image.contains("linux") {
+        "linux"
+    } else if image.contains("macos") || image.contains("osx") {
+        "macos"
+    } else {
+        "windows"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_appveyor() {
+        let yaml: Value = serde_yaml::from_str(r#"
+image:
+  - Visual Studio 2022
+  - Ubuntu2204
+install:
+  - cmd: rustup-init -y
+test_script:
+  - cargo test
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        let job = &parsed.jobs[0];
+        assert_eq!(job.runs_on, vec!["windows", "linux"]);
+        assert_eq!(job.test_commands(), vec!["cargo test"]);
+        assert_eq!(job.test_skip_reason(), None);
+
+        let yaml: Value = serde_yaml::from_str("test: off\ntest_script: [cargo test]").unwrap();
+        assert_eq!(parse(&yaml).jobs[0].test_skip_reason(), Some("test steps are disabled"));
+    }
+}
@@ -0,0 +1,104 @@
+use serde_yaml::Value;
+use super::{CiJob, CiStep, ParsedConfig, script_lines};
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let default_image = yaml.get("image").and_then(image_name);
+    let pipelines = yaml.get("pipelines");
+    let mut jobs = Vec::new();
+
+    // `default` runs for every branch push, which includes pull request branches
+    let runs_on_pull_request = pipelines.is_some_and(|p| p.get("default").is_some() || p.get("pull-requests").is_some());
+
+    if let Some(pipelines) = pipelines.and_then(Value::as_mapping) {
+        for (section, definition) in pipelines {
+            let Some(section) = section.as_str() else { continue };
+            let excludes_pull_requests = !matches!(section, "default" | "pull-requests");
+
+            // Everything but `default` is keyed by branch, tag or pipeline name
+            let lists: Vec<&Value> = match definition {
+                Value::Sequence(_) => vec![definition],
+                Value::Mapping(named) => named.values().collect(),
+                _ => Vec::new(),
+            };
+
+            for list in lists {
+                collect_steps(list, section, excludes_pull_requests, default_image.as_deref(), &mut jobs);
+            }
+        }
+    }
+
+    ParsedConfig { runs_on_pull_request, jobs, ..Default::default() }
+}
+
+fn collect_steps(list: &Value, section: &str, excludes_pull_requests: bool, image: Option<&str>, jobs: &mut Vec<CiJob>) {
+    for item in list.as_sequence().into_iter().flatten() {
+        if let Some(parallel) = item.get("parallel") {
+            // Newer syntax nests the list under `steps:`
+            let nested = parallel.get("steps").unwrap_or(parallel);
+            collect_steps(nested, section, excludes_pull_requests, image, jobs);
+            continue;
+        }
+
+        let Some(step) = item.get("step") else { continue };
+        let name = step.get("name")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| format!("{} step {}", section, jobs.len() + 1));
+
+        jobs.push(CiJob {
+            id: name.clone(),
+            name,
+            image: step.get("image").and_then(image_name).or(image.map(String::from)),
+            runs_on: vec!["linux".to_string()],
+            steps: step.get("script")
+                .map(script_lines)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|command| CiStep { command: Some(command), ..Default::default() })
+                .collect(),
+            excludes_pull_requests,
+            ..Default::default()
+        });
+    }
+}
+
+fn image_name(image: &Value) -> Option<String> {
+    image.as_str().or_else(|| image.get("name")?.as_str()).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bitbucket() {
+        let yaml: Value = serde_yaml::from_str(r#"
+image: node:20
+pipelines:
+  default:
+    - parallel:
+        - step:
+            name: Test
+            script:
+              - npm ci
+              - npm test
+        - step:
+            name: Lint
+            script:
+              - npm run lint
+  branches:
+    main:
+      - step:
+          name: Deploy
+          script:
+            - ./deploy.sh
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert!(parsed.runs_on_pull_request);
+        assert_eq!(parsed.jobs.len(), 3);
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["npm test"]);
+        assert_eq!(parsed.jobs[0].image.as_deref(), Some("node:20"));
+        assert!(parsed.jobs[2].excludes_pull_requests);
+    }
+}
@@ -0,0 +1,74 @@
+use serde_yaml::Value;
+use super::{CiJob, CiStep, ParsedConfig, script_lines};
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let mut jobs = Vec::new();
+    collect_steps(yaml.get("steps"), &mut jobs);
+
+    // Buildkite builds pull requests unless the pipeline settings turn them off
+    ParsedConfig { runs_on_pull_request: true, jobs, ..Default::default() }
+}
+
+fn collect_steps(steps: Option<&Value>, jobs: &mut Vec<CiJob>) {
+    for step in steps.and_then(Value::as_sequence).into_iter().flatten() {
+        // `group` steps nest their own list of steps
+        if step.get("group").is_some() {
+            collect_steps(step.get("steps"), jobs);
+            continue;
+        }
+
+        let commands: Vec<String> = ["command", "commands"].iter()
+            .filter_map(|key| step.get(*key))
+            .flat_map(script_lines)
+            .collect();
+        if commands.is_empty() {
+            continue;
+        }
+
+        let name = ["label", "key", "name"].iter()
+            .find_map(|key| step.get(*key)?.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| format!("step {}", jobs.len() + 1));
+
+        let allow_failure = step.get("soft_fail").is_some_and(|v| v.as_bool() != Some(false));
+
+        jobs.push(CiJob {
+            id: name.clone(),
+            name,
+            steps: commands.into_iter()
+                .map(|command| CiStep { command: Some(command), allow_failure, ..Default::default() })
+                .collect(),
+            disabled: step.get("skip").is_some_and(|v| v.as_bool() != Some(false)),
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_buildkite() {
+        let yaml: Value = serde_yaml::from_str(r#"
+steps:
+  - label: ":rust: Test"
+    command: cargo test
+  - wait
+  - group: Lints
+    steps:
+      - label: Clippy
+        commands:
+          - cargo clippy
+      - label: Flaky
+        command: npm test
+        skip: true
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert_eq!(parsed.jobs.len(), 3);
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["cargo test"]);
+        assert_eq!(parsed.jobs[1].name, "Clippy");
+        assert!(parsed.jobs[2].disabled);
+    }
+}
@@ -0,0 +1,117 @@
+use serde_yaml::Value;
+use std::collections::HashMap;
+use super::{CiJob, CiStep, ParsedConfig};
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let scheduled = scheduled_jobs(yaml);
+
+    let jobs = yaml.get("jobs")
+        .and_then(Value::as_mapping)
+        .map(|jobs| {
+            jobs.iter()
+                .filter_map(|(name, job)| {
+                    let name = name.as_str()?;
+                    let steps = job.get("steps")
+                        .and_then(Value::as_sequence)
+                        .map(|steps| steps.iter().filter_map(parse_step).collect())
+                        .unwrap_or_default();
+
+                    let image = job.get("docker")
+                        .and_then(Value::as_sequence)
+                        .and_then(|images| images.first()?.get("image")?.as_str())
+                        .map(String::from);
+
+                    let runs_on = if job.get("macos").is_some() {
+                        vec!["macos".to_string()]
+                    } else if image.is_some() {
+                        vec!["linux".to_string()]
+                    } else {
+                        Vec::new()
+                    };
+
+                    Some(CiJob {
+                        id: name.to_string(),
+                        name: name.to_string(),
+                        steps,
+                        runs_on,
+                        image,
+                        matrix: HashMap::new(),
+                        // In 2.x configs a job only runs if a workflow references it
+                        disabled: scheduled.as_ref().is_some_and(|s| !s.iter().any(|j| j == name)),
+                        excludes_pull_requests: false,
+                        ..Default::default()
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // CircleCI builds every pushed branch, which includes pull request branches
+    ParsedConfig { runs_on_pull_request: true, jobs, ..Default::default() }
+}
+
+fn parse_step(step: &Value) -> Option<CiStep> {
+    let run = step.get("run")?;
+    let command = match run {
+        Value::String(command) => command.clone(),
+        _ => run.get("command")?.as_str()?.to_string(),
+    };
+
+    Some(CiStep {
+        command: Some(command),
+        ..Default::default()
+    })
+}
+
+/// Names of jobs referenced from `workflows:`, or `None` when there are no workflows.
+fn scheduled_jobs(yaml: &Value) -> Option<Vec<String>> {
+    let workflows = yaml.get("workflows")?.as_mapping()?;
+
+    let names = workflows.values()
+        .filter_map(|w| w.get("jobs")?.as_sequence())
+        .flatten()
+        .filter_map(|job| match job {
+            Value::String(name) => Some(name.clone()),
+            Value::Mapping(m) => m.keys().next()?.as_str().map(String::from),
+            _ => None,
+        })
+        .collect();
+
+    Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_circleci() {
+        let yaml: Value = serde_yaml::from_str(r#"
+version: 2.1
+jobs:
+  build:
+    docker:
+      - image: cimg/node:20.0
+    steps:
+      - checkout
+      - run: npm ci
+      - run:
+          name: Unit tests
+          command: npm test
+  unused:
+    steps:
+      - run: go test ./...
+workflows:
+  main:
+    jobs:
+      - build
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert_eq!(parsed.jobs.len(), 2);
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["npm test"]);
+        assert!(!parsed.jobs[0].disabled);
+        assert_eq!(parsed.jobs[0].image.as_deref(), Some("cimg/node:20.0"));
+        assert!(parsed.jobs[1].disabled);
+    }
+}
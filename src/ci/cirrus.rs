@@ -0,0 +1,88 @@
+use serde_yaml::Value;
+use super::{CiJob, CiStep, ParsedConfig, script_lines};
+
+/// Instance keys and the operating system they provide.
+const INSTANCES: &[(&str, &str)] = &[
+    ("container", "linux"),
+    ("arm_container", "linux"),
+    ("macos_instance", "macos"),
+    ("windows_container", "windows"),
+    ("freebsd_instance", "freebsd"),
+];
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let default_instance = instance(yaml);
+    let mut jobs = Vec::new();
+
+    for (key, task) in yaml.as_mapping().into_iter().flatten() {
+        let Some(key) = key.as_str() else { continue };
+        if key != "task" && !key.ends_with("_task") {
+            continue;
+        }
+
+        let name = task.get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_else(|| key.trim_end_matches("_task"))
+            .to_string();
+
+        let (os, image) = instance(task).or(default_instance.clone()).unwrap_or_default();
+
+        let steps = task.as_mapping().into_iter().flatten()
+            .filter(|(k, _)| k.as_str().is_some_and(|k| k.ends_with("_script")))
+            .flat_map(|(_, script)| script_lines(script))
+            .map(|command| CiStep { command: Some(command), ..Default::default() })
+            .collect();
+
+        let only_if = task.get("only_if").and_then(Value::as_str).unwrap_or("");
+
+        jobs.push(CiJob {
+            id: key.to_string(),
+            name,
+            steps,
+            runs_on: if os.is_empty() { Vec::new() } else { vec![os] },
+            image,
+            excludes_pull_requests: only_if.contains("$CIRRUS_PR == ''"),
+            ..Default::default()
+        });
+    }
+
+    // Cirrus runs tasks for pull requests unless `only_if` says otherwise
+    ParsedConfig { runs_on_pull_request: true, jobs, ..Default::default() }
+}
+
+fn instance(value: &Value) -> Option<(String, Option<String>)> {
+    INSTANCES.iter().find_map(|(key, os)| {
+        let instance = value.get(*key)?;
+        let image = instance.get("image").and_then(Value::as_str).map(String::from);
+        Some((os.to_string(), image))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cirrus() {
+        let yaml: Value = serde_yaml::from_str(r#"
+container:
+  image: rust:latest
+test_task:
+  cargo_cache:
+    folder: $CARGO_HOME/registry
+  build_script: cargo build
+  test_script: cargo test
+macos_task:
+  macos_instance:
+    image: ghcr.io/cirruslabs/macos-sonoma-base:latest
+  test_script: cargo test
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert_eq!(parsed.jobs.len(), 2);
+        assert_eq!(parsed.jobs[0].name, "test");
+        assert_eq!(parsed.jobs[0].runs_on, vec!["linux"]);
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["cargo test"]);
+        assert_eq!(parsed.jobs[1].runs_on, vec!["macos"]);
+    }
+}
@@ -0,0 +1,114 @@
+use serde_yaml::Value;
+use super::{CiJob, CiStep, ParsedConfig, load_all, scalar_list, script_lines};
+
+/// Parse Drone and Woodpecker pipelines, which share a steps/commands layout.
+pub(super) fn parse(content: &str) -> Option<ParsedConfig> {
+    let documents = load_all(content);
+    if documents.is_empty() {
+        return None;
+    }
+
+    let mut runs_on_pull_request = false;
+    let mut jobs = Vec::new();
+
+    for (idx, doc) in documents.iter().enumerate() {
+        // Drone files may also contain secret and signature documents
+        if doc.get("kind").and_then(Value::as_str).is_some_and(|k| k != "pipeline") {
+            continue;
+        }
+
+        let events = doc.get("trigger")
+            .or_else(|| doc.get("when"))
+            .map(|t| scalar_list(t.get("event")))
+            .unwrap_or_default();
+        let on_pull_request = events.is_empty() || events.iter().any(|e| e == "pull_request");
+        runs_on_pull_request |= on_pull_request;
+
+        let name = doc.get("name")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| format!("pipeline {}", idx + 1));
+
+        let os = doc.get("platform")
+            .and_then(|p| p.get("os"))
+            .and_then(Value::as_str)
+            .unwrap_or("linux");
+
+        // Woodpecker allows `steps` as a map keyed by step name
+        let steps: Vec<&Value> = match doc.get("steps").or_else(|| doc.get("pipeline")) {
+            Some(Value::Sequence(steps)) => steps.iter().collect(),
+            Some(Value::Mapping(steps)) => steps.values().collect(),
+            _ => Vec::new(),
+        };
+
+        jobs.push(CiJob {
+            id: name.clone(),
+            name,
+            runs_on: vec![os.to_string()],
+            image: steps.iter().find_map(|s| s.get("image")?.as_str()).map(String::from),
+            steps: steps.iter()
+                .flat_map(|step| {
+                    let allow_failure = step.get("failure").and_then(Value::as_str) == Some("ignore");
+                    ["commands", "command"].iter()
+                        .filter_map(|key| step.get(*key))
+                        .flat_map(script_lines)
+                        .map(move |command| CiStep { command: Some(command), allow_failure, ..Default::default() })
+                })
+                .collect(),
+            excludes_pull_requests: !on_pull_request,
+            ..Default::default()
+        });
+    }
+
+    Some(ParsedConfig { runs_on_pull_request, jobs, ..Default::default() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_drone_multi_document() {
+        let parsed = parse(r#"
+kind: pipeline
+name: test
+steps:
+  - name: test
+    image: golang:1.22
+    commands:
+      - go test ./...
+---
+kind: pipeline
+name: release
+trigger:
+  event: [tag]
+steps:
+  - name: publish
+    image: plugins/docker
+---
+kind: secret
+name: token
+"#).unwrap();
+
+        assert_eq!(parsed.jobs.len(), 2);
+        assert!(parsed.runs_on_pull_request);
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["go test"]);
+        assert!(parsed.jobs[1].excludes_pull_requests);
+    }
+
+    #[test]
+    fn test_parse_woodpecker_step_map() {
+        let parsed = parse(r#"
+when:
+  event: [push, pull_request]
+steps:
+  test:
+    image: python:3.12
+    commands:
+      - pytest
+"#).unwrap();
+
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["pytest"]);
+        assert_eq!(parsed.jobs[0].image.as_deref(), Some("python:3.12"));
+    }
+}
@@ -0,0 +1,211 @@
+use serde_yaml::Value;
+use std::collections::HashMap;
+use super::{CiJob, CiStep, ParsedConfig, is_false, is_true, scalar_list, scalar_string};
+
+const PULL_REQUEST_EVENTS: &[&str] = &["pull_request", "pull_request_target"];
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let triggers = trigger_names(yaml.get("on"));
+    let runs_on_pull_request = triggers.iter().any(|t| PULL_REQUEST_EVENTS.contains(&t.as_str()));
+
+    let jobs = yaml.get("jobs")
+        .and_then(Value::as_mapping)
+        .map(|jobs| {
+            jobs.iter()
+                .filter_map(|(id, job)| Some(parse_job(id.as_str()?, job)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ParsedConfig {
+        runs_on_pull_request,
+        declares_permissions: yaml.get("permissions").is_some(),
+        triggers,
+        jobs,
+        ..Default::default()
+    }
+}
+
+/// Event names from an `on:` value, which may be a string, a list or a map.
+pub(crate) fn trigger_names(on: Option<&Value>) -> Vec<String> {
+    match on {
+        Some(Value::String(event)) => vec![event.clone()],
+        Some(Value::Sequence(events)) => events.iter()
+            .filter_map(|e| e.as_str().map(String::from))
+            .collect(),
+        Some(Value::Mapping(events)) => events.keys()
+            .filter_map(|e| e.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_job(id: &str, job: &Value) -> CiJob {
+    let name = job.get("name").and_then(Value::as_str).unwrap_or(id).to_string();
+    let condition = job.get("if");
+
+    let steps = job.get("steps")
+        .and_then(Value::as_sequence)
+        .map(|steps| steps.iter().map(parse_step).collect())
+        .unwrap_or_default();
+
+    CiJob {
+        id: id.to_string(),
+        name,
+        uses: job.get("uses").and_then(Value::as_str).map(String::from),
+        declares_permissions: job.get("permissions").is_some(),
+        steps,
+        runs_on: scalar_list(job.get("runs-on")),
+        image: job.get("container").and_then(|c| {
+            c.as_str().or_else(|| c.get("image")?.as_str()).map(String::from)
+        }),
+        matrix: job.get("strategy")
+            .and_then(|s| s.get("matrix"))
+            .map(parse_matrix)
+            .unwrap_or_default(),
+        disabled: is_false(condition),
+        excludes_pull_requests: condition
+            .and_then(Value::as_str)
+            .is_some_and(excludes_pull_requests),
+    }
+}
+
+fn parse_step(step: &Value) -> CiStep {
+    let with = step.get("with")
+        .and_then(Value::as_mapping)
+        .map(|inputs| {
+            inputs.iter()
+                .filter_map(|(k, v)| Some((k.as_str()?.to_string(), scalar_string(v)?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    CiStep {
+        command: step.get("run").and_then(Value::as_str).map(String::from),
+        uses: step.get("uses").and_then(Value::as_str).map(String::from),
+        with,
+        disabled: is_false(step.get("if")),
+        allow_failure: is_true(step.get("continue-on-error")),
+    }
+}
+
+/// Collect every value each matrix axis takes, including `include:` entries.
+fn parse_matrix(matrix: &Value) -> HashMap<String, Vec<String>> {
+    let mut axes: HashMap<String, Vec<String>> = HashMap::new();
+    let Some(mapping) = matrix.as_mapping() else {
+        return axes;
+    };
+
+    for (key, values) in mapping {
+        let Some(key) = key.as_str() else { continue };
+        match key {
+            "include" => {
+                for entry in values.as_sequence().into_iter().flatten() {
+                    for (k, v) in entry.as_mapping().into_iter().flatten() {
+                        if let (Some(k), Some(v)) = (k.as_str(), scalar_string(v)) {
+                            let axis = axes.entry(k.to_string()).or_default();
+                            if !axis.contains(&v) {
+                                axis.push(v);
+                            }
+                        }
+                    }
+                }
+            }
+            "exclude" => {}
+            _ => axes.entry(key.to_string()).or_default().extend(scalar_list(Some(values))),
+        }
+    }
+
+    axes
+}
+
+/// Whether a job `if:` expression restricts it to non-PR events.
+fn excludes_pull_requests(condition: &str) -> bool {
+    let normalized: String = condition.split_whitespace().collect::<Vec<_>>().join(" ").replace('"', "'");
+
+    normalized.contains("github.event_name == 'push'")
+        || normalized.contains("github.event_name == 'schedule'")
+        || normalized.contains("github.event_name != 'pull_request'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workflow() {
+        let yaml: Value = serde_yaml::from_str(r#"
+name: CI
+on:
+  push:
+    branches: [main]
+  pull_request:
+jobs:
+  test:
+    name: Test suite
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: cargo test --all-features
+  flaky:
+    runs-on: ubuntu-latest
+    if: false
+    steps:
+      - run: npm test
+  release:
+    if: github.event_name == 'push'
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions-rs/cargo@v1
+        with:
+          command: test
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert!(parsed.runs_on_pull_request);
+        assert_eq!(parsed.jobs.len(), 3);
+
+        assert_eq!(parsed.jobs[0].name, "Test suite");
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["cargo test"]);
+        assert_eq!(parsed.jobs[0].test_skip_reason(), None);
+
+        assert!(parsed.jobs[1].disabled);
+        assert_eq!(parsed.jobs[1].test_skip_reason(), Some("job is disabled"));
+
+        assert!(parsed.jobs[2].excludes_pull_requests);
+        assert_eq!(parsed.jobs[2].test_commands(), vec!["cargo test"]);
+    }
+
+    #[test]
+    fn test_parse_matrix() {
+        let yaml: Value = serde_yaml::from_str(r#"
+on: pull_request
+jobs:
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        rust: [stable, "1.70"]
+        include:
+          - os: macos-latest
+            rust: nightly
+    steps:
+      - run: cargo test
+"#).unwrap();
+
+        let job = &parse(&yaml).jobs[0];
+        assert_eq!(job.runs_on, vec!["${{ matrix.os }}"]);
+        assert_eq!(job.matrix["os"], vec!["ubuntu-latest", "windows-latest", "macos-latest"]);
+        assert_eq!(job.matrix["rust"], vec!["stable", "1.70", "nightly"]);
+    }
+
+    #[test]
+    fn test_trigger_forms() {
+        let yaml: Value = serde_yaml::from_str("on: [push, pull_request]").unwrap();
+        assert_eq!(trigger_names(yaml.get("on")), vec!["push", "pull_request"]);
+
+        let yaml: Value = serde_yaml::from_str("on: push").unwrap();
+        assert!(!parse(&yaml).runs_on_pull_request);
+    }
+}
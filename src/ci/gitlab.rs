@@ -0,0 +1,196 @@
+use serde_yaml::Value;
+use std::collections::HashMap;
+use super::{CiJob, CiStep, ParsedConfig, script_lines, scalar_list, is_true};
+
+/// Top-level keys that configure the pipeline rather than define a job.
+const RESERVED_KEYS: &[&str] = &[
+    "stages", "variables", "include", "default", "workflow", "image",
+    "services", "before_script", "after_script", "cache",
+];
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let workflow_rules = yaml.get("workflow").and_then(|w| w.get("rules"));
+    let runs_on_pull_request = match workflow_rules {
+        // Without workflow rules, branch pipelines run for every push to an MR branch
+        None => true,
+        Some(rules) => mentions_merge_requests(rules) || !restricts_sources(rules),
+    };
+
+    let default_image = yaml.get("default")
+        .and_then(|d| d.get("image"))
+        .or_else(|| yaml.get("image"));
+
+    let default_before = yaml.get("default")
+        .and_then(|d| d.get("before_script"))
+        .or_else(|| yaml.get("before_script"));
+
+    let jobs = yaml.as_mapping()
+        .map(|root| {
+            root.iter()
+                .filter_map(|(key, job)| {
+                    let key = key.as_str()?;
+                    if RESERVED_KEYS.contains(&key) || !job.is_mapping() {
+                        return None;
+                    }
+                    let mut parsed = parse_job(key, job, default_before);
+                    parsed.image = image_name(job.get("image").or(default_image));
+                    Some(parsed)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ParsedConfig {
+        runs_on_pull_request,
+        jobs,
+        includes: local_includes(yaml.get("include")),
+        ..Default::default()
+    }
+}
+
+fn parse_job(name: &str, job: &Value, default_before: Option<&Value>) -> CiJob {
+    let allow_failure = is_true(job.get("allow_failure"));
+    let manual = job.get("when").and_then(Value::as_str).is_some_and(|w| w == "never" || w == "manual");
+
+    let before = job.get("before_script").or(default_before);
+    let steps = [before, job.get("script")]
+        .into_iter()
+        .flatten()
+        .flat_map(script_lines)
+        .map(|line| CiStep {
+            command: Some(line),
+            allow_failure,
+            ..Default::default()
+        })
+        .collect();
+
+    let excludes_pull_requests = match job.get("only") {
+        Some(only) => {
+            let refs = script_lines(only.get("refs").unwrap_or(only));
+            !refs.iter().any(|r| r == "merge_requests" || r == "branches")
+        }
+        None => false,
+    };
+
+    CiJob {
+        id: name.to_string(),
+        name: name.to_string(),
+        steps,
+        runs_on: Vec::new(),
+        image: None,
+        matrix: job.get("parallel")
+            .and_then(|p| p.get("matrix"))
+            .and_then(Value::as_sequence)
+            .map(|entries| parse_matrix(entries))
+            .unwrap_or_default(),
+        // Hidden jobs (leading dot) are templates and never run on their own
+        disabled: name.starts_with('.') || manual,
+        excludes_pull_requests,
+        ..Default::default()
+    }
+}
+
+/// Repository-local files from `include:`; remote, project and template includes can't be resolved.
+fn local_includes(include: Option<&Value>) -> Vec<String> {
+    let entries = match include {
+        Some(Value::Sequence(entries)) => entries.iter().collect(),
+        Some(entry) => vec![entry],
+        None => Vec::new(),
+    };
+
+    entries.into_iter()
+        .filter_map(|entry| match entry {
+            Value::String(path) if !path.contains("://") => Some(path.as_str()),
+            Value::Mapping(_) => entry.get("local")?.as_str(),
+            _ => None,
+        })
+        .map(|path| path.trim_start_matches('/').to_string())
+        .collect()
+}
+
+/// `image:` may be a plain string or a mapping with a `name` key.
+fn image_name(image: Option<&Value>) -> Option<String> {
+    let image = image?;
+    image.as_str().or_else(|| image.get("name")?.as_str()).map(String::from)
+}
+
+/// Merge `parallel: matrix:` entries into axes.
+fn parse_matrix(entries: &[Value]) -> HashMap<String, Vec<String>> {
+    let mut axes: HashMap<String, Vec<String>> = HashMap::new();
+    for entry in entries {
+        for (key, values) in entry.as_mapping().into_iter().flatten() {
+            if let Some(key) = key.as_str() {
+                axes.entry(key.to_string()).or_default().extend(scalar_list(Some(values)));
+            }
+        }
+    }
+    axes
+}
+
+fn mentions_merge_requests(rules: &Value) -> bool {
+    serde_yaml::to_string(rules)
+        .map(|s| s.contains("merge_request_event"))
+        .unwrap_or(false)
+}
+
+fn restricts_sources(rules: &Value) -> bool {
+    serde_yaml::to_string(rules)
+        .map(|s| s.contains("CI_PIPELINE_SOURCE") || s.contains("CI_COMMIT_BRANCH"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gitlab_ci() {
+        let yaml: Value = serde_yaml::from_str(r#"
+stages: [test, deploy]
+before_script:
+  - pip install -r requirements.txt
+unit:
+  stage: test
+  script:
+    - python -m pytest
+.template:
+  script: go test ./...
+lint:
+  script: flake8
+  allow_failure: true
+deploy:
+  only: [tags]
+  script: ./deploy.sh
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert!(parsed.runs_on_pull_request);
+        assert_eq!(parsed.jobs.len(), 4);
+
+        let unit = parsed.jobs.iter().find(|j| j.name == "unit").unwrap();
+        assert_eq!(unit.test_commands(), vec!["pytest"]);
+        assert_eq!(unit.steps.len(), 2);
+
+        let template = parsed.jobs.iter().find(|j| j.name == ".template").unwrap();
+        assert!(template.disabled);
+
+        let deploy = parsed.jobs.iter().find(|j| j.name == "deploy").unwrap();
+        assert!(deploy.excludes_pull_requests);
+        assert_eq!(deploy.image, None);
+    }
+
+    #[test]
+    fn test_local_includes() {
+        let yaml: Value = serde_yaml::from_str(r#"
+include:
+  - local: /ci/test.yml
+  - project: group/templates
+    file: rust.yml
+  - template: Auto-DevOps.gitlab-ci.yml
+  - https://example.com/ci.yml
+  - ci/lint.yml
+"#).unwrap();
+
+        assert_eq!(parse(&yaml).includes, vec!["ci/test.yml", "ci/lint.yml"]);
+    }
+}
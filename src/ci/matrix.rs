@@ -0,0 +1,375 @@
+use std::collections::{BTreeSet, HashMap};
+use chrono::NaiveDate;
+use super::{CiConfig, CiJob};
+
+/// End-of-life dates for Node.js major versions.
+const NODE_EOL: &[(&str, &str)] = &[
+    ("12", "2022-04-30"),
+    ("14", "2023-04-30"),
+    ("16", "2023-09-11"),
+    ("18", "2025-04-30"),
+    ("20", "2026-04-30"),
+    ("22", "2027-04-30"),
+    ("24", "2028-04-30"),
+];
+
+/// End-of-life dates for Python minor versions.
+const PYTHON_EOL: &[(&str, &str)] = &[
+    ("3.6", "2021-12-23"),
+    ("3.7", "2023-06-27"),
+    ("3.8", "2024-10-07"),
+    ("3.9", "2025-10-31"),
+    ("3.10", "2026-10-31"),
+    ("3.11", "2027-10-31"),
+    ("3.12", "2028-10-31"),
+    ("3.13", "2029-10-31"),
+];
+
+/// Operating systems, runtime versions and feature sets exercised by CI.
+#[derive(Debug, Clone, Default)]
+pub struct MatrixCoverage {
+    pub operating_systems: BTreeSet<String>,
+    pub rust: BTreeSet<String>,
+    pub node: BTreeSet<String>,
+    pub python: BTreeSet<String>,
+    pub feature_sets: BTreeSet<String>,
+}
+
+impl MatrixCoverage {
+    pub fn from_configs(configs: &[CiConfig]) -> Self {
+        let mut coverage = Self::default();
+
+        for job in configs.iter().flat_map(|c| &c.jobs).filter(|j| !j.disabled) {
+            coverage.add_job(job);
+        }
+
+        coverage
+    }
+
+    fn add_job(&mut self, job: &CiJob) {
+        for label in job.runs_on.iter().flat_map(|l| expand(l, &job.matrix)) {
+            if let Some(os) = normalize_os(&label) {
+                self.operating_systems.insert(os.to_string());
+            }
+        }
+
+        if let Some(image) = &job.image {
+            self.operating_systems.insert("Linux".to_string());
+            self.add_image(image);
+        }
+
+        for (axis, values) in &job.matrix {
+            for value in values {
+                match axis.as_str() {
+                    "rust" | "toolchain" | "rust-version" => { self.rust.insert(value.clone()); }
+                    "node" | "node-version" | "node_js" => self.add_node(value),
+                    "python" | "python-version" => self.add_python(value),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut uses_cargo = false;
+        let mut explicit_rust = false;
+
+        for step in &job.steps {
+            if let Some(uses) = &step.uses {
+                let (action, version) = uses.split_once('@').unwrap_or((uses.as_str(), ""));
+                let input = |key: &str| step.with.get(key).map(|v| expand(v, &job.matrix)).unwrap_or_default();
+
+                match action {
+                    "dtolnay/rust-toolchain" => {
+                        explicit_rust = true;
+                        let toolchains = input("toolchain");
+                        if toolchains.is_empty() && !matches!(version, "master" | "v1") {
+                            self.rust.insert(version.to_string());
+                        }
+                        self.rust.extend(toolchains);
+                    }
+                    "actions-rs/toolchain" => {
+                        explicit_rust = true;
+                        self.rust.extend(input("toolchain"));
+                    }
+                    "actions/setup-node" => input("node-version").iter().for_each(|v| self.add_node(v)),
+                    "actions/setup-python" => input("python-version").iter().for_each(|v| self.add_python(v)),
+                    _ => {}
+                }
+            }
+
+            let Some(command) = &step.command else { continue };
+            for line in command.lines().flat_map(|l| expand(l, &job.matrix)) {
+                let tokens: Vec<&str> = line.split_whitespace().collect();
+
+                if let Some(toolchain) = rustup_toolchain(&tokens) {
+                    explicit_rust = true;
+                    self.rust.insert(toolchain.to_string());
+                }
+
+                if let Some(pos) = tokens.iter().position(|t| *t == "cargo") {
+                    uses_cargo = true;
+                    if let Some(toolchain) = tokens.get(pos + 1).and_then(|t| t.strip_prefix('+')) {
+                        explicit_rust = true;
+                        self.rust.insert(toolchain.to_string());
+                    }
+                    self.add_feature_set(&tokens[pos..]);
+                }
+            }
+        }
+
+        // GitHub-hosted runners and Rust images come with stable preinstalled
+        if uses_cargo && !explicit_rust && self.rust.is_empty() {
+            self.rust.insert("stable".to_string());
+        }
+    }
+
+    fn add_image(&mut self, image: &str) {
+        let name = image.rsplit('/').next().unwrap_or(image);
+        let Some((runtime, tag)) = name.split_once(':') else { return };
+        let version = tag.split('-').next().unwrap_or(tag);
+
+        match runtime {
+            "rust" => { self.rust.insert(version.to_string()); }
+            "node" => self.add_node(version),
+            "python" => self.add_python(version),
+            _ => {}
+        }
+    }
+
+    fn add_node(&mut self, version: &str) {
+        let major = version.trim_start_matches('v').split('.').next().unwrap_or(version);
+        if !major.is_empty() {
+            self.node.insert(major.to_string());
+        }
+    }
+
+    fn add_python(&mut self, version: &str) {
+        let minor: Vec<&str> = version.split('.').take(2).collect();
+        self.python.insert(minor.join("."));
+    }
+
+    fn add_feature_set(&mut self, cargo_args: &[&str]) {
+        let Some(subcommand) = cargo_args.iter().skip(1).find(|t| !t.starts_with('+') && !t.starts_with('-')) else {
+            return;
+        };
+        if !matches!(*subcommand, "build" | "test" | "check" | "clippy" | "nextest" | "hack") {
+            return;
+        }
+
+        let mut flags = Vec::new();
+        let mut args = cargo_args.iter();
+        while let Some(arg) = args.next() {
+            match *arg {
+                "--all-features" => flags.push("all features".to_string()),
+                "--no-default-features" => flags.push("no default features".to_string()),
+                "--feature-powerset" => flags.push("feature powerset".to_string()),
+                "--each-feature" => flags.push("each feature".to_string()),
+                "--features" | "-F" => {
+                    if let Some(features) = args.next() {
+                        flags.push(format!("features {}", features.trim_matches('"')));
+                    }
+                }
+                _ => {
+                    if let Some(features) = arg.strip_prefix("--features=") {
+                        flags.push(format!("features {}", features.trim_matches('"')));
+                    }
+                }
+            }
+        }
+
+        if flags.is_empty() {
+            self.feature_sets.insert("default features".to_string());
+        } else {
+            self.feature_sets.insert(flags.join(" + "));
+        }
+    }
+
+    /// Whether a job tests the declared MSRV; `1.74` and `1.74.0` are the same toolchain.
+    pub fn has_msrv_job(&self, msrv: &str) -> bool {
+        let Some(msrv) = rust_release(msrv) else { return false };
+        self.rust.iter().any(|v| rust_release(v) == Some(msrv))
+    }
+
+    /// Runtime versions in the matrix that are past their end-of-life date.
+    pub fn eol_versions(&self, today: NaiveDate) -> Vec<String> {
+        let mut eol = Vec::new();
+
+        for version in &self.node {
+            if is_eol(version, NODE_EOL, today) {
+                eol.push(format!("Node.js {}", version));
+            }
+        }
+
+        for version in &self.python {
+            if is_eol(version, PYTHON_EOL, today) {
+                eol.push(format!("Python {}", version));
+            }
+        }
+
+        eol
+    }
+
+    pub fn has_runtimes(&self) -> bool {
+        !self.rust.is_empty() || !self.node.is_empty() || !self.python.is_empty()
+    }
+}
+
+fn is_eol(version: &str, table: &[(&str, &str)], today: NaiveDate) -> bool {
+    if let Some((_, date)) = table.iter().find(|(v, _)| *v == version) {
+        return NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok_and(|eol| eol <= today);
+    }
+
+    // Versions older than anything in the table are long past end-of-life
+    let parse = |v: &str| -> Vec<u32> { v.split('.').filter_map(|p| p.parse().ok()).collect() };
+    let parsed = parse(version);
+    !parsed.is_empty() && table.first().is_some_and(|(oldest, _)| parsed < parse(oldest))
+}
+
+/// A numeric Rust version as `(major, minor, patch)`, with a missing patch as 0.
+fn rust_release(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.split('.').map(|p| p.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    parts.next().is_none().then_some((major, minor, patch))
+}
+
+fn rustup_toolchain<'a>(tokens: &[&'a str]) -> Option<&'a str> {
+    let pos = tokens.iter().position(|t| *t == "rustup")?;
+    let args: Vec<&str> = tokens[pos + 1..].iter().copied().filter(|t| !t.starts_with('-')).collect();
+
+    match args.as_slice() {
+        ["toolchain", "install", toolchain, ..]
+        | ["install", toolchain, ..]
+        | ["default", toolchain, ..]
+        | ["override", "set", toolchain, ..] => Some(toolchain),
+        _ => None,
+    }
+}
+
+fn normalize_os(label: &str) -> Option<&'static str> {
+    let label = label.to_ascii_lowercase();
+    if label.starts_with("ubuntu") || label == "linux" {
+        Some("Linux")
+    } else if label.starts_with("windows") {
+        Some("Windows")
+    } else if label.starts_with("macos") || label == "osx" {
+        Some("macOS")
+    } else {
+        None
+    }
+}
+
+/// Expand `${{ matrix.<axis> }}` references into one string per axis value.
+fn expand(value: &str, matrix: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let Some(start) = value.find("${{") else {
+        return vec![value.to_string()];
+    };
+    let Some(len) = value[start..].find("}}") else {
+        return vec![value.to_string()];
+    };
+
+    let expr = value[start + 3..start + len].trim();
+    let rest = &value[start + len + 2..];
+    let prefix = &value[..start];
+
+    let Some(values) = expr.strip_prefix("matrix.").and_then(|axis| matrix.get(axis)) else {
+        // Unresolvable expressions (secrets, env) are left for the caller to ignore
+        return vec![format!("{}{}", prefix, rest)];
+    };
+
+    values.iter()
+        .flat_map(|v| expand(&format!("{}{}{}", prefix, v, rest), matrix))
+        .collect()
+}
+
+/// The `rust-version` declared in a Cargo manifest, if any.
+///
+/// A package inheriting it with `rust-version.workspace = true` gets the
+/// manifest's `[workspace.package]` value, like a virtual workspace does.
+pub fn declared_rust_version(manifest: &str) -> Option<String> {
+    let manifest: toml::Value = toml::from_str(manifest).ok()?;
+    let workspace = || manifest.get("workspace")?.get("package")?.get("rust-version");
+    let version = match manifest.get("package").and_then(|p| p.get("rust-version")) {
+        Some(toml::Value::Table(inherited)) if inherited.get("workspace").and_then(|w| w.as_bool()) == Some(true) => workspace(),
+        Some(version) => Some(version),
+        None => workspace(),
+    }?;
+
+    version.as_str().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ci::CiStep;
+
+    fn job_with(runs_on: &[&str], matrix: &[(&str, &[&str])], commands: &[&str]) -> CiJob {
+        CiJob {
+            name: "test".to_string(),
+            runs_on: runs_on.iter().map(|s| s.to_string()).collect(),
+            matrix: matrix.iter()
+                .map(|(k, v)| (k.to_string(), v.iter().map(|s| s.to_string()).collect()))
+                .collect(),
+            steps: commands.iter()
+                .map(|c| CiStep { command: Some(c.to_string()), ..Default::default() })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matrix_expansion() {
+        let mut coverage = MatrixCoverage::default();
+        coverage.add_job(&job_with(
+            &["${{ matrix.os }}"],
+            &[("os", &["ubuntu-latest", "macos-14"]), ("toolchain", &["stable", "1.70"])],
+            &["cargo +${{ matrix.toolchain }} test --all-features", "cargo build --no-default-features"],
+        ));
+
+        assert_eq!(coverage.operating_systems.iter().collect::<Vec<_>>(), vec!["Linux", "macOS"]);
+        assert!(coverage.rust.contains("1.70"));
+        assert!(coverage.has_msrv_job("1.70"));
+        assert!(coverage.has_msrv_job("1.70.0"));
+        assert!(coverage.feature_sets.contains("all features"));
+        assert!(coverage.feature_sets.contains("no default features"));
+    }
+
+    #[test]
+    fn test_msrv_mismatch() {
+        let mut coverage = MatrixCoverage::default();
+        coverage.add_job(&job_with(
+            &["ubuntu-latest"],
+            &[("toolchain", &["stable", "1.82.0"])],
+            &["cargo +${{ matrix.toolchain }} test"],
+        ));
+
+        assert!(coverage.has_msrv_job("1.82"));
+        assert!(!coverage.has_msrv_job("1.74"));
+        assert!(!coverage.has_msrv_job("1.82.1"));
+        assert!(!coverage.has_msrv_job("stable"));
+    }
+
+    #[test]
+    fn test_eol_versions() {
+        let mut coverage = MatrixCoverage::default();
+        coverage.add_node("v16.20");
+        coverage.add_node("22.x");
+        coverage.add_python("3.7");
+        coverage.add_python("3.12");
+        coverage.add_node("10");
+
+        let today = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        assert_eq!(coverage.eol_versions(today), vec!["Node.js 10", "Node.js 16", "Python 3.7"]);
+    }
+
+    #[test]
+    fn test_declared_rust_version() {
+        let manifest = "[package]\nname = \"x\"\nrust-version = \"1.74\"\n";
+        assert_eq!(declared_rust_version(manifest).as_deref(), Some("1.74"));
+        assert_eq!(declared_rust_version("[package]\nname = \"x\"\n"), None);
+
+        let inherited = "[package]\nname = \"x\"\nrust-version.workspace = true\n\n[workspace.package]\nrust-version = \"1.70\"\n";
+        assert_eq!(declared_rust_version(inherited).as_deref(), Some("1.70"));
+        assert_eq!(declared_rust_version("[workspace.package]\nrust-version = \"1.65\"\n").as_deref(), Some("1.65"));
+    }
+}
@@ -0,0 +1,358 @@
+mod github_actions;
+mod gitlab;
+mod circleci;
+mod travis;
+mod buildkite;
+mod drone;
+mod bitbucket;
+mod appveyor;
+mod cirrus;
+mod tekton;
+mod matrix;
+mod registry;
+
+pub use matrix::{MatrixCoverage, declared_rust_version};
+pub use registry::{CiProvider, CiProviderRegistry, ParserHook};
+
+use std::collections::{HashMap, HashSet};
+
+/// A CI configuration file parsed into a provider-independent model.
+#[derive(Debug, Clone)]
+pub struct CiConfig {
+    pub provider_id: String,
+    pub provider: String,
+    pub path: String,
+    /// Events that trigger the pipeline, where the provider declares them.
+    pub triggers: Vec<String>,
+    /// Whether the pipeline is triggered for pull/merge requests at all.
+    pub runs_on_pull_request: bool,
+    /// Whether a top-level `permissions:` block restricts the job token.
+    pub declares_permissions: bool,
+    pub jobs: Vec<CiJob>,
+}
+
+/// A CI configuration file found in the repository tree.
+#[derive(Debug, Clone)]
+pub struct CiFile {
+    pub provider_id: String,
+    pub provider: String,
+    pub path: String,
+    pub parser: Option<ParserHook>,
+}
+
+impl CiFile {
+    pub fn new(provider: &CiProvider, path: &str) -> Self {
+        Self {
+            provider_id: provider.id.clone(),
+            provider: provider.name.clone(),
+            path: path.to_string(),
+            parser: provider.parser,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CiJob {
+    /// Key identifying the job in the configuration file.
+    pub id: String,
+    pub name: String,
+    /// Reusable workflow the job delegates to instead of running steps.
+    pub uses: Option<String>,
+    pub declares_permissions: bool,
+    pub steps: Vec<CiStep>,
+    /// Runner labels or operating systems, possibly containing `${{ matrix.* }}` expressions.
+    pub runs_on: Vec<String>,
+    /// Container image the job runs in.
+    pub image: Option<String>,
+    /// Matrix axes and all values each axis takes.
+    pub matrix: HashMap<String, Vec<String>>,
+    /// Job can never run (`if: false`, hidden template, not part of any workflow).
+    pub disabled: bool,
+    /// Job conditions exclude pull request events even if the pipeline runs on them.
+    pub excludes_pull_requests: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CiStep {
+    pub command: Option<String>,
+    pub uses: Option<String>,
+    pub with: HashMap<String, String>,
+    pub disabled: bool,
+    pub allow_failure: bool,
+}
+
+impl CiConfig {
+    /// Jobs that invoke a test runner, together with the detected command.
+    pub fn test_jobs(&self) -> Vec<(&CiJob, Vec<&'static str>)> {
+        self.jobs.iter()
+            .filter_map(|job| {
+                let commands = job.test_commands();
+                if commands.is_empty() { None } else { Some((job, commands)) }
+            })
+            .collect()
+    }
+}
+
+impl CiJob {
+    /// Test runners invoked by this job, including disabled steps.
+    pub fn test_commands(&self) -> Vec<&'static str> {
+        let mut commands = Vec::new();
+        for step in &self.steps {
+            if let Some(command) = step.test_command() {
+                if !commands.contains(&command) {
+                    commands.push(command);
+                }
+            }
+        }
+        commands
+    }
+
+    /// Reason the tests in this job are not (reliably) executed, if any.
+    pub fn test_skip_reason(&self) -> Option<&'static str> {
+        if self.disabled {
+            return Some("job is disabled");
+        }
+
+        let test_steps: Vec<_> = self.steps.iter().filter(|s| s.test_command().is_some()).collect();
+        if !test_steps.is_empty() && test_steps.iter().all(|s| s.disabled) {
+            Some("test steps are disabled")
+        } else if !test_steps.is_empty() && test_steps.iter().all(|s| s.allow_failure) {
+            Some("test failures are ignored")
+        } else if test_steps.iter().any(|s| s.command.as_deref().is_some_and(|c| c.contains("--no-run"))) {
+            Some("tests are compiled but not run")
+        } else {
+            None
+        }
+    }
+}
+
+impl CiStep {
+    pub fn test_command(&self) -> Option<&'static str> {
+        if let Some(command) = &self.command {
+            return detect_test_command(command);
+        }
+
+        // actions-rs/cargo style actions take the subcommand as an input
+        let uses = self.uses.as_deref()?;
+        if uses.starts_with("actions-rs/cargo") {
+            return match self.with.get("command").map(String::as_str) {
+                Some("test") => Some("cargo test"),
+                Some("nextest") => Some("cargo nextest"),
+                _ => None,
+            };
+        }
+
+        None
+    }
+}
+
+/// Detect a test runner invocation in a shell snippet.
+pub fn detect_test_command(script: &str) -> Option<&'static str> {
+    script
+        .split(['\n', ';', '|', '&'])
+        .find_map(|segment| detect_in_segment(segment.trim()))
+}
+
+fn detect_in_segment(segment: &str) -> Option<&'static str> {
+    let mut tokens = segment
+        .split_whitespace()
+        .skip_while(|t| t.contains('=') || matches!(*t, "sudo" | "time" | "exec" | "-" | "run:"))
+        .peekable();
+
+    let program = tokens.next()?;
+    let program = program.rsplit('/').next().unwrap_or(program);
+    let mut args = tokens.filter(|t| !t.starts_with('-') && !t.starts_with('+'));
+
+    match program {
+        "cargo" => match args.next()? {
+            "test" => Some("cargo test"),
+            "nextest" => Some("cargo nextest"),
+            _ => None,
+        },
+        "npm" | "yarn" | "pnpm" => match args.next()? {
+            "test" | "t" => Some("npm test"),
+            "run" if args.next()?.starts_with("test") => Some("npm test"),
+            _ => None,
+        },
+        "pytest" | "py.test" => Some("pytest"),
+        "python" | "python3" if segment.contains("-m pytest") => Some("pytest"),
+        "go" if args.next()? == "test" => Some("go test"),
+        "mvn" | "mvnw" if args.any(|a| a == "test" || a == "verify") => Some("mvn test"),
+        "gradle" | "gradlew" if args.any(|a| a == "test" || a == "check") => Some("gradle test"),
+        _ => None,
+    }
+}
+
+/// Parse every CI configuration whose contents were fetched, following local includes.
+pub fn parse_configs(ci_files: &[CiFile], files: &HashMap<String, String>) -> Vec<CiConfig> {
+    let mut queue: Vec<CiFile> = ci_files.to_vec();
+    let mut seen: HashSet<String> = queue.iter().map(|f| f.path.clone()).collect();
+    let mut configs = Vec::new();
+
+    while let Some(file) = queue.pop() {
+        let Some(parsed) = file.parser.and_then(|parse| parse(files.get(&file.path)?)) else {
+            continue;
+        };
+
+        for include in &parsed.includes {
+            if seen.insert(include.clone()) {
+                queue.push(CiFile { path: include.clone(), ..file.clone() });
+            }
+        }
+
+        configs.push(CiConfig {
+            provider_id: file.provider_id,
+            provider: file.provider,
+            path: file.path,
+            triggers: parsed.triggers,
+            runs_on_pull_request: parsed.runs_on_pull_request,
+            declares_permissions: parsed.declares_permissions,
+            jobs: parsed.jobs,
+        });
+    }
+
+    configs.sort_by(|a, b| a.path.cmp(&b.path));
+    configs
+}
+
+/// Local files included by fetched configurations that have not been fetched yet.
+pub fn pending_includes(ci_files: &[CiFile], files: &HashMap<String, String>) -> Vec<CiFile> {
+    let mut pending: Vec<CiFile> = Vec::new();
+
+    for file in ci_files {
+        let Some(parsed) = file.parser.and_then(|parse| parse(files.get(&file.path)?)) else {
+            continue;
+        };
+
+        for include in parsed.includes {
+            let known = files.contains_key(&include)
+                || ci_files.iter().chain(&pending).any(|f| f.path == include);
+            if !known {
+                pending.push(CiFile { path: include, ..file.clone() });
+            }
+        }
+    }
+
+    pending
+}
+
+/// Output of a provider-specific parser.
+#[derive(Debug, Default)]
+pub struct ParsedConfig {
+    pub triggers: Vec<String>,
+    pub runs_on_pull_request: bool,
+    pub declares_permissions: bool,
+    pub jobs: Vec<CiJob>,
+    /// Repository-local files pulled in by the configuration.
+    pub includes: Vec<String>,
+}
+
+fn load(content: &str) -> Option<serde_yaml::Value> {
+    serde_yaml::from_str(content).ok()
+}
+
+/// All documents of a multi-document YAML stream.
+fn load_all(content: &str) -> Vec<serde_yaml::Value> {
+    use serde::Deserialize;
+
+    serde_yaml::Deserializer::from_str(content)
+        .filter_map(|doc| serde_yaml::Value::deserialize(doc).ok())
+        .filter(|doc| !doc.is_null())
+        .collect()
+}
+
+/// Flatten a YAML string or (nested) list of strings into script lines.
+fn script_lines(value: &serde_yaml::Value) -> Vec<String> {
+    match value {
+        serde_yaml::Value::String(s) => vec![s.clone()],
+        serde_yaml::Value::Sequence(items) => items.iter().flat_map(script_lines).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn scalar_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Scalars from a single value or a list of values.
+fn scalar_list(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    match value {
+        Some(serde_yaml::Value::Sequence(items)) => items.iter().filter_map(scalar_string).collect(),
+        Some(other) => scalar_string(other).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+fn is_false(value: Option<&serde_yaml::Value>) -> bool {
+    match value {
+        Some(serde_yaml::Value::Bool(b)) => !b,
+        Some(serde_yaml::Value::String(s)) => {
+            let s = s.trim();
+            s == "false" || s.trim_start_matches("${{").trim_end_matches("}}").trim() == "false"
+        }
+        _ => false,
+    }
+}
+
+fn is_true(value: Option<&serde_yaml::Value>) -> bool {
+    match value {
+        Some(serde_yaml::Value::Bool(b)) => *b,
+        Some(serde_yaml::Value::String(s)) => s.trim() == "true",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_test_command() {
+        assert_eq!(detect_test_command("cargo test --workspace"), Some("cargo test"));
+        assert_eq!(detect_test_command("cargo +nightly test"), Some("cargo test"));
+        assert_eq!(detect_test_command("cargo nextest run"), Some("cargo nextest"));
+        assert_eq!(detect_test_command("npm ci\nnpm run test:unit"), Some("npm test"));
+        assert_eq!(detect_test_command("python -m pytest -q"), Some("pytest"));
+        assert_eq!(detect_test_command("go vet ./... && go test ./..."), Some("go test"));
+        assert_eq!(detect_test_command("./mvnw -B verify"), Some("mvn test"));
+        assert_eq!(detect_test_command("./gradlew check"), Some("gradle test"));
+        assert_eq!(detect_test_command("RUST_LOG=debug cargo test"), Some("cargo test"));
+        assert_eq!(detect_test_command("cargo build --release"), None);
+        assert_eq!(detect_test_command("echo 'run the tests'"), None);
+    }
+
+    #[test]
+    fn test_parse_configs_follows_includes() {
+        let registry = CiProviderRegistry::builtin();
+        let root = CiFile::new(registry.provider_for(".gitlab-ci.yml").unwrap(), ".gitlab-ci.yml");
+        let files: HashMap<String, String> = [
+            (".gitlab-ci.yml".to_string(), "include:\n  - local: ci/test.yml\n".to_string()),
+            ("ci/test.yml".to_string(), "unit:\n  script: cargo test\n".to_string()),
+        ].into_iter().collect();
+
+        assert!(pending_includes(std::slice::from_ref(&root), &HashMap::new()).is_empty());
+        let configs = parse_configs(&[root], &files);
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[1].path, "ci/test.yml");
+        assert_eq!(configs[1].jobs[0].test_commands(), vec!["cargo test"]);
+    }
+
+    #[test]
+    fn test_skip_reason() {
+        let job = CiJob {
+            name: "test".to_string(),
+            steps: vec![CiStep {
+                command: Some("cargo test".to_string()),
+                allow_failure: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(job.test_skip_reason(), Some("test failures are ignored"));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::github::TreeEntry;
+use super::{
+    CiFile, ParsedConfig, load,
+    github_actions, gitlab, circleci, travis,
+    buildkite, drone, bitbucket, appveyor, cirrus, tekton,
+};
+
+/// Parses the contents of a CI configuration file into jobs.
+pub type ParserHook = fn(&str) -> Option<ParsedConfig>;
+
+/// A CI system and the repository paths where its configuration lives.
+#[derive(Debug, Clone)]
+pub struct CiProvider {
+    pub id: String,
+    pub name: String,
+    pub patterns: Vec<String>,
+    pub parser: Option<ParserHook>,
+}
+
+impl CiProvider {
+    pub fn new(id: &str, name: &str, patterns: &[&str], parser: Option<ParserHook>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            parser,
+        }
+    }
+
+    /// Parse a `NAME[:PARSER]=GLOB[,GLOB...]` provider definition, as given on the command line.
+    pub fn from_spec(spec: &str) -> std::result::Result<Self, String> {
+        let (head, globs) = spec.split_once('=')
+            .ok_or_else(|| format!("expected NAME[:PARSER]=GLOB[,GLOB...], got '{}'", spec))?;
+
+        let (name, parser_id) = match head.split_once(':') {
+            Some((name, parser)) => (name.trim(), Some(parser.trim())),
+            None => (head.trim(), None),
+        };
+
+        let patterns: Vec<String> = globs.split(',')
+            .map(str::trim)
+            .filter(|g| !g.is_empty())
+            .map(String::from)
+            .collect();
+
+        Self::custom(name, parser_id, patterns)
+            .map_err(|problem| format!("provider '{}' {}", spec, problem))
+    }
+
+    /// A user-defined provider, optionally reusing the parser of a built-in one by ID.
+    pub fn custom(name: &str, parser_id: Option<&str>, patterns: Vec<String>) -> std::result::Result<Self, String> {
+        if name.is_empty() || patterns.is_empty() {
+            return Err("needs a name and at least one path pattern".to_string());
+        }
+
+        let parser = match parser_id {
+            Some(id) => Some(CiProviderRegistry::parser(id).ok_or_else(|| format!("uses unknown CI parser '{}'", id))?),
+            None => None,
+        };
+
+        Ok(Self {
+            id: name.to_ascii_lowercase().replace(' ', "-"),
+            name: name.to_string(),
+            patterns,
+            parser,
+        })
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.patterns.iter().any(|pattern| crate::glob::matches(pattern, path))
+    }
+}
+
+/// The set of CI providers used to discover and parse configuration files.
+#[derive(Debug, Clone)]
+pub struct CiProviderRegistry {
+    providers: Vec<CiProvider>,
+}
+
+impl Default for CiProviderRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl CiProviderRegistry {
+    pub fn builtin() -> Self {
+        let providers = vec![
+            CiProvider::new("github-actions", "GitHub Actions",
+                &[".github/workflows/*.yml", ".github/workflows/*.yaml"],
+                Some(|c| Some(github_actions::parse(&load(c)?)))),
+            CiProvider::new("gitlab", "GitLab CI",
+                &[".gitlab-ci.yml", ".gitlab-ci.yaml"],
+                Some(|c| Some(gitlab::parse(&load(c)?)))),
+            CiProvider::new("circleci", "CircleCI",
+                &[".circleci/config.yml", ".circleci/config.yaml"],
+                Some(|c| Some(circleci::parse(&load(c)?)))),
+            CiProvider::new("travis", "Travis CI",
+                &[".travis.yml", ".travis.yaml"],
+                Some(|c| Some(travis::parse(&load(c)?)))),
+            CiProvider::new("jenkins", "Jenkins", &["Jenkinsfile", "**/Jenkinsfile"], None),
+            CiProvider::new("azure-pipelines", "Azure Pipelines",
+                &["azure-pipelines.yml", "azure-pipelines.yaml", ".azure-pipelines/*.yml", ".azure-pipelines/*.yaml"],
+                None),
+            CiProvider::new("buildkite", "Buildkite",
+                &[".buildkite/*.yml", ".buildkite/*.yaml", "buildkite.yml", "buildkite.yaml"],
+                Some(|c| Some(buildkite::parse(&load(c)?)))),
+            CiProvider::new("drone", "Drone",
+                &[".drone.yml", ".drone.yaml"],
+                Some(drone::parse)),
+            CiProvider::new("woodpecker", "Woodpecker CI",
+                &[".woodpecker.yml", ".woodpecker.yaml", ".woodpecker/*.yml", ".woodpecker/*.yaml"],
+                Some(drone::parse)),
+            CiProvider::new("bitbucket", "Bitbucket Pipelines",
+                &["bitbucket-pipelines.yml"],
+                Some(|c| Some(bitbucket::parse(&load(c)?)))),
+            CiProvider::new("appveyor", "AppVeyor",
+                &["appveyor.yml", ".appveyor.yml"],
+                Some(|c| Some(appveyor::parse(&load(c)?)))),
+            CiProvider::new("cirrus", "Cirrus CI",
+                &[".cirrus.yml", ".cirrus.yaml"],
+                Some(|c| Some(cirrus::parse(&load(c)?)))),
+            CiProvider::new("tekton", "Tekton",
+                &[".tekton/*.yml", ".tekton/*.yaml"],
+                Some(tekton::parse)),
+        ];
+
+        Self { providers }
+    }
+
+    /// Add a provider; user-defined providers take precedence over built-in ones.
+    pub fn register(&mut self, provider: CiProvider) {
+        self.providers.insert(0, provider);
+    }
+
+    pub fn providers(&self) -> &[CiProvider] {
+        &self.providers
+    }
+
+    /// Look up a built-in parser by provider ID so custom providers can reuse it.
+    pub fn parser(id: &str) -> Option<ParserHook> {
+        Self::builtin().providers.into_iter()
+            .find(|p| p.id == id)
+            .and_then(|p| p.parser)
+    }
+
+    pub fn provider_for(&self, path: &str) -> Option<&CiProvider> {
+        self.providers.iter().find(|p| p.matches(path))
+    }
+
+    /// Find every CI configuration file in the tree.
+    pub fn discover(&self, tree: &[TreeEntry]) -> Vec<CiFile> {
+        tree.iter()
+            .filter(|entry| entry.entry_type == "blob")
+            .filter_map(|entry| Some(CiFile::new(self.provider_for(&entry.path)?, &entry.path)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider_id(path: &str) -> Option<String> {
+        CiProviderRegistry::builtin().provider_for(path).map(|p| p.id.clone())
+    }
+
+    #[test]
+    fn test_builtin_provider_paths() {
+        let cases = [
+            (".github/workflows/ci.yml", "github-actions"),
+            (".github/workflows/release.yaml", "github-actions"),
+            (".gitlab-ci.yml", "gitlab"),
+            (".circleci/config.yml", "circleci"),
+            (".travis.yml", "travis"),
+            ("Jenkinsfile", "jenkins"),
+            ("ci/Jenkinsfile", "jenkins"),
+            ("azure-pipelines.yml", "azure-pipelines"),
+            (".buildkite/pipeline.yml", "buildkite"),
+            (".drone.yml", "drone"),
+            (".woodpecker/test.yaml", "woodpecker"),
+            ("bitbucket-pipelines.yml", "bitbucket"),
+            ("appveyor.yml", "appveyor"),
+            (".cirrus.yml", "cirrus"),
+            (".tekton/pull-request.yaml", "tekton"),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(provider_id(path).as_deref(), Some(expected), "{}", path);
+        }
+
+        assert_eq!(provider_id(".github/workflows/README.md"), None);
+        assert_eq!(provider_id("src/main.rs"), None);
+    }
+
+    #[test]
+    fn test_custom_provider_spec() {
+        let provider = CiProvider::from_spec("Forgejo Actions:github-actions=.forgejo/workflows/*.yml").unwrap();
+        assert_eq!(provider.id, "forgejo-actions");
+        assert!(provider.parser.is_some());
+        assert!(provider.matches(".forgejo/workflows/ci.yml"));
+
+        let mut registry = CiProviderRegistry::builtin();
+        registry.register(CiProvider::from_spec("Concourse=ci/pipeline.yml").unwrap());
+        assert_eq!(registry.provider_for("ci/pipeline.yml").map(|p| p.name.as_str()), Some("Concourse"));
+
+        assert!(CiProvider::from_spec("no-patterns=").is_err());
+        assert!(CiProvider::from_spec("Bad:unknown=ci.yml").is_err());
+    }
+}
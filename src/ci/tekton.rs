@@ -0,0 +1,116 @@
+use serde_yaml::Value;
+use super::{CiJob, CiStep, ParsedConfig, load_all, scalar_list};
+
+/// Pipelines-as-Code annotation listing the events a PipelineRun reacts to.
+const ON_EVENT_ANNOTATION: &str = "pipelinesascode.tekton.dev/on-event";
+
+pub(super) fn parse(content: &str) -> Option<ParsedConfig> {
+    let documents = load_all(content);
+    if documents.is_empty() {
+        return None;
+    }
+
+    let mut runs_on_pull_request = false;
+    let mut jobs = Vec::new();
+
+    for doc in &documents {
+        let events = doc.get("metadata")
+            .and_then(|m| m.get("annotations"))
+            .and_then(|a| a.get(ON_EVENT_ANNOTATION))
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        runs_on_pull_request |= events.contains("pull_request");
+
+        let name = doc.get("metadata")
+            .and_then(|m| m.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or("task");
+        collect_tasks(doc, name, &mut jobs);
+    }
+
+    Some(ParsedConfig { runs_on_pull_request, jobs, ..Default::default() })
+}
+
+/// Walk the document and turn every list of container steps into a job.
+fn collect_tasks(value: &Value, name: &str, jobs: &mut Vec<CiJob>) {
+    let Some(mapping) = value.as_mapping() else {
+        if let Some(items) = value.as_sequence() {
+            items.iter().for_each(|item| collect_tasks(item, name, jobs));
+        }
+        return;
+    };
+
+    let name = value.get("name").and_then(Value::as_str).unwrap_or(name);
+
+    if let Some(steps) = value.get("steps").and_then(Value::as_sequence) {
+        jobs.push(CiJob {
+            id: name.to_string(),
+            name: name.to_string(),
+            runs_on: vec!["linux".to_string()],
+            image: steps.iter().find_map(|s| s.get("image")?.as_str()).map(String::from),
+            steps: steps.iter()
+                .filter_map(|step| {
+                    let command = match step.get("script").and_then(Value::as_str) {
+                        Some(script) => script.to_string(),
+                        None => {
+                            let mut parts = scalar_list(step.get("command"));
+                            parts.extend(scalar_list(step.get("args")));
+                            if parts.is_empty() {
+                                return None;
+                            }
+                            parts.join(" ")
+                        }
+                    };
+                    Some(CiStep { command: Some(command), ..Default::default() })
+                })
+                .collect(),
+            ..Default::default()
+        });
+        return;
+    }
+
+    for (key, child) in mapping {
+        if key.as_str() != Some("metadata") {
+            collect_tasks(child, name, jobs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tekton_pipeline_run() {
+        let parsed = parse(r#"
+apiVersion: tekton.dev/v1
+kind: PipelineRun
+metadata:
+  name: pull-request
+  annotations:
+    pipelinesascode.tekton.dev/on-event: "[pull_request]"
+spec:
+  pipelineSpec:
+    tasks:
+      - name: unit-tests
+        taskSpec:
+          steps:
+            - name: test
+              image: golang:1.22
+              script: |
+                go test ./...
+      - name: lint
+        taskSpec:
+          steps:
+            - image: golangci/golangci-lint
+              command: [golangci-lint]
+              args: [run]
+"#).unwrap();
+
+        assert!(parsed.runs_on_pull_request);
+        assert_eq!(parsed.jobs.len(), 2);
+        assert_eq!(parsed.jobs[0].name, "unit-tests");
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["go test"]);
+        assert!(parsed.jobs[1].test_commands().is_empty());
+    }
+}
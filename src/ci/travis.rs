@@ -0,0 +1,112 @@
+use serde_yaml::Value;
+use std::collections::HashMap;
+use super::{CiJob, CiStep, ParsedConfig, script_lines, scalar_list};
+
+const SCRIPT_PHASES: &[&str] = &["before_install", "install", "before_script", "script"];
+
+/// Keys that select runtime versions and expand into build matrix axes.
+const RUNTIME_KEYS: &[&str] = &["rust", "node_js", "python", "go", "jdk"];
+
+pub(super) fn parse(yaml: &Value) -> ParsedConfig {
+    let language = yaml.get("language").and_then(Value::as_str).unwrap_or("");
+    let mut root = parse_job("default", yaml, language);
+    if root.runs_on.is_empty() {
+        root.runs_on.push("linux".to_string());
+    }
+    let mut jobs = vec![root];
+
+    let includes = yaml.get("jobs")
+        .or_else(|| yaml.get("matrix"))
+        .and_then(|m| m.get("include"))
+        .and_then(Value::as_sequence);
+
+    for (idx, include) in includes.into_iter().flatten().enumerate() {
+        let name = include.get("name")
+            .and_then(Value::as_str)
+            .map(String::from)
+            .unwrap_or_else(|| format!("job {}", idx + 1));
+        let mut job = parse_job(&name, include, language);
+        if job.runs_on.is_empty() {
+            job.runs_on = jobs[0].runs_on.clone();
+        }
+        jobs.push(job);
+    }
+
+    // Travis builds pull requests unless explicitly turned off in the settings
+    ParsedConfig { runs_on_pull_request: true, jobs, ..Default::default() }
+}
+
+fn parse_job(name: &str, job: &Value, language: &str) -> CiJob {
+    let mut steps: Vec<CiStep> = SCRIPT_PHASES.iter()
+        .filter_map(|phase| job.get(*phase))
+        .flat_map(script_lines)
+        .map(|line| CiStep { command: Some(line), ..Default::default() })
+        .collect();
+
+    if job.get("script").is_none() {
+        if let Some(default) = default_script(language) {
+            steps.push(CiStep { command: Some(default.to_string()), ..Default::default() });
+        }
+    }
+
+    let condition = job.get("if").and_then(Value::as_str).unwrap_or("");
+
+    let matrix: HashMap<String, Vec<String>> = RUNTIME_KEYS.iter()
+        .filter_map(|key| Some((key.to_string(), scalar_list(Some(job.get(*key)?)))))
+        .collect();
+
+    CiJob {
+        id: name.to_string(),
+        name: name.to_string(),
+        steps,
+        runs_on: scalar_list(job.get("os")),
+        image: None,
+        matrix,
+        disabled: false,
+        excludes_pull_requests: condition.contains("type = push") || condition.contains("type != pull_request"),
+        ..Default::default()
+    }
+}
+
+/// The script Travis runs when a job does not define one.
+fn default_script(language: &str) -> Option<&'static str> {
+    match language {
+        "rust" => Some("cargo build --verbose && cargo test --verbose"),
+        "node_js" => Some("npm test"),
+        "go" => Some("go test ./..."),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_travis_default_script() {
+        let yaml: Value = serde_yaml::from_str("language: rust\nrust: [stable, nightly]").unwrap();
+        let parsed = parse(&yaml);
+        assert_eq!(parsed.jobs[0].test_commands(), vec!["cargo test"]);
+        assert_eq!(parsed.jobs[0].runs_on, vec!["linux"]);
+        assert_eq!(parsed.jobs[0].matrix["rust"], vec!["stable", "nightly"]);
+    }
+
+    #[test]
+    fn test_parse_travis_includes() {
+        let yaml: Value = serde_yaml::from_str(r#"
+language: python
+script: flake8
+jobs:
+  include:
+    - name: tests
+      if: type = push
+      script: pytest
+"#).unwrap();
+
+        let parsed = parse(&yaml);
+        assert_eq!(parsed.jobs.len(), 2);
+        assert!(parsed.jobs[0].test_commands().is_empty());
+        assert_eq!(parsed.jobs[1].test_commands(), vec!["pytest"]);
+        assert!(parsed.jobs[1].excludes_pull_requests);
+    }
+}
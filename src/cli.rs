@@ -1,12 +1,22 @@
-use clap::Parser;
+use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+use crate::analyzers::{BusFactorAlgorithm, DEFAULT_BUS_FACTOR_WINDOWS};
+use crate::ci::CiProvider;
+use crate::coverage::DEFAULT_BADGE_ENDPOINT;
+use crate::identity::parse_alias;
+use crate::links::DEFAULT_LINK_CONCURRENCY;
 
 #[derive(Parser, Debug)]
 #[command(name = "repo-health")]
 #[command(version, about = "Analyze GitHub repository health", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Repository in format "owner/repo" or full GitHub URL
-    #[arg(value_name = "REPOSITORY")]
-    pub repository: String,
+    #[arg(value_name = "REPOSITORY", required = true)]
+    pub repository: Option<String>,
 
     /// GitHub personal access token (or set GITHUB_TOKEN env var). Optional for public repos.
     #[arg(short, long, env = "GITHUB_TOKEN")]
@@ -19,6 +29,76 @@ pub struct Cli {
     /// Don't print to stdout, only write file
     #[arg(short, long)]
     pub quiet: bool,
+
+    /// Additional CI provider as NAME[:PARSER]=GLOB[,GLOB...], e.g. "Forgejo:github-actions=.forgejo/workflows/*.yml"
+    #[arg(long = "ci-provider", value_name = "SPEC", value_parser = CiProvider::from_spec)]
+    pub ci_providers: Vec<CiProvider>,
+
+    /// Coverage report to use instead of one found in the repository (lcov, Cobertura, JaCoCo, llvm-cov or tarpaulin JSON)
+    #[arg(long, value_name = "PATH")]
+    pub coverage: Option<PathBuf>,
+
+    /// shields.io-compatible endpoint used to resolve coverage badge percentages
+    #[arg(long, value_name = "URL", default_value = DEFAULT_BADGE_ENDPOINT)]
+    pub badge_endpoint: String,
+
+    /// Only contact the GitHub API; skip coverage badge resolution and external link checks
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Scan a few Rust sources for inline tests without a token (always on with one)
+    #[arg(long)]
+    pub scan_sources: bool,
+
+    /// Check external links in the README and docs/
+    #[arg(long)]
+    pub check_external_links: bool,
+
+    /// Only check external links to this host and its subdomains (repeatable)
+    #[arg(long = "link-host", value_name = "HOST")]
+    pub link_hosts: Vec<String>,
+
+    /// Recent periods, in months, over which the bus factor is computed (comma-separated)
+    #[arg(long = "bus-factor-window", value_name = "MONTHS", value_delimiter = ',', default_values_t = DEFAULT_BUS_FACTOR_WINDOWS.to_vec())]
+    pub bus_factor_windows: Vec<u32>,
+
+    /// How to measure the bus factor: "commits" or "truck-factor" (file authorship; needs --local)
+    #[arg(long, value_name = "ALGORITHM", default_value = "commits")]
+    pub bus_factor_algorithm: BusFactorAlgorithm,
+
+    /// Local clone of the repository, used for history-based metrics such as the truck factor
+    #[arg(long, value_name = "PATH")]
+    pub local: Option<PathBuf>,
+
+    /// Treat contributors whose login matches this glob as bots (repeatable), e.g. "*-automation"
+    #[arg(long = "bot-pattern", value_name = "GLOB")]
+    pub bot_patterns: Vec<String>,
+
+    /// Count contributions of one login as another's (repeatable), as ALIAS=CANONICAL
+    #[arg(long = "alias", value_name = "ALIAS=CANONICAL", value_parser = parse_alias)]
+    pub aliases: Vec<(String, String)>,
+
+    /// Maximum number of external link checks in flight at once
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_LINK_CONCURRENCY)]
+    pub link_concurrency: usize,
+
+    /// Configuration file layered over the repository's .repo-health.toml
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Only run these analyzers (comma-separated IDs, see `list-analyzers`)
+    #[arg(long, value_name = "ID", value_delimiter = ',')]
+    pub only: Vec<String>,
+
+    /// Don't run these analyzers (comma-separated IDs, see `list-analyzers`)
+    #[arg(long, value_name = "ID", value_delimiter = ',')]
+    pub skip: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// List the available analyzers with their IDs, weights and descriptions
+    ListAnalyzers,
 }
 
 /// Parse repository input from either "owner/repo" or full GitHub URL
@@ -62,6 +142,19 @@ pub fn parse_repo_input(input: &str) -> crate::Result<(String, String)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_list_analyzers_command() {
+        let cli = Cli::try_parse_from(["repo-health", "list-analyzers"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::ListAnalyzers)));
+        assert!(cli.repository.is_none());
+
+        let cli = Cli::try_parse_from(["repo-health", "octocat/Hello-World", "--skip", "tests,community"]).unwrap();
+        assert_eq!(cli.repository.as_deref(), Some("octocat/Hello-World"));
+        assert_eq!(cli.skip, ["tests", "community"]);
+
+        assert!(Cli::try_parse_from(["repo-health"]).is_err());
+    }
+
     #[test]
     fn test_parse_owner_repo() {
         let (owner, repo) = parse_repo_input("octocat/Hello-World").unwrap();
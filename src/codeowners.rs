@@ -0,0 +1,181 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::github::TreeEntry;
+use crate::test_layout::module_of;
+
+/// Where GitHub looks for a CODEOWNERS file; the first one found is used.
+pub const CODEOWNERS_PATHS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub pattern: String,
+    /// Empty when the pattern explicitly removes ownership
+    pub owners: Vec<String>,
+    pub line: usize,
+}
+
+impl Rule {
+    /// GitHub's gitignore-style matching: unanchored patterns match at any depth and
+    /// a pattern matching a directory covers everything below it, except `dir/*`.
+    fn matches(&self, path: &str) -> bool {
+        let pattern = self.pattern.as_str();
+        let directory_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_start_matches('/').trim_end_matches('/');
+        let anchored = pattern.starts_with('/') || trimmed.contains('/');
+
+        let glob = if anchored || trimmed.starts_with("**") {
+            trimmed.to_string()
+        } else {
+            format!("**/{}", trimmed)
+        };
+
+        if !directory_only && crate::glob::matches(&glob, path) {
+            return true;
+        }
+        !pattern.ends_with("/*") && crate::glob::matches(&format!("{}/**", glob), path)
+    }
+}
+
+/// A parsed CODEOWNERS file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeOwners {
+    pub path: String,
+    pub rules: Vec<Rule>,
+}
+
+/// How much of the tree CODEOWNERS covers, and by whom.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OwnershipCoverage {
+    pub files: usize,
+    pub owned_files: usize,
+    /// Directories with unowned files: `(directory, unowned files, files)`, most unowned first
+    pub unowned_dirs: Vec<(String, usize, usize)>,
+    /// Files per owner, most files first
+    pub owners: Vec<(String, usize)>,
+    /// Directories whose owned files all belong to one individual: `(directory, owner, files)`
+    pub single_owner_dirs: Vec<(String, String, usize)>,
+}
+
+impl CodeOwners {
+    pub fn parse(path: &str, content: &str) -> Self {
+        let rules = content.lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let line = line.split(" #").next().unwrap_or("").trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut fields = line.split_whitespace();
+                let pattern = fields.next()?.to_string();
+                Some(Rule { pattern, owners: fields.map(String::from).collect(), line: index + 1 })
+            })
+            .collect();
+
+        Self { path: path.to_string(), rules }
+    }
+
+    /// The CODEOWNERS file GitHub would use, if its contents were fetched.
+    pub fn find(tree: &[TreeEntry], files: &HashMap<String, String>) -> Option<Self> {
+        CODEOWNERS_PATHS.iter()
+            .find(|path| tree.iter().any(|e| e.path == **path))
+            .and_then(|path| Some(Self::parse(path, files.get(*path)?)))
+    }
+
+    /// Owners of a path: the last matching rule wins.
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rules.iter()
+            .rev()
+            .find(|rule| rule.matches(path))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn coverage(&self, tree: &[TreeEntry]) -> OwnershipCoverage {
+        let mut coverage = OwnershipCoverage::default();
+        let mut owners: HashMap<&str, usize> = HashMap::new();
+        // directory -> (unowned files, files, owners of owned files)
+        let mut directories: BTreeMap<String, (usize, usize, BTreeMap<&str, usize>)> = BTreeMap::new();
+
+        for entry in tree.iter().filter(|e| e.entry_type == "blob" && !crate::languages::is_vendored(&e.path)) {
+            let file_owners = self.owners_of(&entry.path);
+            let directory = directories.entry(module_of(&entry.path)).or_default();
+            coverage.files += 1;
+            directory.1 += 1;
+
+            if file_owners.is_empty() {
+                directory.0 += 1;
+                continue;
+            }
+            coverage.owned_files += 1;
+            for owner in file_owners {
+                *owners.entry(owner).or_default() += 1;
+                *directory.2.entry(owner).or_default() += 1;
+            }
+        }
+
+        for (directory, (unowned, files, dir_owners)) in directories {
+            if unowned > 0 {
+                coverage.unowned_dirs.push((directory.clone(), unowned, files));
+            }
+            if let [(owner, owned)] = dir_owners.into_iter().collect::<Vec<_>>().as_slice() {
+                if !is_team(owner) {
+                    coverage.single_owner_dirs.push((directory, owner.to_string(), *owned));
+                }
+            }
+        }
+        coverage.unowned_dirs.sort_by_key(|(_, unowned, _)| std::cmp::Reverse(*unowned));
+        coverage.single_owner_dirs.sort_by_key(|(_, _, files)| std::cmp::Reverse(*files));
+
+        coverage.owners = owners.into_iter().map(|(o, n)| (o.to_string(), n)).collect();
+        coverage.owners.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        coverage
+    }
+}
+
+/// `@org/team` owners are teams; `@user` and email owners are individuals.
+pub fn is_team(owner: &str) -> bool {
+    owner.starts_with('@') && owner.contains('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_pattern_semantics() {
+        let owners = CodeOwners::parse(".github/CODEOWNERS", "\
+# Default owners
+*       @acme/core
+*.js    @js-owner  # inline comment
+/build/logs/ @doctocat
+docs/*  docs@example.com
+apps/   @octocat
+/scripts/ @doctocat @octocat
+/scripts/generated.sh
+");
+
+        assert_eq!(owners.owners_of("README.md"), ["@acme/core"]);
+        assert_eq!(owners.owners_of("web/src/app.js"), ["@js-owner"]);
+        assert_eq!(owners.owners_of("build/logs/today/x.log"), ["@doctocat"]);
+        assert_eq!(owners.owners_of("docs/intro.md"), ["docs@example.com"]);
+        // `docs/*` does not reach into subdirectories
+        assert_eq!(owners.owners_of("docs/guides/setup.md"), ["@acme/core"]);
+        assert_eq!(owners.owners_of("src/apps/main.rs"), ["@octocat"]);
+        assert_eq!(owners.owners_of("scripts/generated.sh"), [] as [String; 0]);
+        assert_eq!(owners.rules[1].line, 3);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let owners = CodeOwners::parse("CODEOWNERS", "/src/core/ @alice\n/src/cli/ @acme/cli @alice\n");
+        let tree = vec![
+            TreeEntry::blob("src/core/a.rs"), TreeEntry::blob("src/core/b.rs"), TreeEntry::blob("src/cli/main.rs"),
+            TreeEntry::blob("README.md"), TreeEntry::blob("tests/it.rs"), TreeEntry::blob("vendor/dep.rs"),
+        ];
+
+        let coverage = owners.coverage(&tree);
+        assert_eq!((coverage.owned_files, coverage.files), (3, 5));
+        assert_eq!(coverage.owners, vec![("@alice".to_string(), 3), ("@acme/cli".to_string(), 1)]);
+        assert_eq!(coverage.unowned_dirs, vec![("(root)".to_string(), 1, 1), ("tests".to_string(), 1, 1)]);
+        assert_eq!(coverage.single_owner_dirs, vec![("src/core".to_string(), "@alice".to_string(), 2)]);
+    }
+}
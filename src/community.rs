@@ -0,0 +1,124 @@
+use crate::github::TreeEntry;
+
+/// Where GitHub looks for community health files, in order of precedence.
+const COMMUNITY_DIRS: &[&str] = &[".github/", "", "docs/"];
+
+const TEMPLATE_EXTENSIONS: &[&str] = &[".md", ".yml", ".yaml"];
+
+/// Find a community health file such as `CONTRIBUTING` in `.github/`, the root or `docs/`,
+/// matching the name case-insensitively with any extension (or none).
+pub fn find_file<'a>(tree: &'a [TreeEntry], stem: &str) -> Option<&'a str> {
+    COMMUNITY_DIRS.iter().find_map(|dir| {
+        tree.iter()
+            .filter(|e| e.entry_type == "blob")
+            .find(|e| {
+                e.path.strip_prefix(dir)
+                    .filter(|name| !name.contains('/'))
+                    .is_some_and(|name| {
+                        let base = name.split_once('.').map(|(base, _)| base).unwrap_or(name);
+                        base.eq_ignore_ascii_case(stem)
+                    })
+            })
+            .map(|e| e.path.as_str())
+    })
+}
+
+/// Issue templates configured for the repository.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IssueTemplates {
+    /// Markdown templates in `.github/ISSUE_TEMPLATE/`
+    pub markdown: Vec<String>,
+    /// YAML issue forms in `.github/ISSUE_TEMPLATE/`
+    pub forms: Vec<String>,
+    /// Single legacy `ISSUE_TEMPLATE.md`
+    pub legacy: Option<String>,
+    /// `.github/ISSUE_TEMPLATE/config.yml` chooser configuration
+    pub config: Option<String>,
+}
+
+impl IssueTemplates {
+    pub fn is_empty(&self) -> bool {
+        self.markdown.is_empty() && self.forms.is_empty() && self.legacy.is_none()
+    }
+}
+
+pub fn issue_templates(tree: &[TreeEntry]) -> IssueTemplates {
+    let mut templates = IssueTemplates {
+        legacy: find_file(tree, "ISSUE_TEMPLATE").map(String::from),
+        ..Default::default()
+    };
+
+    for entry in tree.iter().filter(|e| e.entry_type == "blob") {
+        let Some(name) = template_in_dir(&entry.path, "ISSUE_TEMPLATE") else { continue };
+        let lower = name.to_lowercase();
+
+        if lower == "config.yml" || lower == "config.yaml" {
+            templates.config = Some(entry.path.clone());
+        } else if lower.ends_with(".md") {
+            templates.markdown.push(entry.path.clone());
+        } else if lower.ends_with(".yml") || lower.ends_with(".yaml") {
+            templates.forms.push(entry.path.clone());
+        }
+    }
+
+    templates
+}
+
+/// Pull request templates: a single `PULL_REQUEST_TEMPLATE.md` or a `PULL_REQUEST_TEMPLATE/` directory.
+pub fn pull_request_templates(tree: &[TreeEntry]) -> Vec<String> {
+    let single = find_file(tree, "PULL_REQUEST_TEMPLATE").map(String::from);
+
+    single.into_iter()
+        .chain(tree.iter()
+            .filter(|e| e.entry_type == "blob" && template_in_dir(&e.path, "PULL_REQUEST_TEMPLATE").is_some())
+            .map(|e| e.path.clone()))
+        .collect()
+}
+
+/// File name of a template directly inside a `<dir_name>/` template directory in a community location.
+fn template_in_dir<'a>(path: &'a str, dir_name: &str) -> Option<&'a str> {
+    COMMUNITY_DIRS.iter().find_map(|dir| {
+        let rest = path.strip_prefix(dir)?;
+        let (template_dir, name) = rest.split_once('/')?;
+        let is_template = template_dir.eq_ignore_ascii_case(dir_name)
+            && !name.contains('/')
+            && TEMPLATE_EXTENSIONS.iter().any(|ext| name.to_lowercase().ends_with(ext));
+        is_template.then_some(name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(paths: &[&str]) -> Vec<TreeEntry> {
+        paths.iter().copied().map(TreeEntry::blob).collect()
+    }
+
+    #[test]
+    fn test_find_file() {
+        let tree = tree(&["contributing.md", ".github/CONTRIBUTING.md", "docs/CODE_OF_CONDUCT.rst", "src/SUPPORT.md"]);
+
+        assert_eq!(find_file(&tree, "CONTRIBUTING"), Some(".github/CONTRIBUTING.md"));
+        assert_eq!(find_file(&tree, "CODE_OF_CONDUCT"), Some("docs/CODE_OF_CONDUCT.rst"));
+        assert_eq!(find_file(&tree, "SUPPORT"), None);
+    }
+
+    #[test]
+    fn test_templates() {
+        let tree = tree(&[
+            ".github/ISSUE_TEMPLATE/bug_report.yml",
+            ".github/ISSUE_TEMPLATE/feature.md",
+            ".github/ISSUE_TEMPLATE/config.yml",
+            ".github/PULL_REQUEST_TEMPLATE/release.md",
+            "docs/pull_request_template.md",
+        ]);
+
+        let issues = issue_templates(&tree);
+        assert_eq!(issues.forms, vec![".github/ISSUE_TEMPLATE/bug_report.yml"]);
+        assert_eq!(issues.markdown, vec![".github/ISSUE_TEMPLATE/feature.md"]);
+        assert!(issues.config.is_some() && issues.legacy.is_none());
+
+        assert_eq!(pull_request_templates(&tree), vec!["docs/pull_request_template.md", ".github/PULL_REQUEST_TEMPLATE/release.md"]);
+    }
+}
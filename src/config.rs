@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use serde::Deserialize;
+use crate::ci::CiProvider;
+use crate::scoring::GradeBoundaries;
+use crate::suppressions::Suppression;
+use crate::{Result, RepoHealthError};
+
+/// Configuration file looked up at the root of the analysed repository.
+pub const CONFIG_FILE: &str = ".repo-health.toml";
+
+/// Project configuration from `.repo-health.toml` and `--config`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Category weights by analyzer ID; renormalised to sum to 1
+    pub weights: BTreeMap<String, f64>,
+    pub analyzers: AnalyzerSelection,
+    pub thresholds: Thresholds,
+    pub grades: GradeBoundaries,
+    pub paths: ExtraPaths,
+    /// Accepted findings, as `[[suppress]]` tables
+    #[serde(rename = "suppress")]
+    pub suppressions: Vec<Suppression>,
+    /// Additional CI systems, as `[[ci_provider]]` tables
+    #[serde(rename = "ci_provider")]
+    pub ci_providers: Vec<CiProviderConfig>,
+}
+
+/// A CI system the built-in providers don't know, like `--ci-provider`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CiProviderConfig {
+    pub name: String,
+    /// ID of the built-in provider whose parser reads the files, e.g. `github-actions`
+    #[serde(default)]
+    pub parser: Option<String>,
+    /// Globs of the provider's configuration files
+    pub paths: Vec<String>,
+}
+
+impl CiProviderConfig {
+    pub fn provider(&self) -> Result<CiProvider> {
+        CiProvider::custom(self.name.trim(), self.parser.as_deref().map(str::trim), self.paths.clone())
+            .map_err(|problem| RepoHealthError::InvalidConfig(format!("CI provider '{}' {}", self.name, problem)))
+    }
+}
+
+/// Analyzers to run, by ID; `--only` and `--skip` take precedence.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyzerSelection {
+    /// Run only these analyzers (all when empty)
+    pub enable: Vec<String>,
+    pub disable: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    /// Line coverage percentage considered good
+    pub coverage: f64,
+    /// Branch coverage percentage considered good
+    pub branch_coverage: f64,
+    /// Test-to-source file ratios overriding the per-ecosystem targets
+    pub test_ratio: Option<RatioTargets>,
+    /// Bus factor score by bus factor (index 0, 1, 2, ...); the last entry applies to anything higher
+    pub bus_factor_scores: Vec<f64>,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            coverage: 80.0,
+            branch_coverage: 60.0,
+            test_ratio: None,
+            bus_factor_scores: vec![0.0, 10.0, 40.0, 70.0, 70.0, 100.0],
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn bus_factor_score(&self, bus_factor: usize) -> f64 {
+        self.bus_factor_scores.get(bus_factor)
+            .or(self.bus_factor_scores.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RatioTargets {
+    pub good: f64,
+    pub excellent: f64,
+}
+
+/// Globs for documentation and tests outside the conventional locations.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExtraPaths {
+    pub docs: Vec<String>,
+    pub tests: Vec<String>,
+}
+
+impl ExtraPaths {
+    pub fn is_doc(&self, path: &str) -> bool {
+        self.docs.iter().any(|pattern| crate::glob::matches(pattern, path))
+    }
+
+    pub fn is_test(&self, path: &str) -> bool {
+        self.tests.iter().any(|pattern| crate::glob::matches(pattern, path))
+    }
+}
+
+impl Config {
+    pub fn parse(content: &str) -> Result<Self> {
+        Self::from_layers(&[content])
+    }
+
+    /// Merge configuration files, later ones overriding earlier ones key by key.
+    pub fn from_layers(layers: &[&str]) -> Result<Self> {
+        let mut merged = toml::Table::new();
+        for layer in layers {
+            let table: toml::Table = toml::from_str(layer)
+                .map_err(|e| RepoHealthError::InvalidConfig(e.message().to_string()))?;
+            merge(&mut merged, table);
+        }
+
+        let config: Self = toml::Value::Table(merged).try_into()
+            .map_err(|e: toml::de::Error| RepoHealthError::InvalidConfig(e.message().to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(RepoHealthError::InvalidConfig(message));
+        let percent = |value: f64| (0.0..=100.0).contains(&value);
+
+        if let Some((id, weight)) = self.weights.iter().find(|(_, w)| !w.is_finite() || **w < 0.0) {
+            return invalid(format!("weight for {} must be a non-negative number, got {}", id, weight));
+        }
+        if !percent(self.thresholds.coverage) || !percent(self.thresholds.branch_coverage) {
+            return invalid("coverage thresholds must be between 0 and 100".to_string());
+        }
+        if let Some(ratio) = self.thresholds.test_ratio {
+            if !(ratio.good > 0.0 && ratio.excellent > ratio.good) {
+                return invalid("test_ratio needs 0 < good < excellent".to_string());
+            }
+        }
+        if self.thresholds.bus_factor_scores.is_empty() || !self.thresholds.bus_factor_scores.iter().all(|s| percent(*s)) {
+            return invalid("bus_factor_scores needs at least one score between 0 and 100".to_string());
+        }
+        if !self.grades.is_valid() {
+            return invalid("grade boundaries must be between 0 and 100 and decrease from excellent to needs_improvement".to_string());
+        }
+        for suppression in &self.suppressions {
+            suppression.validate()?;
+        }
+        for provider in &self.ci_providers {
+            provider.provider()?;
+        }
+        Ok(())
+    }
+}
+
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(value)) => merge(base, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layers_override_key_by_key() {
+        let repo = r#"
+            [weights]
+            tests = 0.4
+            community = 0.0
+
+            [thresholds]
+            coverage = 70
+            bus_factor_scores = [0, 20, 60, 100]
+
+            [paths]
+            docs = ["handbook/**/*.md"]
+        "#;
+        let local = r#"
+            [weights]
+            tests = 0.3
+
+            [analyzers]
+            disable = ["community"]
+
+            [grades]
+            excellent = 95
+        "#;
+
+        let config = Config::from_layers(&[repo, local]).unwrap();
+        assert_eq!(config.weights["tests"], 0.3);
+        assert_eq!(config.weights["community"], 0.0);
+        assert_eq!(config.analyzers.disable, ["community"]);
+        assert_eq!(config.thresholds.coverage, 70.0);
+        assert_eq!(config.thresholds.branch_coverage, 60.0);
+        assert_eq!(config.thresholds.bus_factor_score(2), 60.0);
+        assert_eq!(config.thresholds.bus_factor_score(7), 100.0);
+        assert_eq!(config.grades.excellent, 95.0);
+        assert_eq!(config.grades.good, 80.0);
+        assert!(config.paths.is_doc("handbook/guide/setup.md"));
+    }
+
+    #[test]
+    fn test_suppressions() {
+        let config = Config::parse(r#"
+            [[suppress]]
+            rule = "COM002"
+            reason = "internal tool"
+
+            [[suppress]]
+            rule = "WFS002"
+            path = ".github/workflows/release.yml"
+            reason = "vendored action, reviewed"
+            expires = 2026-12-31
+
+            [[suppress]]
+            rule = "DOC005"
+            reason = "intranet links"
+            expires = "2027-03-01"
+        "#).unwrap();
+
+        let expiries: Vec<_> = config.suppressions.iter().map(|s| s.expires.map(|d| d.to_string())).collect();
+        assert_eq!(expiries, [None, Some("2026-12-31".to_string()), Some("2027-03-01".to_string())]);
+        assert_eq!(config.suppressions[1].path.as_deref(), Some(".github/workflows/release.yml"));
+    }
+
+    #[test]
+    fn test_ci_providers() {
+        let config = Config::parse(r#"
+            [[ci_provider]]
+            name = "Forgejo Actions"
+            parser = "github-actions"
+            paths = [".forgejo/workflows/*.yml"]
+
+            [[ci_provider]]
+            name = "Concourse"
+            paths = ["ci/pipeline.yml"]
+        "#).unwrap();
+
+        let providers: Vec<CiProvider> = config.ci_providers.iter().map(|p| p.provider().unwrap()).collect();
+        assert_eq!(providers[0].id, "forgejo-actions");
+        assert!(providers[0].parser.is_some() && providers[0].matches(".forgejo/workflows/ci.yml"));
+        assert!(providers[1].parser.is_none());
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        for content in [
+            "[weights]\ntests = -1",
+            "[thresholds]\ncoverage = 120",
+            "[thresholds]\ntest_ratio = { good = 0.5, excellent = 0.2 }",
+            "[grades]\ngood = 95",
+            "[unknown]\nkey = 1",
+            "[[suppress]]\nrule = \"DOC012\"",
+            "[[suppress]]\nrule = \"DOC012\"\nreason = \" \"",
+            "[[suppress]]\nrule = \"DOC999\"\nreason = \"internal tool\"",
+            "[[suppress]]\nrule = \"DOC012\"\nreason = \"internal tool\"\nexpires = \"soon\"",
+            "[[ci_provider]]\nname = \"Concourse\"\npaths = []",
+            "[[ci_provider]]\nname = \"Forgejo\"\nparser = \"forgejo\"\npaths = [\".forgejo/workflows/*.yml\"]",
+            "weights = [",
+        ] {
+            assert!(matches!(Config::parse(content), Err(RepoHealthError::InvalidConfig(_))), "{}", content);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::Duration;
+use serde::Deserialize;
+use url::Url;
+
+/// Public shields.io instance; self-hosted instances expose the same routes.
+pub const DEFAULT_BADGE_ENDPOINT: &str = "https://img.shields.io";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeService {
+    Codecov,
+    Coveralls,
+    Shields,
+}
+
+impl BadgeService {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BadgeService::Codecov => "Codecov",
+            BadgeService::Coveralls => "Coveralls",
+            BadgeService::Shields => "shields.io",
+        }
+    }
+}
+
+/// A coverage badge image referenced from the README.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageBadge {
+    pub service: BadgeService,
+    pub image_url: String,
+    /// Equivalent shields.io route (path and query) used to resolve the percentage
+    pub route: String,
+    /// Reported coverage, when known from the URL or resolved through the endpoint
+    pub percent: Option<f64>,
+}
+
+impl CoverageBadge {
+    /// Recognise Codecov, Coveralls and shields.io coverage badge URLs.
+    pub fn from_url(image_url: &str) -> Option<Self> {
+        let url = Url::parse(image_url).ok()?;
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        let query = |key: &str| url.query_pairs().find(|(k, _)| k == key).map(|(_, v)| v.into_owned());
+
+        let (service, route, percent) = match url.host_str()? {
+            // codecov.io/gh/OWNER/REPO[/branch/BRANCH]/graph/badge.svg
+            "codecov.io" | "app.codecov.io" => {
+                let [host, owner, repo, rest @ ..] = segments.as_slice() else { return None };
+                if !matches!(*host, "gh" | "github") {
+                    return None;
+                }
+                let branch = match rest {
+                    ["branch", branch, ..] => Some(branch.to_string()),
+                    _ => query("branch"),
+                };
+                let mut route = format!("/codecov/c/github/{}/{}", owner, repo);
+                if let Some(branch) = branch {
+                    route.push_str(&format!("/{}", branch));
+                }
+                if let Some(token) = query("token") {
+                    route.push_str(&format!("?token={}", token));
+                }
+                (BadgeService::Codecov, route, None)
+            }
+            // coveralls.io/repos/github/OWNER/REPO/badge.svg?branch=BRANCH
+            "coveralls.io" => {
+                let ["repos", "github", owner, repo, ..] = segments.as_slice() else { return None };
+                let mut route = format!("/coveralls/github/{}/{}", owner, repo);
+                if let Some(branch) = query("branch") {
+                    route.push_str(&format!("/{}", branch));
+                }
+                (BadgeService::Coveralls, route, None)
+            }
+            "img.shields.io" => {
+                let path = url.path();
+                if !(path.contains("coverage") || path.starts_with("/codecov/") || path.starts_with("/coveralls/")
+                    || url.query().is_some_and(|q| q.contains("coverage")))
+                {
+                    return None;
+                }
+                // Static badges carry the number: /badge/coverage-87%25-green
+                let percent = match segments.as_slice() {
+                    ["badge", content] => content.split('-').find_map(parse_percent),
+                    _ => None,
+                };
+                let route = match url.query() {
+                    Some(q) => format!("{}?{}", path, q),
+                    None => path.to_string(),
+                };
+                (BadgeService::Shields, route, percent)
+            }
+            _ => return None,
+        };
+
+        Some(Self { service, image_url: image_url.to_string(), route, percent })
+    }
+
+    /// shields.io serves any badge as JSON when the route ends in `.json`.
+    fn json_route(&self) -> String {
+        let (path, query) = match self.route.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (self.route.as_str(), None),
+        };
+        let path = path.strip_suffix(".svg").unwrap_or(path);
+
+        match query {
+            Some(query) => format!("{}.json?{}", path, query),
+            None => format!("{}.json", path),
+        }
+    }
+}
+
+/// Coverage badges among the images in a Markdown/HTML README.
+pub fn find_badges(readme: &str) -> Vec<CoverageBadge> {
+    let mut badges: Vec<CoverageBadge> = Vec::new();
+
+    for url in image_urls(readme) {
+        if let Some(badge) = CoverageBadge::from_url(&url) {
+            if !badges.iter().any(|b| b.route == badge.route) {
+                badges.push(badge);
+            }
+        }
+    }
+
+    badges
+}
+
+/// `![alt](url)` and `<img src="url">` targets.
+fn image_urls(text: &str) -> Vec<String> {
+    let mut urls = Vec::new();
+
+    for (start, _) in text.match_indices("![") {
+        let rest = &text[start..];
+        let Some(open) = rest.find("](") else { continue };
+        let target = &rest[open + 2..];
+        if let Some(end) = target.find(')') {
+            let url = target[..end].split_whitespace().next().unwrap_or("");
+            urls.push(url.trim_matches(['<', '>']).to_string());
+        }
+    }
+
+    for (start, _) in text.match_indices("src=") {
+        let rest = &text[start + 4..];
+        let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') else { continue };
+        if let Some(end) = rest[1..].find(quote) {
+            urls.push(rest[1..end + 1].to_string());
+        }
+    }
+
+    urls
+}
+
+fn parse_percent(text: &str) -> Option<f64> {
+    let text = text.replace("%25", "%");
+    let number = text.trim().strip_suffix('%')?;
+    number.trim().parse::<f64>().ok().filter(|p| (0.0..=100.0).contains(p))
+}
+
+#[derive(Deserialize)]
+struct ShieldsResponse {
+    message: String,
+}
+
+/// Resolves badge percentages through a shields.io-compatible endpoint.
+pub struct BadgeResolver {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl BadgeResolver {
+    pub fn new(endpoint: &str) -> crate::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent("repo-health")
+            .build()?;
+
+        Ok(Self { client, endpoint: endpoint.trim_end_matches('/').to_string() })
+    }
+
+    /// Fill in unknown percentages; badges that cannot be resolved are left as they are.
+    pub async fn resolve(&self, badges: &mut [CoverageBadge]) {
+        for badge in badges.iter_mut().filter(|b| b.percent.is_none()) {
+            badge.percent = self.fetch_percent(badge).await;
+        }
+    }
+
+    async fn fetch_percent(&self, badge: &CoverageBadge) -> Option<f64> {
+        let url = format!("{}{}", self.endpoint, badge.json_route());
+        let response = self.client.get(&url).send().await.ok()?.error_for_status().ok()?;
+        let body: ShieldsResponse = response.json().await.ok()?;
+        parse_percent(&body.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_badges() {
+        let readme = r#"# app
+[![codecov](https://codecov.io/gh/acme/app/branch/main/graph/badge.svg?token=abc)](https://codecov.io/gh/acme/app)
+[![Coverage Status](https://coveralls.io/repos/github/acme/app/badge.svg?branch=main)](https://coveralls.io/github/acme/app?branch=main)
+<img src="https://img.shields.io/badge/coverage-87.5%25-green" alt="coverage">
+![build](https://img.shields.io/github/actions/workflow/status/acme/app/ci.yml)
+"#;
+
+        let badges = find_badges(readme);
+        assert_eq!(badges.len(), 3);
+        assert_eq!(badges[0].service, BadgeService::Codecov);
+        assert_eq!(badges[0].route, "/codecov/c/github/acme/app/main?token=abc");
+        assert_eq!(badges[0].json_route(), "/codecov/c/github/acme/app/main.json?token=abc");
+        assert_eq!(badges[1].route, "/coveralls/github/acme/app/main");
+        assert_eq!(badges[2].percent, Some(87.5));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_through_endpoint() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/coveralls/github/acme/app.json")
+            .with_body(r#"{"label":"coverage","message":"91%","color":"brightgreen"}"#)
+            .create_async()
+            .await;
+
+        let mut badges = find_badges("![c](https://coveralls.io/repos/github/acme/app/badge.svg)\n![d](https://codecov.io/gh/acme/gone/graph/badge.svg)");
+        BadgeResolver::new(&server.url()).unwrap().resolve(&mut badges).await;
+
+        mock.assert_async().await;
+        assert_eq!(badges[0].percent, Some(91.0));
+        // Unknown routes fall back to presence only
+        assert_eq!(badges[1].percent, None);
+    }
+}
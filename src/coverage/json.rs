@@ -0,0 +1,113 @@
+use serde::Deserialize;
+use super::{CoverageFormat, FileCoverage};
+
+/// `cargo llvm-cov --json` export (llvm-cov export format).
+#[derive(Deserialize)]
+struct LlvmExport {
+    data: Vec<LlvmData>,
+}
+
+#[derive(Deserialize)]
+struct LlvmData {
+    files: Vec<LlvmFile>,
+}
+
+#[derive(Deserialize)]
+struct LlvmFile {
+    filename: String,
+    summary: LlvmSummary,
+}
+
+#[derive(Deserialize)]
+struct LlvmSummary {
+    lines: LlvmCount,
+    #[serde(default)]
+    branches: Option<LlvmCount>,
+}
+
+#[derive(Deserialize)]
+struct LlvmCount {
+    count: u64,
+    covered: u64,
+}
+
+/// `cargo tarpaulin --out Json` report.
+#[derive(Deserialize)]
+struct TarpaulinReport {
+    files: Vec<TarpaulinFile>,
+}
+
+#[derive(Deserialize)]
+struct TarpaulinFile {
+    /// Path components, starting with the root directory
+    path: Vec<String>,
+    covered: u64,
+    coverable: u64,
+}
+
+pub(super) fn parse(content: &str) -> Option<(CoverageFormat, Vec<FileCoverage>)> {
+    if let Ok(export) = serde_json::from_str::<LlvmExport>(content) {
+        let files = export.data.into_iter()
+            .flat_map(|data| data.files)
+            .map(|file| {
+                let branches = file.summary.branches.unwrap_or(LlvmCount { count: 0, covered: 0 });
+                FileCoverage {
+                    path: file.filename,
+                    lines_total: file.summary.lines.count,
+                    lines_covered: file.summary.lines.covered,
+                    branches_total: branches.count,
+                    branches_covered: branches.covered,
+                }
+            })
+            .collect();
+        return Some((CoverageFormat::LlvmCov, files));
+    }
+
+    let report = serde_json::from_str::<TarpaulinReport>(content).ok()?;
+    let files = report.files.into_iter()
+        .map(|file| FileCoverage {
+            path: file.path.join("/").replace("//", "/"),
+            lines_total: file.coverable,
+            lines_covered: file.covered,
+            ..Default::default()
+        })
+        .collect();
+    Some((CoverageFormat::Tarpaulin, files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_llvm_cov() {
+        let json = r#"{"type":"llvm.coverage.json.export","version":"2.0.1","data":[{"files":[
+            {"filename":"/work/app/src/lib.rs","summary":{
+                "lines":{"count":40,"covered":30,"percent":75.0},
+                "branches":{"count":4,"covered":2,"percent":50.0},
+                "functions":{"count":3,"covered":3,"percent":100.0}}}],
+            "totals":{}}]}"#;
+
+        let (format, files) = parse(json).unwrap();
+        assert_eq!(format, CoverageFormat::LlvmCov);
+        assert_eq!(files[0].path, "/work/app/src/lib.rs");
+        assert_eq!((files[0].lines_covered, files[0].lines_total), (30, 40));
+        assert_eq!((files[0].branches_covered, files[0].branches_total), (2, 4));
+    }
+
+    #[test]
+    fn test_parse_tarpaulin() {
+        let json = r#"{"files":[{"path":["/","work","app","src","main.rs"],"content":"","traces":[],
+            "covered":3,"coverable":5}],"coverage":60.0,"covered":3,"coverable":5}"#;
+
+        let (format, files) = parse(json).unwrap();
+        assert_eq!(format, CoverageFormat::Tarpaulin);
+        assert_eq!(files[0].path, "/work/app/src/main.rs");
+        assert_eq!((files[0].lines_covered, files[0].lines_total), (3, 5));
+    }
+
+    #[test]
+    fn test_rejects_other_json() {
+        assert!(parse(r#"{"name":"app","version":"1.0.0"}"#).is_none());
+    }
+}
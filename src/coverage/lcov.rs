@@ -0,0 +1,62 @@
+use super::FileCoverage;
+
+/// Parse an lcov tracefile (`SF:`/`LF:`/`LH:`/`BRF:`/`BRH:` records).
+pub(super) fn parse(content: &str) -> Option<Vec<FileCoverage>> {
+    let mut files = Vec::new();
+    let mut current: Option<FileCoverage> = None;
+    // Line hits from `DA:` records, for tracefiles without `LF`/`LH` summaries
+    let mut da = (0u64, 0u64);
+
+    for line in content.lines().map(str::trim) {
+        let (key, value) = line.split_once(':').unwrap_or((line, ""));
+        match key {
+            "SF" => {
+                current = Some(FileCoverage { path: value.to_string(), ..Default::default() });
+                da = (0, 0);
+            }
+            "DA" => {
+                da.1 += 1;
+                if value.split(',').nth(1).and_then(|hits| hits.parse::<u64>().ok()).is_some_and(|h| h > 0) {
+                    da.0 += 1;
+                }
+            }
+            "LF" | "LH" | "BRF" | "BRH" => {
+                let (Some(file), Ok(n)) = (current.as_mut(), value.parse::<u64>()) else { continue };
+                match key {
+                    "LF" => file.lines_total = n,
+                    "LH" => file.lines_covered = n,
+                    "BRF" => file.branches_total = n,
+                    _ => file.branches_covered = n,
+                }
+            }
+            "end_of_record" => {
+                if let Some(mut file) = current.take() {
+                    if file.lines_total == 0 {
+                        file.lines_covered = da.0;
+                        file.lines_total = da.1;
+                    }
+                    files.push(file);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if files.is_empty() { None } else { Some(files) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lcov() {
+        let files = parse("TN:\nSF:src/lib.rs\nDA:1,1\nDA:2,0\nLF:2\nLH:1\nBRF:4\nBRH:3\nend_of_record\nSF:src/main.rs\nDA:1,5\nDA:2,0\nDA:3,0\nend_of_record\n").unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!((files[0].lines_covered, files[0].lines_total), (1, 2));
+        assert_eq!((files[0].branches_covered, files[0].branches_total), (3, 4));
+        assert_eq!((files[1].lines_covered, files[1].lines_total), (1, 3));
+        assert!(parse("not a tracefile").is_none());
+    }
+}
@@ -0,0 +1,185 @@
+mod badge;
+mod json;
+mod lcov;
+mod xml;
+
+use std::collections::{BTreeMap, HashMap};
+use crate::github::TreeEntry;
+
+pub use badge::{find_badges, BadgeResolver, BadgeService, CoverageBadge, DEFAULT_BADGE_ENDPOINT};
+
+/// File names coverage tools write by default.
+const REPORT_FILE_NAMES: &[&str] = &[
+    "lcov.info", "coverage.xml", "cobertura.xml", "cobertura-coverage.xml",
+    "jacoco.xml", "jacocoTestReport.xml", "tarpaulin-report.json", "llvm-cov.json", "coverage.json",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageFormat {
+    Lcov,
+    Cobertura,
+    JaCoCo,
+    LlvmCov,
+    Tarpaulin,
+}
+
+impl CoverageFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CoverageFormat::Lcov => "lcov",
+            CoverageFormat::Cobertura => "Cobertura",
+            CoverageFormat::JaCoCo => "JaCoCo",
+            CoverageFormat::LlvmCov => "cargo llvm-cov",
+            CoverageFormat::Tarpaulin => "tarpaulin",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileCoverage {
+    pub path: String,
+    pub lines_total: u64,
+    pub lines_covered: u64,
+    pub branches_total: u64,
+    pub branches_covered: u64,
+}
+
+/// A parsed coverage artifact.
+#[derive(Debug, Clone)]
+pub struct CoverageReport {
+    pub format: CoverageFormat,
+    /// Where the report came from: a local path or a repository path.
+    pub source: String,
+    pub files: Vec<FileCoverage>,
+}
+
+impl CoverageReport {
+    /// Parse a report, detecting the format from its contents.
+    pub fn parse(source: &str, content: &str) -> Option<Self> {
+        let trimmed = content.trim_start();
+
+        let (format, files) = if trimmed.starts_with('{') {
+            json::parse(trimmed)?
+        } else if trimmed.starts_with('<') {
+            xml::parse(trimmed)?
+        } else {
+            (CoverageFormat::Lcov, lcov::parse(content)?)
+        };
+
+        Some(Self { format, source: source.to_string(), files })
+    }
+
+    pub fn lines(&self) -> (u64, u64) {
+        self.files.iter().fold((0, 0), |(c, t), f| (c + f.lines_covered, t + f.lines_total))
+    }
+
+    pub fn branches(&self) -> (u64, u64) {
+        self.files.iter().fold((0, 0), |(c, t), f| (c + f.branches_covered, t + f.branches_total))
+    }
+
+    pub fn line_percent(&self) -> Option<f64> {
+        percent(self.lines())
+    }
+
+    pub fn branch_percent(&self) -> Option<f64> {
+        percent(self.branches())
+    }
+
+    /// Map report paths (often absolute CI paths) onto repository tree paths.
+    pub fn relativize(&mut self, tree: &[TreeEntry]) {
+        for file in &mut self.files {
+            let normalized = file.path.replace('\\', "/");
+            let matched = tree.iter()
+                .filter(|e| e.entry_type == "blob")
+                .filter(|e| normalized == e.path || normalized.ends_with(&format!("/{}", e.path)))
+                .max_by_key(|e| e.path.len());
+
+            file.path = match matched {
+                Some(entry) => entry.path.clone(),
+                None => normalized.trim_start_matches("./").trim_start_matches('/').to_string(),
+            };
+        }
+    }
+
+    /// Covered and total lines per directory.
+    pub fn by_directory(&self) -> BTreeMap<String, (u64, u64)> {
+        let mut dirs: BTreeMap<String, (u64, u64)> = BTreeMap::new();
+
+        for file in &self.files {
+            let dir = file.path.rsplit_once('/').map(|(d, _)| d).unwrap_or(".");
+            let entry = dirs.entry(dir.to_string()).or_default();
+            entry.0 += file.lines_covered;
+            entry.1 += file.lines_total;
+        }
+
+        dirs
+    }
+
+    /// Directories with at least `min_lines` lines, lowest coverage first.
+    pub fn lowest_directories(&self, min_lines: u64, limit: usize) -> Vec<(String, u64, u64, f64)> {
+        let mut dirs: Vec<(String, u64, u64, f64)> = self.by_directory()
+            .into_iter()
+            .filter(|(_, (_, total))| *total >= min_lines)
+            .filter_map(|(dir, lines)| Some((dir, lines.0, lines.1, percent(lines)?)))
+            .collect();
+
+        dirs.sort_by(|a, b| a.3.total_cmp(&b.3));
+        dirs.truncate(limit);
+        dirs
+    }
+}
+
+fn percent((covered, total): (u64, u64)) -> Option<f64> {
+    if total == 0 { None } else { Some(covered as f64 / total as f64 * 100.0) }
+}
+
+/// Whether a tree path looks like a committed coverage report.
+pub fn is_report_path(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    REPORT_FILE_NAMES.contains(&file_name) || file_name.ends_with(".lcov")
+}
+
+/// The first parseable coverage report among fetched repository files.
+pub fn find_in_repo(tree: &[TreeEntry], files: &HashMap<String, String>) -> Option<CoverageReport> {
+    tree.iter()
+        .filter(|e| e.entry_type == "blob" && is_report_path(&e.path))
+        .find_map(|e| {
+            let mut report = CoverageReport::parse(&e.path, files.get(&e.path)?)?;
+            report.relativize(tree);
+            Some(report)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relativize_and_directories() {
+        let mut report = CoverageReport {
+            format: CoverageFormat::Lcov,
+            source: "lcov.info".to_string(),
+            files: vec![
+                FileCoverage { path: "/home/runner/work/app/app/src/lib.rs".to_string(), lines_total: 100, lines_covered: 90, ..Default::default() },
+                FileCoverage { path: "/home/runner/work/app/app/src/io/fs.rs".to_string(), lines_total: 50, lines_covered: 10, ..Default::default() },
+            ],
+        };
+
+        report.relativize(&[TreeEntry::blob("src/lib.rs"), TreeEntry::blob("src/io/fs.rs")]);
+        assert_eq!(report.files[0].path, "src/lib.rs");
+        assert_eq!(report.lines(), (100, 150));
+        assert_eq!(report.branch_percent(), None);
+
+        let lowest = report.lowest_directories(10, 1);
+        assert_eq!(lowest[0].0, "src/io");
+        assert_eq!(lowest[0].3, 20.0);
+    }
+
+    #[test]
+    fn test_is_report_path() {
+        assert!(is_report_path("coverage/lcov.info"));
+        assert!(is_report_path("target/coverage.lcov"));
+        assert!(is_report_path("build/reports/jacoco/test/jacocoTestReport.xml"));
+        assert!(!is_report_path("src/coverage.rs"));
+    }
+}
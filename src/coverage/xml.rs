@@ -0,0 +1,174 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use super::{CoverageFormat, FileCoverage};
+
+/// Parse a Cobertura (`<coverage>`) or JaCoCo (`<report>`) XML report.
+pub(super) fn parse(content: &str) -> Option<(CoverageFormat, Vec<FileCoverage>)> {
+    let mut reader = Reader::from_str(content);
+    let mut stack: Vec<String> = Vec::new();
+    let mut format = None;
+    let mut files: Vec<FileCoverage> = Vec::new();
+    let mut package = String::new();
+
+    loop {
+        let (element, is_empty) = match reader.read_event().ok()? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(_) => {
+                stack.pop();
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+
+        if format.is_none() {
+            format = match name.as_str() {
+                "coverage" => Some(CoverageFormat::Cobertura),
+                "report" => Some(CoverageFormat::JaCoCo),
+                _ => return None,
+            };
+        }
+
+        match (format, name.as_str()) {
+            (Some(CoverageFormat::Cobertura), "class") => {
+                let path = attribute(&element, "filename")?;
+                // Inner classes repeat the file name of their outer class
+                if files.last().is_none_or(|f| f.path != path) {
+                    files.push(FileCoverage { path, ..Default::default() });
+                }
+            }
+            // `<method>` elements repeat the lines of their class
+            (Some(CoverageFormat::Cobertura), "line") if !stack.iter().any(|s| s == "methods") => {
+                if let Some(file) = files.last_mut() {
+                    add_cobertura_line(file, &element);
+                }
+            }
+            (Some(CoverageFormat::JaCoCo), "package") => {
+                package = attribute(&element, "name").unwrap_or_default();
+            }
+            (Some(CoverageFormat::JaCoCo), "sourcefile") => {
+                let file_name = attribute(&element, "name")?;
+                let path = if package.is_empty() { file_name } else { format!("{}/{}", package, file_name) };
+                files.push(FileCoverage { path, ..Default::default() });
+            }
+            (Some(CoverageFormat::JaCoCo), "counter") if stack.last().is_some_and(|s| s == "sourcefile") => {
+                if let Some(file) = files.last_mut() {
+                    add_jacoco_counter(file, &element);
+                }
+            }
+            _ => {}
+        }
+
+        if !is_empty {
+            stack.push(name);
+        }
+    }
+
+    Some((format?, files)).filter(|(_, files)| !files.is_empty())
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Option<String> {
+    element.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name.as_bytes())
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+fn add_cobertura_line(file: &mut FileCoverage, element: &BytesStart) {
+    file.lines_total += 1;
+    if attribute(element, "hits").and_then(|h| h.parse::<u64>().ok()).is_some_and(|h| h > 0) {
+        file.lines_covered += 1;
+    }
+
+    // condition-coverage="50% (1/2)"
+    let conditions = attribute(element, "condition-coverage")
+        .and_then(|c| {
+            let (_, fraction) = c.split_once('(')?;
+            let (covered, total) = fraction.trim_end_matches(')').split_once('/')?;
+            Some((covered.trim().parse::<u64>().ok()?, total.trim().parse::<u64>().ok()?))
+        });
+    if let Some((covered, total)) = conditions {
+        file.branches_covered += covered;
+        file.branches_total += total;
+    }
+}
+
+fn add_jacoco_counter(file: &mut FileCoverage, element: &BytesStart) {
+    let count = |name| attribute(element, name).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+    let (missed, covered) = (count("missed"), count("covered"));
+
+    match attribute(element, "type").as_deref() {
+        Some("LINE") => {
+            file.lines_covered += covered;
+            file.lines_total += missed + covered;
+        }
+        Some("BRANCH") => {
+            file.branches_covered += covered;
+            file.branches_total += missed + covered;
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cobertura() {
+        let xml = r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5" branch-rate="0.5" version="7.4">
+  <sources><source>/home/runner/work/app/app</source></sources>
+  <packages><package name="app"><classes>
+    <class name="cli.py" filename="app/cli.py" line-rate="0.5">
+      <methods><method name="main"><lines><line number="1" hits="1"/></lines></method></methods>
+      <lines>
+        <line number="1" hits="1"/>
+        <line number="2" hits="0" branch="true" condition-coverage="50% (1/2)"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+
+        let (format, files) = parse(xml).unwrap();
+        assert_eq!(format, CoverageFormat::Cobertura);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "app/cli.py");
+        assert_eq!((files[0].lines_covered, files[0].lines_total), (1, 2));
+        assert_eq!((files[0].branches_covered, files[0].branches_total), (1, 2));
+    }
+
+    #[test]
+    fn test_parse_jacoco() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<!DOCTYPE report PUBLIC "-//JACOCO//DTD Report 1.1//EN" "report.dtd">
+<report name="app">
+  <package name="com/acme">
+    <class name="com/acme/App" sourcefilename="App.java">
+      <counter type="LINE" missed="100" covered="100"/>
+    </class>
+    <sourcefile name="App.java">
+      <line nr="3" mi="0" ci="3" mb="0" cb="0"/>
+      <counter type="INSTRUCTION" missed="4" covered="12"/>
+      <counter type="BRANCH" missed="1" covered="3"/>
+      <counter type="LINE" missed="2" covered="8"/>
+    </sourcefile>
+    <counter type="LINE" missed="2" covered="8"/>
+  </package>
+</report>"#;
+
+        let (format, files) = parse(xml).unwrap();
+        assert_eq!(format, CoverageFormat::JaCoCo);
+        assert_eq!(files[0].path, "com/acme/App.java");
+        assert_eq!((files[0].lines_covered, files[0].lines_total), (8, 10));
+        assert_eq!((files[0].branches_covered, files[0].branches_total), (3, 4));
+    }
+
+    #[test]
+    fn test_rejects_other_xml() {
+        assert!(parse("<project><modelVersion>4.0.0</modelVersion></project>").is_none());
+    }
+}
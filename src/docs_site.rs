@@ -0,0 +1,292 @@
+use std::collections::{BTreeSet, HashMap};
+use crate::ci::CiConfig;
+use crate::config::ExtraPaths;
+use crate::github::TreeEntry;
+use crate::rust_crates;
+
+const DOC_EXTENSIONS: &[&str] = &[".md", ".mdx", ".markdown", ".rst", ".adoc"];
+
+/// Conventional documentation directories, whether or not a site generator is used
+const DOC_DIRS: &[&str] = &["docs", "doc", "documentation"];
+
+/// Directories that never contain the project's own pages
+const IGNORED_DIRS: &[&str] = &["node_modules/", "vendor/", "_site/", "target/", "public/"];
+
+/// Project files written in Markdown that are not documentation pages
+const NON_DOC_FILES: &[&str] = &[
+    "changelog", "changes", "history", "license", "code_of_conduct", "contributing", "security",
+    "pull_request_template", "issue_template",
+];
+
+/// Jekyll directories, one of which must sit next to `_config.yml`
+const JEKYLL_DIRS: &[&str] = &["_posts", "_layouts", "_includes"];
+
+/// CI steps that publish a built site.
+const DEPLOY_MARKERS: &[&str] = &[
+    "deploy-pages", "gh-pages", "github-pages-deploy", "gh-deploy", "upload-pages-artifact",
+    "netlify", "vercel", "cloudflare/pages-action", "wrangler pages",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DocsFramework {
+    MdBook,
+    Docusaurus,
+    MkDocs,
+    Sphinx,
+    Hugo,
+    Jekyll,
+    VitePress,
+    Rustdoc,
+}
+
+impl DocsFramework {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DocsFramework::MdBook => "mdBook",
+            DocsFramework::Docusaurus => "Docusaurus",
+            DocsFramework::MkDocs => "MkDocs",
+            DocsFramework::Sphinx => "Sphinx",
+            DocsFramework::Hugo => "Hugo",
+            DocsFramework::Jekyll => "Jekyll",
+            DocsFramework::VitePress => "VitePress",
+            DocsFramework::Rustdoc => "rustdoc (docs.rs)",
+        }
+    }
+
+    /// Words identifying a build of this site in a CI command or action reference.
+    fn build_markers(&self) -> &'static [&'static str] {
+        match self {
+            DocsFramework::MdBook => &["mdbook"],
+            DocsFramework::Docusaurus => &["docusaurus"],
+            DocsFramework::MkDocs => &["mkdocs"],
+            DocsFramework::Sphinx => &["sphinx", "make html"],
+            DocsFramework::Hugo => &["hugo"],
+            DocsFramework::Jekyll => &["jekyll"],
+            DocsFramework::VitePress => &["vitepress", "docs:build"],
+            DocsFramework::Rustdoc => &["cargo doc", "rustdoc"],
+        }
+    }
+}
+
+/// A documentation site configured in the repository.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocsSite {
+    pub framework: DocsFramework,
+    /// Configuration file that identified the site
+    pub config: String,
+    /// Directory holding the site's pages; `None` for generated API docs
+    pub source: Option<String>,
+}
+
+/// Detect site generators from their configuration files.
+pub fn detect(tree: &[TreeEntry], files: &HashMap<String, String>) -> Vec<DocsSite> {
+    let dirs: BTreeSet<&str> = tree.iter()
+        .filter(|e| e.entry_type == "tree")
+        .map(|e| e.path.as_str())
+        .collect();
+    let has_dir = |dir: &str, name: &str| dirs.contains(join(dir, name).as_str());
+    let mut sites = Vec::new();
+
+    for entry in tree.iter().filter(|e| e.entry_type == "blob") {
+        let path = entry.path.as_str();
+        if IGNORED_DIRS.iter().any(|d| path.starts_with(d) || path.contains(&format!("/{}", d))) {
+            continue;
+        }
+        let (dir, file_name) = path.rsplit_once('/').unwrap_or(("", path));
+
+        let site = match file_name {
+            "book.toml" => Some((DocsFramework::MdBook, Some(join(dir, "src")))),
+            "mkdocs.yml" | "mkdocs.yaml" => Some((DocsFramework::MkDocs, Some(join(dir, "docs")))),
+            "hugo.toml" | "hugo.yaml" | "hugo.json" => Some((DocsFramework::Hugo, Some(join(dir, "content")))),
+            "config.toml" if has_dir(dir, "content") && (has_dir(dir, "layouts") || has_dir(dir, "themes")) => {
+                Some((DocsFramework::Hugo, Some(join(dir, "content"))))
+            }
+            "_config.yml" if JEKYLL_DIRS.iter().any(|d| has_dir(dir, d)) => {
+                Some((DocsFramework::Jekyll, Some(dir.to_string())))
+            }
+            "conf.py" if is_sphinx_source(tree, dir, &has_dir) => Some((DocsFramework::Sphinx, Some(dir.to_string()))),
+            name if name.starts_with("docusaurus.config.") => Some((DocsFramework::Docusaurus, Some(join(dir, "docs")))),
+            name if name.starts_with("config.") && (dir == ".vitepress" || dir.ends_with("/.vitepress")) => {
+                let root = dir.strip_suffix(".vitepress").unwrap_or("").trim_end_matches('/');
+                Some((DocsFramework::VitePress, Some(root.to_string())))
+            }
+            _ if rust_crates::is_manifest(path) && files.get(path).is_some_and(|m| publishes_to_docs_rs(m)) => {
+                Some((DocsFramework::Rustdoc, None))
+            }
+            _ => None,
+        };
+
+        if let Some((framework, source)) = site {
+            sites.push(DocsSite { framework, config: path.to_string(), source });
+        }
+    }
+
+    sites
+}
+
+/// A `conf.py` next to a reStructuredText index, or a Markdown index with the
+/// `_static`/`_templates` directories or `make.bat` that `sphinx-quickstart` creates.
+fn is_sphinx_source(tree: &[TreeEntry], dir: &str, has_dir: &impl Fn(&str, &str) -> bool) -> bool {
+    let has_file = |name: &str| tree.iter().any(|e| e.path == join(dir, name));
+    has_file("index.rst")
+        || (has_file("index.md") && (has_file("make.bat") || has_dir(dir, "_static") || has_dir(dir, "_templates")))
+}
+
+/// Crates that configure docs.rs builds or link their documentation there.
+fn publishes_to_docs_rs(manifest: &str) -> bool {
+    manifest.contains("[package.metadata.docs.rs]")
+        || manifest.lines().any(|line| {
+            line.trim_start().starts_with("documentation") && line.contains("docs.rs")
+        })
+}
+
+fn join(dir: &str, name: &str) -> String {
+    if dir.is_empty() { name.to_string() } else { format!("{}/{}", dir, name) }
+}
+
+/// Non-empty documentation pages under the site sources, conventional doc directories
+/// and configured documentation paths.
+pub fn count_pages(tree: &[TreeEntry], sites: &[DocsSite], extra: &ExtraPaths) -> usize {
+    let roots: Vec<String> = sites.iter()
+        .filter_map(|site| site.source.clone())
+        .chain(DOC_DIRS.iter().map(|d| d.to_string()))
+        .collect();
+
+    tree.iter()
+        .filter(|e| e.entry_type == "blob" && e.size != Some(0))
+        .filter(|e| DOC_EXTENSIONS.iter().any(|ext| e.path.to_lowercase().ends_with(ext)))
+        .filter(|e| !IGNORED_DIRS.iter().any(|d| e.path.starts_with(d) || e.path.contains(&format!("/{}", d))))
+        .filter(|e| !crate::languages::is_vendored(&e.path) && !is_hidden(&e.path) && !is_project_file(&e.path))
+        .filter(|e| {
+            extra.is_doc(&e.path) || roots.iter().any(|root| {
+                // A site at the repository root: skip the usual top-level project files and
+                // the READMEs of nested packages
+                if root.is_empty() {
+                    e.path.contains('/') && !stem(&e.path).eq_ignore_ascii_case("readme")
+                } else {
+                    e.path.starts_with(&format!("{}/", root))
+                }
+            })
+        })
+        .count()
+}
+
+/// Paths under a dot-directory such as `.github/`.
+fn is_hidden(path: &str) -> bool {
+    path.split('/').rev().skip(1).any(|dir| dir.starts_with('.'))
+}
+
+/// Changelogs, licenses, community files and templates, wherever they are.
+fn is_project_file(path: &str) -> bool {
+    NON_DOC_FILES.contains(&stem(path).to_lowercase().as_str())
+}
+
+/// The file name without directories or extension.
+fn stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.split_once('.').map_or(name, |(stem, _)| stem)
+}
+
+/// The CI configuration that builds the given site, if any.
+pub fn built_in_ci(configs: &[CiConfig], framework: DocsFramework) -> Option<&CiConfig> {
+    configs.iter().find(|config| {
+        step_texts(config).any(|text| framework.build_markers().iter().any(|m| text.contains(m)))
+    })
+}
+
+/// The CI configuration that deploys a site, if any.
+pub fn deployed_in_ci(configs: &[CiConfig]) -> Option<&CiConfig> {
+    configs.iter().find(|config| step_texts(config).any(|text| DEPLOY_MARKERS.iter().any(|m| text.contains(m))))
+}
+
+fn step_texts(config: &CiConfig) -> impl Iterator<Item = String> + '_ {
+    config.jobs.iter()
+        .filter(|job| !job.disabled)
+        .flat_map(|job| &job.steps)
+        .filter(|step| !step.disabled)
+        .flat_map(|step| [step.command.as_deref(), step.uses.as_deref()])
+        .flatten()
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ci::{CiJob, CiStep};
+
+    #[test]
+    fn test_detect_sites() {
+        let tree = vec![
+            TreeEntry::dir("book"),
+            TreeEntry::blob("book/book.toml"),
+            TreeEntry::blob("book/src/SUMMARY.md"),
+            TreeEntry::blob("book/src/intro.md"),
+            TreeEntry::blob("site/.vitepress/config.mts"),
+            TreeEntry::blob("docs/conf.py"),
+            TreeEntry::blob("docs/index.rst"),
+            TreeEntry::blob("docs/.gitkeep").with_size(0),
+            TreeEntry::blob("node_modules/x/mkdocs.yml").with_size(10),
+            TreeEntry::blob("Cargo.toml"),
+        ];
+        let mut files = HashMap::new();
+        files.insert("Cargo.toml".to_string(), "[package]\nname = \"app\"\ndocumentation = \"https://docs.rs/app\"\n".to_string());
+
+        let sites = detect(&tree, &files);
+        let frameworks: Vec<DocsFramework> = sites.iter().map(|s| s.framework).collect();
+        assert_eq!(frameworks, vec![DocsFramework::MdBook, DocsFramework::VitePress, DocsFramework::Sphinx, DocsFramework::Rustdoc]);
+        assert_eq!(sites[0].source.as_deref(), Some("book/src"));
+        assert_eq!(sites[1].source.as_deref(), Some("site"));
+
+        assert_eq!(count_pages(&tree, &sites, &ExtraPaths::default()), 3);
+        assert_eq!(count_pages(&[TreeEntry::blob("docs/.gitkeep").with_size(0)], &[], &ExtraPaths::default()), 0);
+    }
+
+    #[test]
+    fn test_root_site_needs_framework_evidence() {
+        let mut tree = vec![
+            TreeEntry::blob("_config.yml"),
+            TreeEntry::blob("conf.py"),
+            TreeEntry::blob("index.md"),
+            TreeEntry::blob("guide/install.md"),
+            TreeEntry::blob("guide/README.md"),
+            TreeEntry::blob("packages/core/CHANGELOG.md"),
+            TreeEntry::blob(".github/ISSUE_TEMPLATE/bug.md"),
+            TreeEntry::blob("third_party/lib/guide.md"),
+        ];
+        assert!(detect(&tree, &HashMap::new()).is_empty());
+
+        tree.push(TreeEntry::dir("_layouts"));
+        let sites = detect(&tree, &HashMap::new());
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].framework, DocsFramework::Jekyll);
+        assert_eq!(sites[0].source.as_deref(), Some(""));
+
+        assert_eq!(count_pages(&tree, &sites, &ExtraPaths::default()), 1);
+    }
+
+    #[test]
+    fn test_ci_build_and_deploy() {
+        let step = |command: &str, uses: Option<&str>| CiStep {
+            command: Some(command.to_string()).filter(|c| !c.is_empty()),
+            uses: uses.map(String::from),
+            ..Default::default()
+        };
+        let config = CiConfig {
+            provider_id: "github-actions".to_string(),
+            provider: "GitHub Actions".to_string(),
+            path: ".github/workflows/docs.yml".to_string(),
+            triggers: vec!["push".to_string()],
+            runs_on_pull_request: false,
+            declares_permissions: true,
+            jobs: vec![CiJob {
+                steps: vec![step("mdbook build book", None), step("", Some("actions/deploy-pages@v4"))],
+                ..Default::default()
+            }],
+        };
+        let configs = vec![config];
+
+        assert!(built_in_ci(&configs, DocsFramework::MdBook).is_some());
+        assert!(built_in_ci(&configs, DocsFramework::Sphinx).is_none());
+        assert!(deployed_in_ci(&configs).is_some());
+    }
+}
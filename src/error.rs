@@ -3,7 +3,7 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum RepoHealthError {
     #[error("GitHub API error: {0}")]
-    GitHubApi(#[from] octocrab::Error),
+    GitHubApi(Box<octocrab::Error>),
 
     #[error("Invalid repository format: {0}")]
     InvalidRepoFormat(String),
@@ -20,12 +20,30 @@ pub enum RepoHealthError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Unrecognized coverage report: {0}")]
+    InvalidCoverageReport(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Unknown analyzer: {0} (see `repo-health list-analyzers`)")]
+    UnknownAnalyzer(String),
+
     #[error("Analysis failed: {0}")]
     AnalysisFailed(String),
 
+    #[error("{path} is not a clone of {repository}: no remote points at it")]
+    CheckoutMismatch { path: String, repository: String },
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
     #[error("Template error: {0}")]
     Template(#[from] handlebars::RenderError),
 }
+
+impl From<octocrab::Error> for RepoHealthError {
+    fn from(err: octocrab::Error) -> Self {
+        RepoHealthError::GitHubApi(Box::new(err))
+    }
+}
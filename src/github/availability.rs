@@ -0,0 +1,138 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::RepoHealthError;
+
+/// Repository data fetched from GitHub that analyzers depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DataSource {
+    Contributors,
+    Readme,
+    License,
+    Languages,
+    CommunityProfile,
+    FileContents,
+    Config,
+}
+
+impl DataSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataSource::Contributors => "Contributor statistics",
+            DataSource::Readme => "README",
+            DataSource::License => "License",
+            DataSource::Languages => "Languages",
+            DataSource::CommunityProfile => "Community profile",
+            DataSource::FileContents => "File contents",
+            DataSource::Config => "Repository configuration",
+        }
+    }
+}
+
+/// Which data could not be fetched and why.
+///
+/// A resource GitHub reports as missing (404) is not a gap: it genuinely does not
+/// exist. Rate limits, server errors and stats still being computed are gaps, and
+/// analyzers treat what depends on them as unknown rather than absent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataAvailability {
+    gaps: BTreeMap<DataSource, String>,
+    unfetched_files: BTreeSet<String>,
+}
+
+/// Failed file fetches named in the data gap before summarising the rest
+const MAX_FAILED_FILES_LISTED: usize = 5;
+
+impl DataAvailability {
+    pub fn record(&mut self, source: DataSource, reason: impl Into<String>) {
+        self.gaps.insert(source, reason.into());
+    }
+
+    /// The value of a fetch, recording a gap when it failed.
+    pub fn take<T>(&mut self, source: DataSource, result: crate::Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.record(source, describe(&error));
+                None
+            }
+        }
+    }
+
+    /// Records files whose contents could not be fetched, out of `total` requested.
+    pub fn record_unfetched_files(&mut self, paths: Vec<String>, total: usize) {
+        if paths.is_empty() {
+            return;
+        }
+
+        self.unfetched_files.extend(paths);
+        let listed = self.unfetched_files.iter().take(MAX_FAILED_FILES_LISTED).cloned().collect::<Vec<_>>().join(", ");
+        let more = self.unfetched_files.len().saturating_sub(MAX_FAILED_FILES_LISTED);
+        self.record(DataSource::FileContents, format!(
+            "{} of {} files could not be fetched: {}{}",
+            self.unfetched_files.len(), total, listed,
+            if more > 0 { format!(" and {} more", more) } else { String::new() }
+        ));
+    }
+
+    /// Which of `paths` were requested but could not be fetched.
+    pub fn unfetched<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        paths.into_iter().filter(|path| self.unfetched_files.contains(*path)).collect()
+    }
+
+    pub fn is_available(&self, source: DataSource) -> bool {
+        !self.gaps.contains_key(&source)
+    }
+
+    /// Why a source is unavailable, if it is.
+    pub fn gap(&self, source: DataSource) -> Option<&str> {
+        self.gaps.get(&source).map(String::as_str)
+    }
+
+    pub fn gaps(&self) -> impl Iterator<Item = (DataSource, &str)> {
+        self.gaps.iter().map(|(source, reason)| (*source, reason.as_str()))
+    }
+}
+
+/// Whether GitHub answered that the resource does not exist.
+pub(crate) fn is_not_found(error: &octocrab::Error) -> bool {
+    matches!(error, octocrab::Error::GitHub { source, .. } if source.status_code.as_u16() == 404)
+}
+
+fn describe(error: &RepoHealthError) -> String {
+    match error {
+        RepoHealthError::GitHubApi(error) => match error.as_ref() {
+            octocrab::Error::GitHub { source, .. } => format!("HTTP {}: {}", source.status_code.as_u16(), source.message),
+            other => other.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_records_gaps() {
+        let mut availability = DataAvailability::default();
+
+        assert_eq!(availability.take(DataSource::Readme, Ok("# Title")), Some("# Title"));
+        let failed: crate::Result<bool> = Err(RepoHealthError::AnalysisFailed("timed out".to_string()));
+        assert_eq!(availability.take(DataSource::License, failed), None);
+
+        assert!(availability.is_available(DataSource::Readme));
+        assert!(!availability.is_available(DataSource::License));
+        assert_eq!(availability.gap(DataSource::License), Some("Analysis failed: timed out"));
+        assert_eq!(availability.gaps().count(), 1);
+    }
+
+    #[test]
+    fn test_unfetched_files() {
+        let mut availability = DataAvailability::default();
+        availability.record_unfetched_files(Vec::new(), 3);
+        assert!(availability.is_available(DataSource::FileContents));
+
+        availability.record_unfetched_files(vec![".github/workflows/ci.yml".to_string()], 3);
+        assert_eq!(availability.gap(DataSource::FileContents), Some("1 of 3 files could not be fetched: .github/workflows/ci.yml"));
+        assert_eq!(availability.unfetched(["Cargo.toml", ".github/workflows/ci.yml"]), vec![".github/workflows/ci.yml"]);
+    }
+}
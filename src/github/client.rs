@@ -1,11 +1,57 @@
+use std::collections::{BTreeMap, HashMap};
+use futures::stream::{self, StreamExt};
 use octocrab::{Octocrab, models::Repository};
 use serde::{Deserialize, Serialize};
 use base64::{Engine as _, engine::general_purpose};
+use super::availability::{is_not_found, DataAvailability, DataSource};
+use crate::analyzers::Finding;
+use crate::ci::{self, CiFile, CiProviderRegistry};
+use crate::codeowners;
+use crate::config::{self, Config, ExtraPaths};
+use crate::coverage::{self, CoverageBadge, CoverageReport};
+use crate::identity::{IdentityReport, IdentityResolver};
+use crate::languages::{self, LanguageBreakdown};
+use crate::links::{self, BrokenLink};
+use crate::rust_crates;
+use crate::suppressions::{self, Suppression};
+use crate::truck_factor::FileOwnership;
 use crate::{Result, RepoHealthError};
 
 #[derive(Debug, Clone)]
 pub struct GitHubClient {
     octocrab: Octocrab,
+    ci_providers: CiProviderRegistry,
+    identities: IdentityResolver,
+    /// Contents of a `--config` file, layered over the repository's own
+    config_override: Option<String>,
+    limits: ContentLimits,
+}
+
+/// Caps on the file contents fetched per repository, one request each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentLimits {
+    pub rust_sources: usize,
+    pub docs: usize,
+    pub manifests: usize,
+    pub coverage_reports: usize,
+}
+
+impl ContentLimits {
+    /// Stays well inside the 60 requests/hour allowed without a token; Rust
+    /// sources are not scanned.
+    pub const UNAUTHENTICATED: Self = Self {
+        rust_sources: 0,
+        docs: 15,
+        manifests: 5,
+        coverage_reports: 2,
+    };
+
+    pub const AUTHENTICATED: Self = Self {
+        rust_sources: MAX_RUST_SOURCES,
+        docs: links::MAX_DOC_FILES,
+        manifests: 30,
+        coverage_reports: 5,
+    };
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,17 +59,89 @@ pub struct TreeEntry {
     pub path: String,
     #[serde(rename = "type")]
     pub entry_type: String,
+    /// Blob size in bytes; absent for trees
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+#[cfg(test)]
+impl TreeEntry {
+    /// A 100-byte file
+    pub(crate) fn blob(path: &str) -> Self {
+        TreeEntry { path: path.to_string(), entry_type: "blob".to_string(), size: Some(100) }
+    }
+
+    /// A directory
+    pub(crate) fn dir(path: &str) -> Self {
+        TreeEntry { path: path.to_string(), entry_type: "tree".to_string(), size: None }
+    }
+
+    pub(crate) fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContributorStats {
     pub author: Author,
     pub total: i64,
+    /// Weekly activity, oldest first
+    #[serde(default)]
+    pub weeks: Vec<ContributorWeek>,
+}
+
+/// One week of a contributor's activity from the stats endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributorWeek {
+    /// Start of the week as a Unix timestamp
+    pub w: i64,
+    /// Additions
+    pub a: i64,
+    /// Deletions
+    pub d: i64,
+    /// Commits
+    pub c: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Author {
     pub login: String,
+    /// `User` or `Bot`
+    #[serde(rename = "type", default)]
+    pub account_type: Option<String>,
+}
+
+/// GitHub's community profile (`/repos/{owner}/{repo}/community/profile`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityProfile {
+    pub health_percentage: u32,
+    pub files: CommunityProfileFiles,
+}
+
+/// Community files GitHub recognises; `None` where it found none.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommunityProfileFiles {
+    pub code_of_conduct_file: Option<CommunityProfileFile>,
+    pub contributing: Option<CommunityProfileFile>,
+    pub issue_template: Option<CommunityProfileFile>,
+    pub pull_request_template: Option<CommunityProfileFile>,
+    pub license: Option<CommunityProfileFile>,
+    pub readme: Option<CommunityProfileFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityProfileFile {
+    pub html_url: Option<String>,
+}
+
+impl CommunityProfileFile {
+    /// Whether the file is an organization default from the owner's `.github` repository.
+    pub fn is_inherited(&self, owner: &str) -> bool {
+        self.html_url.as_deref().is_some_and(|url| {
+            url.to_lowercase().contains(&format!("github.com/{}/.github/", owner.to_lowercase()))
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -33,10 +151,114 @@ pub struct RepoData {
     pub contributors: Vec<ContributorStats>,
     pub readme_content: Option<String>,
     pub has_license: bool,
+    pub languages: LanguageBreakdown,
+    /// CI configuration files discovered in the tree
+    pub ci_files: Vec<CiFile>,
+    /// Contents of files analyzers need to inspect, keyed by tree path
+    pub file_contents: HashMap<String, String>,
+    /// Coverage report committed to the repository or supplied with `--coverage`
+    pub coverage: Option<CoverageReport>,
+    /// Coverage badges referenced from the README
+    pub coverage_badges: Vec<CoverageBadge>,
+    /// External documentation links that failed, when they were checked
+    pub broken_external_links: Option<Vec<BrokenLink>>,
+    /// GitHub's own community health assessment, when the endpoint is available
+    pub community_profile: Option<CommunityProfile>,
+    /// Bots excluded from and accounts merged in `contributors`
+    pub identities: IdentityReport,
+    /// File authorship from a local checkout's history (`--local`)
+    pub file_ownership: Option<FileOwnership>,
+    /// Data that could not be fetched, as opposed to data that does not exist
+    pub availability: DataAvailability,
+    /// `.repo-health.toml` from the repository, overridden by `--config`
+    pub config: Config,
+    /// Suppressions from the configuration and inline `repo-health: ignore` comments
+    pub suppressions: Vec<Suppression>,
+    /// Problems with the repository's configuration and inline suppressions, which were ignored
+    pub config_warnings: Vec<Finding>,
+}
+
+#[cfg(test)]
+impl RepoData {
+    /// A public repository with the given tree and file contents and nothing else fetched.
+    pub(crate) fn for_tests(tree: Vec<TreeEntry>, file_contents: &[(&str, &str)]) -> Self {
+        let repository = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "name": "repo",
+            "full_name": "owner/repo",
+            "url": "https://api.github.com/repos/owner/repo",
+            "private": false,
+        })).expect("minimal repository");
+        let languages = LanguageBreakdown::from_tree(&tree);
+
+        Self {
+            repository,
+            ci_files: CiProviderRegistry::builtin().discover(&tree),
+            tree,
+            contributors: Vec::new(),
+            readme_content: None,
+            has_license: false,
+            languages,
+            file_contents: file_contents.iter().map(|(path, content)| (path.to_string(), content.to_string())).collect(),
+            coverage: None,
+            coverage_badges: Vec::new(),
+            broken_external_links: None,
+            community_profile: None,
+            identities: IdentityReport::default(),
+            file_ownership: None,
+            availability: DataAvailability::default(),
+            config: Config::default(),
+            suppressions: Vec::new(),
+            config_warnings: Vec::new(),
+        }
+    }
+}
+
+impl RepoData {
+    /// The user or organization that owns the repository.
+    pub fn owner(&self) -> &str {
+        let full_name = self.repository.full_name.as_deref().unwrap_or("");
+        full_name.split('/').next().unwrap_or(full_name)
+    }
+
+    /// Owner whose `.github` repository supplies a community file, if GitHub reports it as inherited.
+    pub fn inherited_community_file(
+        &self,
+        file: impl Fn(&CommunityProfileFiles) -> Option<&CommunityProfileFile>,
+    ) -> Option<&str> {
+        let owner = self.owner();
+        let profile = self.community_profile.as_ref()?;
+        file(&profile.files).filter(|f| f.is_inherited(owner)).map(|_| owner)
+    }
+}
+
+const MAILMAP_PATH: &str = ".mailmap";
+
+/// Maximum number of file content requests in flight at once
+const CONTENT_CONCURRENCY: usize = 8;
+
+/// Upper bound on Rust sources fetched for inline test detection, to spare the rate limit
+const MAX_RUST_SOURCES: usize = 200;
+
+/// Rust sources scanned without a token when `--scan-sources` opts in
+const UNAUTHENTICATED_RUST_SOURCES: usize = 15;
+
+#[derive(Deserialize)]
+struct ContentResponse {
+    content: String,
+}
+
+fn decode_content(content: &str, path: &str) -> Result<String> {
+    let bytes = general_purpose::STANDARD.decode(content.replace("\n", ""))
+        .map_err(|_| RepoHealthError::AnalysisFailed(format!("Failed to decode {}", path)))?;
+
+    String::from_utf8(bytes)
+        .map_err(|_| RepoHealthError::AnalysisFailed(format!("Failed to decode {} as UTF-8", path)))
 }
 
 impl GitHubClient {
     pub fn new(token: Option<String>) -> Result<Self> {
+        let limits = if token.is_some() { ContentLimits::AUTHENTICATED } else { ContentLimits::UNAUTHENTICATED };
         let octocrab = if let Some(token) = token {
             Octocrab::builder()
                 .personal_token(token)
@@ -48,7 +270,42 @@ impl GitHubClient {
                 .map_err(|_| RepoHealthError::AuthenticationFailed)?
         };
 
-        Ok(Self { octocrab })
+        Ok(Self {
+            octocrab,
+            ci_providers: CiProviderRegistry::builtin(),
+            identities: IdentityResolver::default(),
+            config_override: None,
+            limits,
+        })
+    }
+
+    /// Scan a few Rust sources for inline tests even without a token.
+    pub fn with_source_scan(mut self) -> Self {
+        self.limits.rust_sources = self.limits.rust_sources.max(UNAUTHENTICATED_RUST_SOURCES);
+        self
+    }
+
+    /// Use a custom set of CI providers for configuration discovery.
+    pub fn with_ci_providers(mut self, ci_providers: CiProviderRegistry) -> Self {
+        self.ci_providers = ci_providers;
+        self
+    }
+
+    /// Bot patterns and aliases used to resolve contributor identities.
+    pub fn identities(&self) -> &IdentityResolver {
+        &self.identities
+    }
+
+    /// Layer a configuration file over the repository's `.repo-health.toml`.
+    pub fn with_config_override(mut self, content: String) -> Self {
+        self.config_override = Some(content);
+        self
+    }
+
+    /// Use custom bot patterns and aliases when resolving contributor identities.
+    pub fn with_identities(mut self, identities: IdentityResolver) -> Self {
+        self.identities = identities;
+        self
     }
 
     pub async fn fetch_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
@@ -80,21 +337,17 @@ impl GitHubClient {
         );
 
         // Handle 202 response (stats still being calculated)
-        for attempt in 0..3 {
+        let mut attempt = 0;
+        loop {
             match self.octocrab.get::<Vec<ContributorStats>, _, _>(&url, None::<&()>).await {
                 Ok(contributors) => return Ok(contributors),
                 Err(_) if attempt < 2 => {
                     tokio::time::sleep(tokio::time::Duration::from_secs(2u64.pow(attempt))).await;
+                    attempt += 1;
                 }
-                Err(_) => {
-                    // If we can't get contributor stats after retries, return empty list
-                    // This is non-critical data, so we continue with analysis
-                    return Ok(Vec::new());
-                }
+                Err(e) => return Err(e.into()),
             }
         }
-
-        Ok(Vec::new())
     }
 
     pub async fn fetch_readme(&self, owner: &str, repo: &str) -> Result<Option<String>> {
@@ -103,31 +356,151 @@ impl GitHubClient {
             owner, repo
         );
 
-        #[derive(Deserialize)]
-        struct ReadmeResponse {
-            content: String,
+        match self.octocrab.get::<ContentResponse, _, _>(&url, None::<&()>).await {
+            Ok(response) => Ok(Some(decode_content(&response.content, "README")?)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
         }
+    }
+
+    pub async fn fetch_file(&self, owner: &str, repo: &str, branch: &str, path: &str) -> Result<Option<String>> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/contents/{}?ref={}",
+            owner, repo, path, branch
+        );
+
+        match self.octocrab.get::<ContentResponse, _, _>(&url, None::<&()>).await {
+            // Binary or non-UTF-8 files are of no use to the analyzers
+            Ok(response) => Ok(decode_content(&response.content, path).ok()),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Fetch the contents of the given paths, skipping files that can't be read.
+    ///
+    /// Also returns the paths whose requests failed, as opposed to binary files.
+    pub async fn fetch_file_contents(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+        paths: &[String],
+    ) -> (HashMap<String, String>, Vec<String>) {
+        let results: Vec<_> = stream::iter(paths)
+            .map(|path| async move { (path, self.fetch_file(owner, repo, branch, path).await) })
+            .buffer_unordered(CONTENT_CONCURRENCY)
+            .collect()
+            .await;
 
-        match self.octocrab.get::<ReadmeResponse, _, _>(&url, None::<&()>).await {
-            Ok(response) => {
-                // Base64 decode the content
-                let decoded = String::from_utf8(
-                    general_purpose::STANDARD.decode(&response.content.replace("\n", ""))
-                        .map_err(|_| RepoHealthError::AnalysisFailed("Failed to decode README".to_string()))?
-                ).map_err(|_| RepoHealthError::AnalysisFailed("Failed to decode README as UTF-8".to_string()))?;
+        let mut contents = HashMap::new();
+        let mut failed = Vec::new();
+        for (path, result) in results {
+            match result {
+                Ok(Some(content)) => {
+                    contents.insert(path.clone(), content);
+                }
+                Ok(None) => {}
+                Err(_) => failed.push(path.clone()),
+            }
+        }
+
+        (contents, failed)
+    }
+
+    /// The repository's configuration under `--config`. Only `--config` has to be valid: a
+    /// broken repository configuration is reported and replaced by the defaults.
+    fn layered_config(repo_config: Option<&str>, config_override: Option<&str>) -> Result<(Config, Vec<Finding>)> {
+        let local_layers: Vec<&str> = config_override.into_iter().collect();
+        let local_config = Config::from_layers(&local_layers)?;
 
-                Ok(Some(decoded))
+        let Some(content) = repo_config else {
+            return Ok((local_config, Vec::new()));
+        };
+        let layers: Vec<&str> = std::iter::once(content).chain(config_override).collect();
+        match Config::from_layers(&layers) {
+            Ok(config) => Ok((config, Vec::new())),
+            Err(error) => {
+                let warning = Finding::warning(format!("Ignored invalid repository configuration: {}", error))
+                    .at(config::CONFIG_FILE, None);
+                Ok((local_config, vec![warning]))
             }
-            Err(_) => Ok(None),
         }
     }
 
+    /// Tree paths whose contents the analyzers want to inspect.
+    fn content_paths(tree: &[TreeEntry], ci_files: &[CiFile], extra: &ExtraPaths, limits: &ContentLimits) -> Vec<String> {
+        let mut paths: Vec<String> = ci_files.iter().map(|f| f.path.clone()).collect();
+
+        if tree.iter().any(|entry| entry.path == MAILMAP_PATH) {
+            paths.push(MAILMAP_PATH.to_string());
+        }
+
+        paths.extend(codeowners::CODEOWNERS_PATHS.iter()
+            .filter(|path| tree.iter().any(|entry| entry.path == **path))
+            .map(|path| path.to_string()));
+
+        let blobs = || tree.iter().filter(|entry| entry.entry_type == "blob");
+        // Shallowest manifests first, so the workspace root is always included
+        let mut manifests: Vec<&str> = blobs()
+            .map(|entry| entry.path.as_str())
+            .filter(|path| rust_crates::is_manifest(path) && !languages::is_vendored(path))
+            .collect();
+        manifests.sort_by_key(|path| (path.matches('/').count(), *path));
+        paths.extend(manifests.into_iter().take(limits.manifests).map(String::from));
+
+        // Rust sources, crate `src/` directories first
+        if limits.rust_sources > 0 && blobs().any(|entry| rust_crates::is_manifest(&entry.path)) {
+            let mut sources: Vec<&str> = blobs()
+                .map(|entry| entry.path.as_str())
+                .filter(|path| path.ends_with(".rs") && !languages::is_vendored(path))
+                .collect();
+            sources.sort_by_key(|path| (!(path.starts_with("src/") || path.contains("/src/")), *path));
+            paths.extend(sources.into_iter().take(limits.rust_sources).map(String::from));
+        }
+
+        let mut docs: Vec<&str> = blobs()
+            .map(|entry| entry.path.as_str())
+            .filter(|path| links::is_doc_file(path, extra))
+            .collect();
+        docs.sort();
+        paths.extend(docs.into_iter().take(limits.docs).map(String::from));
+
+        paths.extend(blobs()
+            .filter(|entry| coverage::is_report_path(&entry.path))
+            .take(limits.coverage_reports)
+            .map(|entry| entry.path.clone()));
+
+        paths
+    }
+
     pub async fn fetch_license(&self, owner: &str, repo: &str) -> Result<bool> {
         let url = format!("https://api.github.com/repos/{}/{}/license", owner, repo);
 
         match self.octocrab.get::<serde_json::Value, _, _>(&url, None::<&()>).await {
             Ok(_) => Ok(true),
-            Err(_) => Ok(false),
+            Err(e) if is_not_found(&e) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Community profile; unavailable for private repositories and forks.
+    pub async fn fetch_community_profile(&self, owner: &str, repo: &str) -> Result<Option<CommunityProfile>> {
+        let url = format!("https://api.github.com/repos/{}/{}/community/profile", owner, repo);
+
+        match self.octocrab.get::<CommunityProfile, _, _>(&url, None::<&()>).await {
+            Ok(profile) => Ok(Some(profile)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn fetch_languages(&self, owner: &str, repo: &str) -> Result<BTreeMap<String, u64>> {
+        let url = format!("https://api.github.com/repos/{}/{}/languages", owner, repo);
+
+        match self.octocrab.get::<BTreeMap<String, u64>, _, _>(&url, None::<&()>).await {
+            Ok(languages) => Ok(languages),
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -157,12 +530,84 @@ impl GitHubClient {
         let repository = self.fetch_repository(owner, repo).await?;
         let default_branch = repository.default_branch.as_deref().unwrap_or("main");
 
-        let (tree, contributors, readme_content, has_license) = tokio::try_join!(
+        let (tree, contributors, readme_content, has_license, github_languages, community_profile) = tokio::join!(
             self.fetch_tree(owner, repo, default_branch),
             self.fetch_contributors(owner, repo),
             self.fetch_readme(owner, repo),
             self.fetch_license(owner, repo),
-        )?;
+            self.fetch_languages(owner, repo),
+            self.fetch_community_profile(owner, repo),
+        );
+
+        // Only the tree is essential; anything else that fails is recorded as a gap
+        let tree = tree?;
+        let mut availability = DataAvailability::default();
+        let contributors = availability.take(DataSource::Contributors, contributors).unwrap_or_default();
+        let readme_content = availability.take(DataSource::Readme, readme_content).flatten();
+        let has_license = availability.take(DataSource::License, has_license).unwrap_or_default();
+        let github_languages = availability.take(DataSource::Languages, github_languages).unwrap_or_default();
+        let community_profile = availability.take(DataSource::CommunityProfile, community_profile).flatten();
+
+        let languages = LanguageBreakdown::from_tree(&tree).with_github(github_languages);
+
+        // The configuration decides which other files are worth fetching
+        let repo_config = match tree.iter().any(|entry| entry.path == config::CONFIG_FILE) {
+            true => availability.take(DataSource::Config, self.fetch_file(owner, repo, default_branch, config::CONFIG_FILE).await).flatten(),
+            false => None,
+        };
+        let (config, mut config_warnings) = Self::layered_config(repo_config.as_deref(), self.config_override.as_deref())?;
+
+        // Providers the repository describes itself, checked before the built-in ones
+        let mut ci_providers = self.ci_providers.clone();
+        for provider in &config.ci_providers {
+            ci_providers.register(provider.provider()?);
+        }
+        let mut ci_files = ci_providers.discover(&tree);
+        let paths = Self::content_paths(&tree, &ci_files, &config.paths, &self.limits);
+        let (mut file_contents, mut failed_files) = self.fetch_file_contents(owner, repo, default_branch, &paths).await;
+
+        // Follow local includes (e.g. GitLab `include: local:`) a few levels deep
+        for _ in 0..3 {
+            let includes = ci::pending_includes(&ci_files, &file_contents);
+            if includes.is_empty() {
+                break;
+            }
+
+            let paths: Vec<String> = includes.iter().map(|f| f.path.clone()).collect();
+            let (contents, failed) = self.fetch_file_contents(owner, repo, default_branch, &paths).await;
+            file_contents.extend(contents);
+            failed_files.extend(failed);
+            ci_files.extend(includes.into_iter().filter(|f| file_contents.contains_key(&f.path)));
+        }
+
+        let requested = failed_files.len() + file_contents.len();
+        availability.record_unfetched_files(failed_files, requested);
+
+        // Exclude bots and merge aliases before any contributor metric sees the data
+        let identities = match file_contents.get(MAILMAP_PATH) {
+            Some(mailmap) => self.identities.clone().with_mailmap(mailmap),
+            None => self.identities.clone(),
+        };
+        let (contributors, identities) = identities.apply(contributors);
+
+        // Inline suppressions count in the documents and CI files that findings point at
+        let readme_path = links::readme_path(&tree);
+        let mut annotated: Vec<(&str, &str)> = file_contents.iter()
+            .filter(|(path, _)| {
+                **path == readme_path || links::is_doc_file(path, &config.paths) || ci_files.iter().any(|f| &f.path == *path)
+            })
+            .map(|(path, content)| (path.as_str(), content.as_str()))
+            .collect();
+        if let Some(readme) = readme_content.as_deref().filter(|_| !file_contents.contains_key(&readme_path)) {
+            annotated.push((&readme_path, readme));
+        }
+        let (inline, inline_warnings) = suppressions::find_inline(&annotated);
+        config_warnings.extend(inline_warnings);
+        let mut suppressions = config.suppressions.clone();
+        suppressions.extend(inline);
+
+        let coverage = coverage::find_in_repo(&tree, &file_contents);
+        let coverage_badges = readme_content.as_deref().map(coverage::find_badges).unwrap_or_default();
 
         Ok(RepoData {
             repository,
@@ -170,6 +615,68 @@ impl GitHubClient {
             contributors,
             readme_content,
             has_license,
+            languages,
+            ci_files,
+            file_contents,
+            coverage,
+            coverage_badges,
+            broken_external_links: None,
+            community_profile,
+            identities,
+            file_ownership: None,
+            availability,
+            config,
+            suppressions,
+            config_warnings,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_community_profile() {
+        let profile: CommunityProfile = serde_json::from_str(r#"{
+            "health_percentage": 71,
+            "description": null,
+            "documentation": null,
+            "files": {
+                "code_of_conduct": null,
+                "code_of_conduct_file": null,
+                "contributing": {"url": "https://api.github.com/repos/Acme/.github/contents/CONTRIBUTING.md",
+                                 "html_url": "https://github.com/acme/.github/blob/main/CONTRIBUTING.md"},
+                "issue_template": null,
+                "pull_request_template": null,
+                "license": {"key": "mit", "name": "MIT License", "html_url": "https://github.com/acme/app/blob/main/LICENSE"},
+                "readme": {"url": "https://api.github.com/repos/acme/app/contents/README.md",
+                           "html_url": "https://github.com/acme/app/blob/main/README.md"}
+            },
+            "updated_at": "2026-01-01T00:00:00Z"
+        }"#).unwrap();
+
+        assert_eq!(profile.health_percentage, 71);
+        assert!(profile.files.contributing.unwrap().is_inherited("Acme"));
+        assert!(!profile.files.readme.unwrap().is_inherited("acme"));
+        assert!(profile.files.code_of_conduct_file.is_none());
+    }
+
+    #[test]
+    fn test_invalid_repository_config_falls_back() {
+        let override_config = "[thresholds]\ncoverage = 90";
+
+        let (config, warnings) = GitHubClient::layered_config(Some("[thresholds]\ncoverage = 70"), Some(override_config)).unwrap();
+        assert_eq!(config.thresholds.coverage, 90.0);
+        assert!(warnings.is_empty());
+
+        let (config, warnings) = GitHubClient::layered_config(Some("[grades]\ngood = 95"), Some(override_config)).unwrap();
+        assert_eq!(config.thresholds.coverage, 90.0);
+        assert_eq!(config.grades, Config::default().grades);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path.as_deref(), Some(config::CONFIG_FILE));
+
+        let invalid_override = GitHubClient::layered_config(None, Some("[thresholds]\ncoverage = 120"));
+        assert!(matches!(invalid_override, Err(RepoHealthError::InvalidConfig(_))));
+    }
+}
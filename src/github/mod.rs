@@ -1,3 +1,8 @@
+mod availability;
 mod client;
 
-pub use client::{GitHubClient, RepoData, TreeEntry, ContributorStats};
+pub use availability::{DataAvailability, DataSource};
+pub use client::{
+    GitHubClient, RepoData, TreeEntry, ContributorStats, ContributorWeek, Author,
+    CommunityProfile, CommunityProfileFiles, CommunityProfileFile,
+};
@@ -0,0 +1,67 @@
+/// Match a `/`-separated path against a glob pattern.
+///
+/// `*` matches within a path segment, `**` matches across segments and `?`
+/// matches a single character other than `/`.
+pub fn matches(pattern: &str, path: &str) -> bool {
+    match_bytes(pattern.as_bytes(), path.as_bytes())
+}
+
+fn match_bytes(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            // `**/` may also match zero directories
+            let rest = &pattern[2..];
+            let rest_after_slash = rest.strip_prefix(b"/").unwrap_or(rest);
+            if match_bytes(rest_after_slash, path) {
+                return true;
+            }
+            (0..path.len()).any(|i| match_bytes(rest, &path[i + 1..]) || match_bytes(rest_after_slash, &path[i + 1..]))
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            if match_bytes(rest, path) {
+                return true;
+            }
+            for i in 0..path.len() {
+                if path[i] == b'/' {
+                    break;
+                }
+                if match_bytes(rest, &path[i + 1..]) {
+                    return true;
+                }
+            }
+            false
+        }
+        Some(b'?') => !path.is_empty() && path[0] != b'/' && match_bytes(&pattern[1..], &path[1..]),
+        Some(c) => path.first() == Some(c) && match_bytes(&pattern[1..], &path[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_star() {
+        assert!(matches(".github/workflows/*.yml", ".github/workflows/ci.yml"));
+        assert!(!matches(".github/workflows/*.yml", ".github/workflows/nested/ci.yml"));
+        assert!(!matches("*.yml", "ci.yaml"));
+    }
+
+    #[test]
+    fn test_double_star() {
+        assert!(matches("**/Jenkinsfile", "Jenkinsfile"));
+        assert!(matches("**/Jenkinsfile", "ci/jenkins/Jenkinsfile"));
+        assert!(matches("docs/**", "docs/guide/intro.md"));
+        assert!(matches("src/**/*.rs", "src/lib.rs"));
+        assert!(matches("src/**/*.rs", "src/a/b/c.rs"));
+        assert!(!matches("src/**/*.rs", "tests/a.rs"));
+    }
+
+    #[test]
+    fn test_question_mark() {
+        assert!(matches("file?.txt", "file1.txt"));
+        assert!(!matches("file?.txt", "file/.txt"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet};
+use chrono::{DateTime, Datelike, Months, Utc};
+use crate::github::ContributorStats;
+
+/// Length of the period compared to the one before it when looking for a shrinking community
+const ACTIVE_MONTHS: u32 = 12;
+
+/// Previously active contributors needed before a drop is called shrinking
+const MIN_CONTRIBUTORS_FOR_TREND: usize = 3;
+
+/// Commits, new contributors and active contributors in one calendar quarter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuarterActivity {
+    pub year: i32,
+    pub quarter: u32,
+    pub active: usize,
+    pub new: usize,
+}
+
+impl QuarterActivity {
+    pub fn label(&self) -> String {
+        format!("{} Q{}", self.year, self.quarter)
+    }
+}
+
+/// How the contributor base grows and holds on to newcomers, from weekly contributor stats.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContributorGrowth {
+    /// Every quarter from the first commit to now, oldest first
+    pub quarters: Vec<QuarterActivity>,
+    /// Contributors whose first commit predates the current quarter
+    pub first_timers: usize,
+    /// First timers who committed again in a later quarter
+    pub retained: usize,
+    /// Contributors with commits in the last year
+    pub active: usize,
+    /// Contributors with commits in the year before that
+    pub previously_active: usize,
+    /// Everyone who has ever committed
+    pub historical: usize,
+}
+
+impl ContributorGrowth {
+    pub fn from_stats(contributors: &[ContributorStats], now: DateTime<Utc>) -> Self {
+        let active_since = now.checked_sub_months(Months::new(ACTIVE_MONTHS)).unwrap_or(now);
+        let previous_since = active_since.checked_sub_months(Months::new(ACTIVE_MONTHS)).unwrap_or(active_since);
+        let current = quarter_of(now);
+
+        let mut growth = Self::default();
+        // (year, quarter) -> (active, new)
+        let mut quarters: BTreeMap<(i32, u32), (usize, usize)> = BTreeMap::new();
+
+        for contributor in contributors {
+            let weeks: Vec<DateTime<Utc>> = contributor.weeks.iter()
+                .filter(|week| week.c > 0)
+                .filter_map(|week| DateTime::from_timestamp(week.w, 0))
+                .collect();
+            let active_quarters: BTreeSet<(i32, u32)> = weeks.iter().map(|w| quarter_of(*w)).collect();
+            let Some(&first) = active_quarters.first() else { continue };
+
+            growth.historical += 1;
+            for quarter in &active_quarters {
+                quarters.entry(*quarter).or_default().0 += 1;
+            }
+            quarters.entry(first).or_default().1 += 1;
+
+            if first < current {
+                growth.first_timers += 1;
+                if active_quarters.len() > 1 {
+                    growth.retained += 1;
+                }
+            }
+            if weeks.iter().any(|w| *w >= active_since) {
+                growth.active += 1;
+            }
+            if weeks.iter().any(|w| *w >= previous_since && *w < active_since) {
+                growth.previously_active += 1;
+            }
+        }
+
+        if let Some((&first, _)) = quarters.first_key_value() {
+            let mut quarter = first;
+            while quarter <= current {
+                let (active, new) = quarters.get(&quarter).copied().unwrap_or_default();
+                growth.quarters.push(QuarterActivity { year: quarter.0, quarter: quarter.1, active, new });
+                quarter = if quarter.1 == 4 { (quarter.0 + 1, 1) } else { (quarter.0, quarter.1 + 1) };
+            }
+        }
+
+        growth
+    }
+
+    /// Share of first-time contributors who came back in a later quarter.
+    pub fn retention_percent(&self) -> Option<f64> {
+        (self.first_timers > 0).then(|| self.retained as f64 / self.first_timers as f64 * 100.0)
+    }
+
+    /// Share of all contributors who committed in the last year.
+    pub fn active_percent(&self) -> f64 {
+        if self.historical == 0 { 0.0 } else { self.active as f64 / self.historical as f64 * 100.0 }
+    }
+
+    /// Fewer than three quarters of last period's contributors are active now.
+    pub fn is_shrinking(&self) -> bool {
+        self.previously_active >= MIN_CONTRIBUTORS_FOR_TREND && self.active * 4 < self.previously_active * 3
+    }
+
+    /// New contributors in the last four quarters and the four before them.
+    pub fn new_contributors_trend(&self) -> (usize, usize) {
+        let mut recent = self.quarters.iter().rev().map(|q| q.new);
+        let last: usize = recent.by_ref().take(4).sum();
+        (last, recent.take(4).sum())
+    }
+}
+
+fn quarter_of(time: DateTime<Utc>) -> (i32, u32) {
+    (time.year(), (time.month() - 1) / 3 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::github::{Author, ContributorWeek};
+
+    fn contributor(login: &str, dates: &[(i32, u32)]) -> ContributorStats {
+        let weeks: Vec<ContributorWeek> = dates.iter()
+            .map(|&(year, month)| ContributorWeek {
+                w: Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap().timestamp(), a: 0, d: 0, c: 1,
+            })
+            .collect();
+        ContributorStats { author: Author { login: login.to_string(), account_type: None }, total: weeks.len() as i64, weeks }
+    }
+
+    #[test]
+    fn test_growth_and_retention() {
+        let now = Utc.with_ymd_and_hms(2026, 8, 15, 0, 0, 0).unwrap();
+        let contributors = vec![
+            contributor("alice", &[(2024, 9), (2025, 3), (2026, 7)]),
+            contributor("bob", &[(2024, 10), (2025, 2)]),
+            contributor("carol", &[(2025, 1)]),
+            contributor("dave", &[(2025, 4)]),
+            contributor("erin", &[(2026, 8)]),
+        ];
+
+        let growth = ContributorGrowth::from_stats(&contributors, now);
+        assert_eq!(growth.quarters.first().unwrap().label(), "2024 Q3");
+        assert_eq!(growth.quarters.len(), 9);
+        assert_eq!(growth.quarters[2], QuarterActivity { year: 2025, quarter: 1, active: 3, new: 1 });
+
+        // erin joined this quarter and has had no chance to return yet
+        assert_eq!((growth.retained, growth.first_timers), (2, 4));
+        assert_eq!((growth.active, growth.previously_active, growth.historical), (2, 4, 5));
+        assert!(growth.is_shrinking());
+        assert_eq!(growth.new_contributors_trend(), (1, 3));
+    }
+}
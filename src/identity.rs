@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::github::{Author, ContributorStats, ContributorWeek};
+
+/// Login patterns of well-known automation accounts, matched case-insensitively.
+const BUILTIN_BOT_PATTERNS: &[&str] = &[
+    "*[bot]", "dependabot*", "renovate*", "greenkeeper*", "snyk-bot", "github-actions",
+    "pre-commit-ci*", "allcontributors*", "imgbot*", "codecov-*", "mergify*", "*-bot",
+];
+
+/// Parse `ALIAS=CANONICAL` login pairs given on the command line.
+pub fn parse_alias(spec: &str) -> std::result::Result<(String, String), String> {
+    match spec.split_once('=') {
+        Some((alias, canonical)) if !alias.trim().is_empty() && !canonical.trim().is_empty() => {
+            Ok((alias.trim().to_string(), canonical.trim().to_string()))
+        }
+        _ => Err(format!("expected ALIAS=CANONICAL, got `{}`", spec)),
+    }
+}
+
+/// Excludes bots and merges the accounts of one person before contributor metrics are computed.
+#[derive(Debug, Clone)]
+pub struct IdentityResolver {
+    bot_patterns: Vec<String>,
+    /// Lowercase alias login to canonical login
+    aliases: HashMap<String, String>,
+}
+
+impl Default for IdentityResolver {
+    fn default() -> Self {
+        Self {
+            bot_patterns: BUILTIN_BOT_PATTERNS.iter().map(|p| p.to_string()).collect(),
+            aliases: HashMap::new(),
+        }
+    }
+}
+
+/// What identity resolution changed, for reporting.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdentityReport {
+    /// Logins excluded as bots
+    pub bots: Vec<String>,
+    /// `(alias, canonical)` logins that were merged
+    pub merged: Vec<(String, String)>,
+}
+
+impl IdentityResolver {
+    /// Add a glob pattern (`*`, `?`) for bot logins.
+    pub fn with_bot_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.bot_patterns.push(pattern.into());
+        self
+    }
+
+    /// Count `alias`'s contributions as `canonical`'s.
+    pub fn with_alias(mut self, alias: &str, canonical: &str) -> Self {
+        if !alias.eq_ignore_ascii_case(canonical) {
+            self.aliases.insert(alias.to_lowercase(), canonical.to_string());
+        }
+        self
+    }
+
+    /// Merge accounts a `.mailmap` maps to one person.
+    ///
+    /// The stats API only reports logins, so only entries whose emails carry a login
+    /// (GitHub `users.noreply.github.com` addresses) can be used. Explicit aliases win.
+    pub fn with_mailmap(mut self, mailmap: &str) -> Self {
+        for line in mailmap.lines().map(|l| l.split('#').next().unwrap_or("").trim()) {
+            let emails: Vec<&str> = line.split('<')
+                .skip(1)
+                .filter_map(|part| part.split_once('>').map(|(email, _)| email.trim()))
+                .collect();
+            let [proper, commit] = emails.as_slice() else { continue };
+            if let (Some(canonical), Some(alias)) = (noreply_login(proper), noreply_login(commit)) {
+                if !self.aliases.contains_key(&alias.to_lowercase()) {
+                    self = self.with_alias(alias, canonical);
+                }
+            }
+        }
+        self
+    }
+
+    pub fn is_bot(&self, author: &Author) -> bool {
+        let login = author.login.to_lowercase();
+        author.account_type.as_deref() == Some("Bot")
+            || self.bot_patterns.iter().any(|p| crate::glob::matches(&p.to_lowercase(), &login))
+    }
+
+    pub fn canonical<'a>(&'a self, login: &'a str) -> &'a str {
+        self.aliases.get(&login.to_lowercase()).map(String::as_str).unwrap_or(login)
+    }
+
+    /// Drop bots and fold aliases into their canonical account, summing weekly activity.
+    pub fn apply(&self, contributors: Vec<ContributorStats>) -> (Vec<ContributorStats>, IdentityReport) {
+        let mut report = IdentityReport::default();
+        let mut merged: Vec<ContributorStats> = Vec::new();
+
+        for contributor in contributors {
+            if self.is_bot(&contributor.author) {
+                report.bots.push(contributor.author.login);
+                continue;
+            }
+
+            let canonical = self.canonical(&contributor.author.login).to_string();
+            if canonical != contributor.author.login {
+                report.merged.push((contributor.author.login.clone(), canonical.clone()));
+            }
+
+            match merged.iter_mut().find(|c| c.author.login.eq_ignore_ascii_case(&canonical)) {
+                Some(existing) => {
+                    existing.total += contributor.total;
+                    existing.weeks = merge_weeks(&existing.weeks, &contributor.weeks);
+                }
+                None => merged.push(ContributorStats {
+                    author: Author { login: canonical, ..contributor.author },
+                    ..contributor
+                }),
+            }
+        }
+
+        report.bots.sort();
+        report.merged.sort();
+        (merged, report)
+    }
+}
+
+fn merge_weeks(a: &[ContributorWeek], b: &[ContributorWeek]) -> Vec<ContributorWeek> {
+    let mut weeks: BTreeMap<i64, ContributorWeek> = BTreeMap::new();
+
+    for week in a.iter().chain(b) {
+        let entry = weeks.entry(week.w).or_insert(ContributorWeek { w: week.w, a: 0, d: 0, c: 0 });
+        entry.a += week.a;
+        entry.d += week.d;
+        entry.c += week.c;
+    }
+
+    weeks.into_values().collect()
+}
+
+/// Login from `login@users.noreply.github.com` or `12345+login@users.noreply.github.com`.
+pub(crate) fn noreply_login(email: &str) -> Option<&str> {
+    let local = email.strip_suffix("@users.noreply.github.com")?;
+    Some(local.split_once('+').map(|(_, login)| login).unwrap_or(local)).filter(|l| !l.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contributor(login: &str, account_type: &str, weeks: &[(i64, i64)]) -> ContributorStats {
+        ContributorStats {
+            author: Author { login: login.to_string(), account_type: Some(account_type.to_string()) },
+            total: weeks.iter().map(|(_, c)| c).sum(),
+            weeks: weeks.iter().map(|&(w, c)| ContributorWeek { w, a: 0, d: 0, c }).collect(),
+        }
+    }
+
+    #[test]
+    fn test_bots_and_aliases() {
+        let resolver = IdentityResolver::default()
+            .with_bot_pattern("release-*")
+            .with_mailmap("# team\nJane Doe <1+jane@users.noreply.github.com> Jane <2+jdoe-work@users.noreply.github.com>\n")
+            .with_alias("OldBob", "bob");
+
+        let contributors = vec![
+            contributor("jane", "User", &[(100, 5)]),
+            contributor("dependabot[bot]", "Bot", &[(100, 50)]),
+            contributor("jdoe-work", "User", &[(100, 1), (200, 2)]),
+            contributor("bob", "User", &[(200, 4)]),
+            contributor("OldBob", "User", &[(100, 3)]),
+            contributor("release-please", "User", &[(100, 9)]),
+            contributor("ci-runner", "Bot", &[(100, 9)]),
+        ];
+
+        let (merged, report) = resolver.apply(contributors);
+
+        assert_eq!(report.bots, vec!["ci-runner", "dependabot[bot]", "release-please"]);
+        assert_eq!(report.merged, vec![
+            ("OldBob".to_string(), "bob".to_string()),
+            ("jdoe-work".to_string(), "jane".to_string()),
+        ]);
+
+        let totals: Vec<(&str, i64)> = merged.iter().map(|c| (c.author.login.as_str(), c.total)).collect();
+        assert_eq!(totals, vec![("jane", 8), ("bob", 7)]);
+        assert_eq!(merged[0].weeks.iter().map(|w| (w.w, w.c)).collect::<Vec<_>>(), vec![(100, 6), (200, 2)]);
+    }
+
+    #[test]
+    fn test_parse_alias() {
+        assert_eq!(parse_alias("old=new"), Ok(("old".to_string(), "new".to_string())));
+        assert!(parse_alias("old").is_err());
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::github::TreeEntry;
+
+/// Path prefixes that hold third-party code rather than the project's own sources.
+const VENDORED_PREFIXES: &[&str] = &["vendor/", "node_modules/", "third_party/", "third-party/"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Language {
+    Rust,
+    JavaScript,
+    TypeScript,
+    Python,
+    Go,
+    Java,
+    Kotlin,
+    Ruby,
+    C,
+    Cpp,
+    CSharp,
+    Php,
+    Swift,
+}
+
+impl Language {
+    pub const ALL: &'static [Language] = &[
+        Language::Rust, Language::JavaScript, Language::TypeScript, Language::Python,
+        Language::Go, Language::Java, Language::Kotlin, Language::Ruby, Language::C,
+        Language::Cpp, Language::CSharp, Language::Php, Language::Swift,
+    ];
+
+    /// Name as used by GitHub's languages endpoint.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::JavaScript => "JavaScript",
+            Language::TypeScript => "TypeScript",
+            Language::Python => "Python",
+            Language::Go => "Go",
+            Language::Java => "Java",
+            Language::Kotlin => "Kotlin",
+            Language::Ruby => "Ruby",
+            Language::C => "C",
+            Language::Cpp => "C++",
+            Language::CSharp => "C#",
+            Language::Php => "PHP",
+            Language::Swift => "Swift",
+        }
+    }
+
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &["rs"],
+            Language::JavaScript => &["js", "jsx", "mjs", "cjs"],
+            Language::TypeScript => &["ts", "tsx", "mts", "cts"],
+            Language::Python => &["py"],
+            Language::Go => &["go"],
+            Language::Java => &["java"],
+            Language::Kotlin => &["kt", "kts"],
+            Language::Ruby => &["rb"],
+            Language::C => &["c", "h"],
+            Language::Cpp => &["cc", "cpp", "cxx", "hpp", "hh", "hxx"],
+            Language::CSharp => &["cs"],
+            Language::Php => &["php"],
+            Language::Swift => &["swift"],
+        }
+    }
+
+    pub fn from_path(path: &str) -> Option<Language> {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        let (_, extension) = file_name.rsplit_once('.')?;
+        Self::ALL.iter().copied().find(|lang| lang.extensions().contains(&extension))
+    }
+
+    pub fn from_name(name: &str) -> Option<Language> {
+        Self::ALL.iter().copied().find(|lang| lang.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Test-to-source file ratios considered good and excellent for this ecosystem.
+    ///
+    /// Rust keeps most unit tests inline and Go puts one `_test.go` next to
+    /// each file, so the same ratio means very different things.
+    pub fn test_ratio_targets(&self) -> (f64, f64) {
+        match self {
+            Language::Rust | Language::C | Language::Cpp => (0.10, 0.25),
+            Language::Go | Language::Java | Language::Kotlin | Language::CSharp => (0.30, 0.60),
+            Language::Ruby | Language::Php => (0.30, 0.70),
+            Language::JavaScript | Language::TypeScript | Language::Python | Language::Swift => (0.20, 0.50),
+        }
+    }
+
+    /// Whether a source file of this language is a test according to the language's conventions.
+    pub fn is_test_file(&self, path: &str) -> bool {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        let stem = file_name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(file_name);
+        let in_dir = |dir: &str| path.starts_with(&format!("{}/", dir)) || path.contains(&format!("/{}/", dir));
+
+        match self {
+            Language::Rust => in_dir("tests") || stem.ends_with("_test") || stem.ends_with("_tests"),
+            Language::JavaScript | Language::TypeScript => {
+                file_name.contains(".test.") || file_name.contains(".spec.")
+                    || in_dir("__tests__") || in_dir("test") || in_dir("tests")
+            }
+            Language::Python => {
+                stem.starts_with("test_") || stem.ends_with("_test") || stem == "conftest"
+                    || in_dir("tests") || in_dir("test")
+            }
+            Language::Go => stem.ends_with("_test"),
+            Language::Java | Language::Kotlin => {
+                path.contains("src/test/") || stem.ends_with("Test") || stem.ends_with("Tests")
+            }
+            Language::Ruby => {
+                stem.ends_with("_spec") || stem.ends_with("_test") || in_dir("spec") || in_dir("test")
+            }
+            Language::C | Language::Cpp => {
+                stem.starts_with("test_") || stem.ends_with("_test") || in_dir("test") || in_dir("tests")
+            }
+            Language::CSharp => stem.ends_with("Tests") || stem.ends_with("Test") || path.contains(".Tests/"),
+            Language::Php => stem.ends_with("Test") || in_dir("tests"),
+            Language::Swift => stem.ends_with("Tests") || in_dir("Tests"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LanguageStats {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Per-language size of the repository, from the tree and GitHub's languages endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LanguageBreakdown {
+    /// Files and bytes per language counted from the git tree.
+    pub tree: BTreeMap<Language, LanguageStats>,
+    /// Bytes per language as reported by GitHub (linguist), including languages we don't model.
+    pub github: BTreeMap<String, u64>,
+}
+
+impl LanguageBreakdown {
+    pub fn from_tree(tree: &[TreeEntry]) -> Self {
+        let mut stats: BTreeMap<Language, LanguageStats> = BTreeMap::new();
+
+        for entry in tree.iter().filter(|e| e.entry_type == "blob" && !is_vendored(&e.path)) {
+            if let Some(language) = Language::from_path(&entry.path) {
+                let stat = stats.entry(language).or_default();
+                stat.files += 1;
+                stat.bytes += entry.size.unwrap_or(0);
+            }
+        }
+
+        Self { tree: stats, github: BTreeMap::new() }
+    }
+
+    pub fn with_github(mut self, github: BTreeMap<String, u64>) -> Self {
+        self.github = github;
+        self
+    }
+
+    /// Languages whose conventions apply to this repository.
+    ///
+    /// GitHub's linguist data excludes vendored, generated and documentation
+    /// files, so it is preferred; the tree is the fallback when it's unavailable.
+    pub fn present(&self) -> Vec<Language> {
+        if self.github.is_empty() {
+            self.tree.keys().copied().collect()
+        } else {
+            let mut present: Vec<Language> = self.github.keys().filter_map(|n| Language::from_name(n)).collect();
+            present.sort();
+            present
+        }
+    }
+
+    pub fn is_present(&self, language: Language) -> bool {
+        self.present().contains(&language)
+    }
+
+    /// The language of a path, if it is one of the languages present in the repository.
+    pub fn language_of(&self, path: &str) -> Option<Language> {
+        Language::from_path(path).filter(|lang| self.is_present(*lang))
+    }
+
+    /// Rows of (language, files, bytes, share %) sorted by size, for reporting.
+    pub fn rows(&self) -> Vec<(String, Option<usize>, u64, f64)> {
+        let mut rows: Vec<(String, Option<usize>, u64)> = if self.github.is_empty() {
+            self.tree.iter()
+                .map(|(lang, stat)| (lang.name().to_string(), Some(stat.files), stat.bytes))
+                .collect()
+        } else {
+            self.github.iter()
+                .map(|(name, bytes)| {
+                    let files = Language::from_name(name).and_then(|l| self.tree.get(&l)).map(|s| s.files);
+                    (name.clone(), files, *bytes)
+                })
+                .collect()
+        };
+
+        rows.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+        let total: u64 = rows.iter().map(|r| r.2).sum();
+
+        rows.into_iter()
+            .map(|(name, files, bytes)| {
+                let share = if total > 0 { bytes as f64 / total as f64 * 100.0 } else { 0.0 };
+                (name, files, bytes, share)
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn is_vendored(path: &str) -> bool {
+    VENDORED_PREFIXES.iter().any(|prefix| path.starts_with(prefix) || path.contains(&format!("/{}", prefix)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(Language::from_path("src/main.rs"), Some(Language::Rust));
+        assert_eq!(Language::from_path("web/app.tsx"), Some(Language::TypeScript));
+        assert_eq!(Language::from_path("README.md"), None);
+        assert_eq!(Language::from_path("Makefile"), None);
+    }
+
+    #[test]
+    fn test_conventions_are_per_language() {
+        // A `/test_` path is a Python convention, not a Go or Rust one
+        assert!(Language::Python.is_test_file("pkg/test_utils.py"));
+        assert!(!Language::Go.is_test_file("pkg/test_utils.go"));
+        assert!(Language::Go.is_test_file("pkg/utils_test.go"));
+        assert!(!Language::Rust.is_test_file("src/test_helpers.rs"));
+        assert!(Language::Rust.is_test_file("tests/integration.rs"));
+        assert!(Language::Java.is_test_file("src/test/java/com/example/AppTest.java"));
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let tree = vec![
+            TreeEntry::blob("src/main.rs").with_size(1000),
+            TreeEntry::blob("src/lib.rs").with_size(500),
+            TreeEntry::blob("scripts/build.py"),
+            TreeEntry::blob("vendor/lib/dep.go").with_size(9000),
+        ];
+
+        let breakdown = LanguageBreakdown::from_tree(&tree);
+        assert_eq!(breakdown.tree[&Language::Rust].files, 2);
+        assert_eq!(breakdown.tree[&Language::Rust].bytes, 1500);
+        assert!(!breakdown.tree.contains_key(&Language::Go));
+        assert_eq!(breakdown.present(), vec![Language::Rust, Language::Python]);
+
+        let breakdown = breakdown.with_github([("Rust".to_string(), 1500), ("Shell".to_string(), 20)].into_iter().collect());
+        assert_eq!(breakdown.present(), vec![Language::Rust]);
+        assert_eq!(breakdown.language_of("scripts/build.py"), None);
+        assert_eq!(breakdown.rows()[0].0, "Rust");
+    }
+}
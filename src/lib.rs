@@ -2,6 +2,23 @@ pub mod cli;
 pub mod error;
 pub mod github;
 pub mod analyzers;
+pub mod ci;
+pub mod codeowners;
+pub mod community;
+pub mod config;
+pub mod coverage;
+pub mod docs_site;
+pub mod glob;
+pub mod growth;
+pub mod identity;
+pub mod languages;
+pub mod links;
+pub mod markdown;
+pub mod rust_crates;
+pub mod rust_docs;
+pub mod suppressions;
+pub mod test_layout;
+pub mod truck_factor;
 pub mod scoring;
 pub mod output;
 
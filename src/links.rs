@@ -0,0 +1,295 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use futures::stream::{self, StreamExt};
+use url::Url;
+use crate::config::ExtraPaths;
+use crate::github::TreeEntry;
+use crate::markdown::{Document, Link};
+
+/// Directories whose Markdown files are checked alongside the README
+const DOC_DIRS: &[&str] = &["docs/", "documentation/"];
+
+/// Upper bound on documentation files fetched for link checking, to spare the rate limit
+pub const MAX_DOC_FILES: usize = 50;
+
+pub const DEFAULT_LINK_CONCURRENCY: usize = 8;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A link that does not resolve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrokenLink {
+    /// Document containing the link
+    pub file: String,
+    pub line: usize,
+    pub text: String,
+    pub destination: String,
+    pub reason: String,
+}
+
+impl BrokenLink {
+    fn new(file: &str, link: &Link, reason: impl Into<String>) -> Self {
+        Self {
+            file: file.to_string(),
+            line: link.line,
+            text: link.text.clone(),
+            destination: link.destination.clone(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Whether a tree path is documentation whose links should be checked.
+pub fn is_doc_file(path: &str, extra: &ExtraPaths) -> bool {
+    (DOC_DIRS.iter().any(|dir| path.starts_with(dir)) || extra.is_doc(path)) && path.ends_with(".md")
+}
+
+/// Path of the README GitHub displays: `.github/`, then the root, then `docs/`.
+pub fn readme_path(tree: &[TreeEntry]) -> String {
+    [".github/", "", "docs/"].iter()
+        .find_map(|dir| {
+            tree.iter().find(|e| {
+                e.entry_type == "blob"
+                    && e.path.strip_prefix(dir).is_some_and(|name| {
+                        !name.contains('/') && name.to_lowercase().starts_with("readme")
+                    })
+            })
+        })
+        .map(|e| e.path.clone())
+        .unwrap_or_else(|| "README.md".to_string())
+}
+
+/// The README and documentation files that were fetched, parsed and keyed by tree path.
+pub fn documents(
+    tree: &[TreeEntry],
+    readme: Option<&str>,
+    files: &HashMap<String, String>,
+    extra: &ExtraPaths,
+) -> HashMap<String, Document> {
+    let mut documents: HashMap<String, Document> = files.iter()
+        .filter(|(path, _)| is_doc_file(path, extra))
+        .map(|(path, content)| (path.clone(), Document::parse(content)))
+        .collect();
+
+    if let Some(readme) = readme {
+        documents.insert(readme_path(tree), Document::parse(readme));
+    }
+
+    documents
+}
+
+/// Relative links and anchors that don't match the tree or the target's headings.
+pub fn check_internal(documents: &HashMap<String, Document>, tree: &[TreeEntry]) -> Vec<BrokenLink> {
+    let paths: HashSet<&str> = tree.iter().map(|e| e.path.as_str()).collect();
+    let anchors: HashMap<&str, Vec<String>> = documents.iter()
+        .map(|(path, document)| (path.as_str(), document.anchors()))
+        .collect();
+
+    let mut sources: Vec<&String> = documents.keys().collect();
+    sources.sort();
+
+    let mut broken = Vec::new();
+    for source in sources {
+        for link in documents[source].links.iter().chain(&documents[source].images) {
+            if is_external(&link.destination) {
+                continue;
+            }
+
+            let (target, fragment) = match link.destination.split_once('#') {
+                Some((target, fragment)) => (target, Some(fragment)),
+                None => (link.destination.as_str(), None),
+            };
+            let target = target.split('?').next().unwrap_or("");
+
+            let resolved = if target.is_empty() {
+                source.clone()
+            } else {
+                match resolve(source, &target.replace("%20", " ")) {
+                    Some(path) => path,
+                    None => {
+                        broken.push(BrokenLink::new(source, link, "points outside the repository"));
+                        continue;
+                    }
+                }
+            };
+
+            if !resolved.is_empty() && !paths.contains(resolved.as_str()) {
+                broken.push(BrokenLink::new(source, link, format!("{} does not exist", resolved)));
+                continue;
+            }
+
+            // Anchors can only be checked in documents that were parsed
+            if let (Some(fragment), Some(target_anchors)) = (fragment, anchors.get(resolved.as_str())) {
+                let fragment = fragment.to_lowercase();
+                if !fragment.is_empty() && !is_line_anchor(&fragment) && !target_anchors.contains(&fragment) {
+                    broken.push(BrokenLink::new(source, link, format!("no heading for #{} in {}", fragment, resolved)));
+                }
+            }
+        }
+    }
+
+    broken
+}
+
+/// GitHub line anchors such as `#L10` or `#L10-L20`.
+fn is_line_anchor(fragment: &str) -> bool {
+    fragment.strip_prefix('l')
+        .is_some_and(|rest| !rest.is_empty() && rest.split("-l").all(|n| n.chars().all(|c| c.is_ascii_digit())))
+}
+
+fn is_external(destination: &str) -> bool {
+    destination.starts_with("//")
+        || destination.split_once(':').is_some_and(|(scheme, _)| {
+            !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        })
+}
+
+/// Resolve a relative link against the linking document; `/` is the repository root.
+fn resolve(source: &str, target: &str) -> Option<String> {
+    let mut parts: Vec<&str> = if target.starts_with('/') {
+        Vec::new()
+    } else {
+        source.split('/').collect::<Vec<_>>().split_last().map(|(_, dir)| dir.to_vec()).unwrap_or_default()
+    };
+
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            segment => parts.push(segment),
+        }
+    }
+
+    Some(parts.join("/"))
+}
+
+/// External link checker with a global concurrency limit and an optional host allowlist.
+pub struct ExternalLinkChecker {
+    client: reqwest::Client,
+    concurrency: usize,
+    /// Hosts that may be contacted; empty allows every host
+    allowed_hosts: Vec<String>,
+}
+
+impl ExternalLinkChecker {
+    pub fn new(concurrency: usize, allowed_hosts: Vec<String>) -> crate::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent("repo-health")
+            .build()?;
+
+        Ok(Self { client, concurrency: concurrency.max(1), allowed_hosts })
+    }
+
+    fn is_allowed(&self, url: &Url) -> bool {
+        self.allowed_hosts.is_empty()
+            || url.host_str().is_some_and(|host| {
+                self.allowed_hosts.iter().any(|allowed| host == allowed || host.ends_with(&format!(".{}", allowed)))
+            })
+    }
+
+    /// Check the external links of every document; each URL is requested once.
+    pub async fn check(&self, documents: &HashMap<String, Document>) -> Vec<BrokenLink> {
+        let mut links: Vec<(&str, &Link, Url)> = documents.iter()
+            .flat_map(|(file, document)| document.links.iter().map(move |link| (file.as_str(), link)))
+            .filter_map(|(file, link)| {
+                let url = Url::parse(&link.destination).ok()?;
+                (matches!(url.scheme(), "http" | "https") && self.is_allowed(&url)).then_some((file, link, url))
+            })
+            .collect();
+        links.sort_by(|a, b| (a.0, a.1.line).cmp(&(b.0, b.1.line)));
+
+        let mut urls: Vec<&Url> = links.iter().map(|(_, _, url)| url).collect();
+        urls.sort();
+        urls.dedup();
+
+        let failures: HashMap<&Url, String> = stream::iter(urls)
+            .map(|url| async move { (url, self.fetch_failure(url).await) })
+            .buffer_unordered(self.concurrency)
+            .filter_map(|(url, failure)| async move { failure.map(|f| (url, f)) })
+            .collect()
+            .await;
+
+        links.iter()
+            .filter_map(|(file, link, url)| Some(BrokenLink::new(file, link, failures.get(url)?.clone())))
+            .collect()
+    }
+
+    async fn fetch_failure(&self, url: &Url) -> Option<String> {
+        let mut target = url.clone();
+        target.set_fragment(None);
+
+        // Some servers reject HEAD; retry those with GET
+        let response = match self.client.head(target.clone()).send().await {
+            Ok(response) if response.status().is_success() => return None,
+            Ok(_) | Err(_) => self.client.get(target).send().await,
+        };
+
+        match response {
+            Ok(response) if response.status().is_success() => None,
+            Ok(response) => Some(format!("HTTP {}", response.status().as_u16())),
+            Err(e) => Some(format!("request failed: {}", e.without_url())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Vec<TreeEntry> {
+        vec![
+            TreeEntry::blob("README.md"),
+            TreeEntry::dir("docs"),
+            TreeEntry::blob("docs/guide.md"),
+            TreeEntry::blob("docs/img/logo.png"),
+            TreeEntry::blob("LICENSE"),
+        ]
+    }
+
+    #[test]
+    fn test_check_internal() {
+        let mut files = HashMap::new();
+        files.insert("docs/guide.md".to_string(), "# Guide\n## Setup\n[back](../README.md#usage) [logo](img/logo.png) [up](../../x)".to_string());
+        let readme = "# App\n## Usage\n[Guide](docs/guide.md#setup) [Missing](docs/missing.md) [Bad anchor](#install)\n\
+            [License](LICENSE) [Docs](docs/) [Line](docs/guide.md#L3) [Site](https://example.com) [Mail](mailto:a@b.c)";
+
+        let documents = documents(&tree(), Some(readme), &files, &ExtraPaths::default());
+        let broken = check_internal(&documents, &tree());
+
+        let summary: Vec<(&str, &str, &str)> = broken.iter()
+            .map(|b| (b.file.as_str(), b.text.as_str(), b.reason.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("README.md", "Missing", "docs/missing.md does not exist"),
+            ("README.md", "Bad anchor", "no heading for #install in README.md"),
+            ("docs/guide.md", "up", "points outside the repository"),
+        ]);
+    }
+
+    #[test]
+    fn test_readme_path() {
+        assert_eq!(readme_path(&tree()), "README.md");
+        assert_eq!(readme_path(&[TreeEntry::blob(".github/README.md"), TreeEntry::blob("README.md")]), ".github/README.md");
+    }
+
+    #[tokio::test]
+    async fn test_check_external() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("HEAD", "/ok").with_status(200).create_async().await;
+        server.mock("HEAD", "/gone").with_status(404).create_async().await;
+        server.mock("GET", "/gone").with_status(404).create_async().await;
+
+        let readme = format!("[ok]({0}/ok) [gone]({0}/gone) [elsewhere](https://unreachable.invalid/)", server.url());
+        let documents = documents(&tree(), Some(&readme), &HashMap::new(), &ExtraPaths::default());
+
+        let checker = ExternalLinkChecker::new(2, vec!["127.0.0.1".to_string()]).unwrap();
+        let broken = checker.check(&documents).await;
+
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].text, "gone");
+        assert_eq!(broken[0].reason, "HTTP 404");
+    }
+}
@@ -1,15 +1,20 @@
 use clap::Parser;
 use colored::Colorize;
 use repo_health::{
-    cli::{Cli, parse_repo_input},
+    cli::{Cli, Command, parse_repo_input},
+    config::{self, Config},
+    ci::CiProviderRegistry,
+    coverage::{BadgeResolver, CoverageReport},
+    links::{self, ExternalLinkChecker},
     github::GitHubClient,
-    analyzers::{
-        Analyzer, DocumentationAnalyzer, TestsAnalyzer,
-        CiCdAnalyzer, DependenciesAnalyzer, BusFactorAnalyzer,
-    },
+    identity::IdentityResolver,
+    truck_factor::FileOwnership,
+    analyzers::{AnalyzerRegistry, BusFactorAnalyzer, CategoryResult, Finding},
     scoring::ScoreCalculator,
     output::MarkdownGenerator,
+    suppressions,
 };
+use std::collections::BTreeMap;
 use std::fs;
 
 #[tokio::main]
@@ -23,8 +28,23 @@ async fn main() {
 async fn run() -> repo_health::Result<()> {
     let cli = Cli::parse();
 
+    if let Some(Command::ListAnalyzers) = cli.command {
+        // Show each analyzer's share of the overall score when every analyzer runs
+        list_analyzers(&AnalyzerRegistry::builtin().with_weights(&BTreeMap::new())?);
+        return Ok(());
+    }
+
+    // Check the local configuration and analyzer selection before any API calls
+    let config_override = cli.config.as_ref().map(fs::read_to_string).transpose()?;
+    let local_config = config_override.as_deref().map(Config::parse).transpose()?.unwrap_or_default();
+    analyzer_registry(&cli, &local_config)?;
+
     // Parse repository input
-    let (owner, repo) = parse_repo_input(&cli.repository)?;
+    let repository = cli.repository.as_deref().expect("clap requires a repository without a subcommand");
+    let (owner, repo) = parse_repo_input(repository)?;
+    if let Some(path) = &cli.local {
+        FileOwnership::verify_checkout(path, &owner, &repo)?;
+    }
 
     if !cli.quiet {
         println!("{}", "=".repeat(60).cyan());
@@ -41,70 +61,133 @@ async fn run() -> repo_health::Result<()> {
     }
 
     // Initialize GitHub client
-    let client = GitHubClient::new(cli.token)?;
+    let mut ci_providers = CiProviderRegistry::builtin();
+    for provider in cli.ci_providers.iter().cloned() {
+        ci_providers.register(provider);
+    }
+    let mut identities = IdentityResolver::default();
+    for pattern in &cli.bot_patterns {
+        identities = identities.with_bot_pattern(pattern);
+    }
+    for (alias, canonical) in &cli.aliases {
+        identities = identities.with_alias(alias, canonical);
+    }
+
+    let mut client = GitHubClient::new(cli.token.clone())?
+        .with_ci_providers(ci_providers)
+        .with_identities(identities);
+    if let Some(content) = config_override {
+        client = client.with_config_override(content);
+    }
+    if cli.scan_sources {
+        client = client.with_source_scan();
+    }
 
     // Fetch all data
     if !cli.quiet {
         println!("{}", "Fetching repository data...".yellow());
     }
 
-    let repo_data = client.fetch_all_data(&owner, &repo).await?;
+    let mut repo_data = client.fetch_all_data(&owner, &repo).await?;
+
+    if let Some(path) = &cli.coverage {
+        let source = path.display().to_string();
+        let mut report = CoverageReport::parse(&source, &fs::read_to_string(path)?)
+            .ok_or(repo_health::RepoHealthError::InvalidCoverageReport(source))?;
+        report.relativize(&repo_data.tree);
+        repo_data.coverage = Some(report);
+    }
+
+    if let Some(path) = &cli.local {
+        repo_data.file_ownership = Some(FileOwnership::from_checkout(path, client.identities())?);
+    }
+
+    if !cli.offline && !repo_data.coverage_badges.is_empty() {
+        BadgeResolver::new(&cli.badge_endpoint)?.resolve(&mut repo_data.coverage_badges).await;
+    }
+
+    if cli.check_external_links && !cli.offline {
+        let documents = links::documents(
+            &repo_data.tree, repo_data.readme_content.as_deref(), &repo_data.file_contents, &repo_data.config.paths,
+        );
+        let checker = ExternalLinkChecker::new(cli.link_concurrency, cli.link_hosts.clone())?;
+        repo_data.broken_external_links = Some(checker.check(&documents).await);
+    }
+
+    // Analyzer IDs in the repository's configuration that don't exist are ignored, like any invalid setting there
+    let registry = match analyzer_registry(&cli, &repo_data.config) {
+        Ok(registry) => registry,
+        Err(error) => {
+            repo_data.config_warnings.push(
+                Finding::warning(format!("Ignored analyzer selection and weights in the repository configuration: {}", error))
+                    .at(config::CONFIG_FILE, None)
+            );
+            analyzer_registry(&cli, &local_config)?
+        }
+    };
 
     if !cli.quiet {
         println!("{} Data fetched successfully", "✓".green());
+        for (source, reason) in repo_data.availability.gaps() {
+            println!("{} {} unavailable: {}", "⚠".yellow(), source.name(), reason);
+        }
+        for warning in &repo_data.config_warnings {
+            println!("{} {} ({})", "⚠".yellow(), warning.message, warning.location().unwrap_or_default());
+        }
         println!();
     }
 
     // Run analyzers
-    let analyzers: Vec<(String, f64, Box<dyn Analyzer>)> = vec![
-        ("Documentation".to_string(), 0.20, Box::new(DocumentationAnalyzer)),
-        ("Tests".to_string(), 0.25, Box::new(TestsAnalyzer)),
-        ("CI/CD".to_string(), 0.20, Box::new(CiCdAnalyzer)),
-        ("Dependencies".to_string(), 0.20, Box::new(DependenciesAnalyzer)),
-        ("Bus Factor".to_string(), 0.15, Box::new(BusFactorAnalyzer)),
-    ];
-
     if !cli.quiet {
         println!("{}", "Running analyzers...".yellow());
         println!();
     }
 
+    let grades = repo_data.config.grades;
+    let today = chrono::Utc::now().date_naive();
     let mut results = Vec::new();
 
-    for (name, weight, analyzer) in analyzers {
+    for analyzer in registry.analyzers() {
         if !cli.quiet {
-            print!("  {} {}... ", "→".cyan(), name.bold());
+            print!("  {} {}... ", "→".cyan(), analyzer.name().bold());
         }
 
-        let result = analyzer.analyze(&repo_data).await?;
+        let mut category = CategoryResult::new(analyzer.as_ref(), analyzer.analyze(&repo_data).await?);
+        suppressions::apply(&mut category, &repo_data.suppressions, today);
+        let result = &category.result;
 
         if !cli.quiet {
-            let grade = ScoreCalculator::grade_short(result.score);
-            let score_colored = if result.score >= 80.0 {
+            let grade = grades.grade(result.score).short();
+            let score_colored = if !result.is_scored() {
+                format!("{} (excluded)", result.assessment.label()).dimmed()
+            } else if result.score >= grades.good {
                 format!("{:.1}/100 ({})", result.score, grade).green()
-            } else if result.score >= 60.0 {
+            } else if result.score >= grades.needs_improvement {
                 format!("{:.1}/100 ({})", result.score, grade).yellow()
             } else {
                 format!("{:.1}/100 ({})", result.score, grade).red()
             };
-            println!("{}", score_colored);
+            match category.suppressed.len() {
+                0 => println!("{}", score_colored),
+                n => println!("{} {}", score_colored, format!("({} suppressed)", n).dimmed()),
+            }
         }
 
-        results.push((name, weight, result));
+        results.push(category);
     }
 
     // Calculate overall score
     let overall_score = ScoreCalculator::calculate_overall(&results);
-    let grade = ScoreCalculator::grade(overall_score);
+    let grade = grades.grade(overall_score).label();
 
     if !cli.quiet {
         println!();
         println!("{}", "=".repeat(60).cyan());
         print!("{} ", "Overall Score:".bold());
 
-        let score_colored = if overall_score >= 80.0 {
+        let score_colored = if overall_score >= grades.good {
             format!("{:.1}/100 ({})", overall_score, grade).green().bold()
-        } else if overall_score >= 60.0 {
+        } else if overall_score >= grades.needs_improvement {
             format!("{:.1}/100 ({})", overall_score, grade).yellow().bold()
         } else {
             format!("{:.1}/100 ({})", overall_score, grade).red().bold()
@@ -116,7 +199,7 @@ async fn run() -> repo_health::Result<()> {
     }
 
     // Generate markdown report
-    let markdown = MarkdownGenerator::generate(&owner, &repo, overall_score, &results);
+    let markdown = MarkdownGenerator::generate(&owner, &repo, overall_score, &results, &repo_data);
 
     // Write to file
     fs::write(&cli.output, &markdown)?;
@@ -137,3 +220,25 @@ async fn run() -> repo_health::Result<()> {
 
     Ok(())
 }
+
+/// The analyzers to run: `--only`/`--skip` over the configuration's selection, with its weights.
+fn analyzer_registry(cli: &Cli, config: &Config) -> repo_health::Result<AnalyzerRegistry> {
+    let mut registry = AnalyzerRegistry::builtin();
+    registry.register(Box::new(
+        BusFactorAnalyzer::new(cli.bus_factor_windows.clone()).with_algorithm(cli.bus_factor_algorithm)
+    ));
+
+    let only = if cli.only.is_empty() { &config.analyzers.enable } else { &cli.only };
+    let skip: Vec<String> = cli.skip.iter().chain(&config.analyzers.disable).cloned().collect();
+    registry.select(only, &skip)?.with_weights(&config.weights)
+}
+
+fn list_analyzers(registry: &AnalyzerRegistry) {
+    println!("{:<20} {:<20} {:>6}  {}", "ID".bold(), "NAME".bold(), "WEIGHT".bold(), "DESCRIPTION".bold());
+    for analyzer in registry.analyzers() {
+        println!(
+            "{:<20} {:<20} {:>5.1}%  {}",
+            analyzer.id(), analyzer.name(), analyzer.weight() * 100.0, analyzer.description()
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    /// 1 for `#`, 6 for `######`
+    pub level: u8,
+    pub text: String,
+    /// 1-based source line
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    pub destination: String,
+    /// Link text, or alt text for images
+    pub text: String,
+    pub line: usize,
+}
+
+/// The structural elements of a Markdown document.
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    pub headings: Vec<Heading>,
+    /// Info-string language of each code block (empty for indented or bare fences)
+    pub code_blocks: Vec<String>,
+    pub links: Vec<Link>,
+    pub images: Vec<Link>,
+}
+
+impl Document {
+    pub fn parse(text: &str) -> Self {
+        let mut document = Document::default();
+        let mut heading: Option<(u8, String, usize)> = None;
+        // Links and images whose text is still being collected, innermost last
+        let mut open: Vec<(bool, Link)> = Vec::new();
+        let mut lines = LineCounter::new(text);
+
+        for (event, range) in Parser::new(text).into_offset_iter() {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some((heading_level(level), String::new(), lines.line_of(range.start)));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, text, line)) = heading.take() {
+                        document.headings.push(Heading { level, text: text.trim().to_string(), line });
+                    }
+                }
+                Event::Text(t) | Event::Code(t) => {
+                    if let Some((_, text, _)) = heading.as_mut() {
+                        text.push_str(&t);
+                    }
+                    for (_, link) in open.iter_mut() {
+                        link.text.push_str(&t);
+                    }
+                }
+                Event::Start(Tag::CodeBlock(kind)) => {
+                    document.code_blocks.push(match kind {
+                        CodeBlockKind::Fenced(info) => info.split([',', ' ']).next().unwrap_or("").to_string(),
+                        CodeBlockKind::Indented => String::new(),
+                    });
+                }
+                Event::Start(tag @ (Tag::Link { .. } | Tag::Image { .. })) => {
+                    let (is_image, dest_url) = match tag {
+                        Tag::Image { dest_url, .. } => (true, dest_url),
+                        Tag::Link { dest_url, .. } => (false, dest_url),
+                        _ => unreachable!(),
+                    };
+                    // Badges are images nested in links; the outer link keeps collecting text
+                    let line = lines.line_of(range.start);
+                    open.push((is_image, Link { destination: dest_url.to_string(), text: String::new(), line }));
+                }
+                Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+                    match open.pop() {
+                        Some((true, image)) => document.images.push(image),
+                        Some((false, found)) => document.links.push(found),
+                        None => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        document
+    }
+
+    /// GitHub-style anchors for each heading, with `-1`, `-2` suffixes for duplicates.
+    pub fn anchors(&self) -> Vec<String> {
+        let mut anchors: Vec<String> = Vec::new();
+
+        for heading in &self.headings {
+            let base = slug(&heading.text);
+            let mut anchor = base.clone();
+            let mut n = 0;
+            while anchors.contains(&anchor) {
+                n += 1;
+                anchor = format!("{}-{}", base, n);
+            }
+            anchors.push(anchor);
+        }
+
+        anchors
+    }
+
+    /// Heading structure problems: several top-level headings and skipped levels.
+    pub fn hierarchy_problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let top_level = self.headings.iter().filter(|h| h.level == 1).count();
+        if top_level > 1 {
+            problems.push(format!("{} top-level (`#`) headings; expected one title", top_level));
+        }
+
+        let mut previous = 0;
+        for heading in &self.headings {
+            if previous > 0 && heading.level > previous + 1 {
+                problems.push(format!(
+                    "Heading \"{}\" (line {}) jumps from level {} to level {}",
+                    heading.text, heading.line, previous, heading.level
+                ));
+            }
+            previous = heading.level;
+        }
+
+        problems
+    }
+}
+
+/// Maps byte offsets to 1-based lines, counting forward from the previous offset.
+struct LineCounter<'a> {
+    text: &'a str,
+    offset: usize,
+    line: usize,
+}
+
+impl<'a> LineCounter<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, offset: 0, line: 1 }
+    }
+
+    fn line_of(&mut self, offset: usize) -> usize {
+        // Events arrive in document order, so going back is rare; restart when it happens
+        if offset < self.offset {
+            self.offset = 0;
+            self.line = 1;
+        }
+        self.line += self.text[self.offset..offset].matches('\n').count();
+        self.offset = offset;
+        self.line
+    }
+}
+
+/// Anchor GitHub generates for a heading: lowercase, punctuation dropped, spaces as hyphens.
+pub fn slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document() {
+        let text = "# Title\n\nSee [docs](docs/guide.md).\n\n## Install `app`\n\n```bash\ncargo install app\n```\n\n![logo](logo.png)\n\n    indented\n";
+        let document = Document::parse(text);
+
+        assert_eq!(document.headings.len(), 2);
+        assert_eq!(document.headings[1], Heading { level: 2, text: "Install app".to_string(), line: 5 });
+        assert_eq!(document.code_blocks, vec!["bash".to_string(), String::new()]);
+        assert_eq!(document.links, vec![Link { destination: "docs/guide.md".to_string(), text: "docs".to_string(), line: 3 }]);
+        assert_eq!(document.images[0].destination, "logo.png");
+    }
+
+    #[test]
+    fn test_anchors() {
+        let document = Document::parse("# What's new?\n## FAQ\n## FAQ\n## `--token` option\n");
+        assert_eq!(document.anchors(), vec!["whats-new", "faq", "faq-1", "--token-option"]);
+
+        let badge = Document::parse("[![CI](https://ci.example/badge.svg)](https://ci.example)");
+        assert_eq!(badge.links[0].destination, "https://ci.example");
+        assert_eq!(badge.images[0].text, "CI");
+
+        let logo = Document::parse("Intro\n\n[![logo](logo.png) Acme docs](https://acme.example)\n");
+        assert_eq!(logo.links, vec![Link {
+            destination: "https://acme.example".to_string(),
+            text: "logo Acme docs".to_string(),
+            line: 3,
+        }]);
+        assert_eq!(logo.images[0].line, 3);
+    }
+
+    #[test]
+    fn test_hierarchy_problems() {
+        let document = Document::parse("# A\n\n#### B\n\n## C\n\n# D\n");
+        let problems = document.hierarchy_problems();
+
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("2 top-level"));
+        assert!(problems[1].contains("\"B\" (line 3) jumps from level 1 to level 4"));
+        assert!(Document::parse("# A\n## B\n### C\n## D\n").hierarchy_problems().is_empty());
+    }
+}
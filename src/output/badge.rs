@@ -1,10 +1,12 @@
-pub fn generate_badge_url(score: f64) -> String {
-    let (label, color) = match score {
-        s if s >= 90.0 => ("excellent", "brightgreen"),
-        s if s >= 80.0 => ("good", "green"),
-        s if s >= 70.0 => ("fair", "yellow"),
-        s if s >= 60.0 => ("needs--improvement", "orange"),
-        _ => ("poor", "red"),
+use crate::scoring::{Grade, GradeBoundaries};
+
+pub fn generate_badge_url(score: f64, grades: &GradeBoundaries) -> String {
+    let (label, color) = match grades.grade(score) {
+        Grade::Excellent => ("excellent", "brightgreen"),
+        Grade::Good => ("good", "green"),
+        Grade::Fair => ("fair", "yellow"),
+        Grade::NeedsImprovement => ("needs--improvement", "orange"),
+        Grade::Poor => ("poor", "red"),
     };
 
     format!(
@@ -20,15 +22,15 @@ mod tests {
 
     #[test]
     fn test_badge_url_generation() {
-        let url = generate_badge_url(95.0);
+        let url = generate_badge_url(95.0, &GradeBoundaries::default());
         assert!(url.contains("excellent"));
         assert!(url.contains("brightgreen"));
 
-        let url = generate_badge_url(85.0);
+        let url = generate_badge_url(85.0, &GradeBoundaries::default());
         assert!(url.contains("good"));
         assert!(url.contains("green"));
 
-        let url = generate_badge_url(50.0);
+        let url = generate_badge_url(50.0, &GradeBoundaries::default());
         assert!(url.contains("poor"));
         assert!(url.contains("red"));
     }
@@ -1,6 +1,7 @@
 use chrono::Utc;
-use crate::analyzers::{AnalysisResult, FindingStatus};
-use crate::scoring::ScoreCalculator;
+use crate::analyzers::{Assessment, CategoryResult, Finding, FindingStatus, ReportTable};
+use crate::github::{DataAvailability, RepoData};
+use crate::scoring::GradeBoundaries;
 
 pub struct MarkdownGenerator;
 
@@ -9,11 +10,13 @@ impl MarkdownGenerator {
         owner: &str,
         repo: &str,
         overall_score: f64,
-        results: &[(String, f64, AnalysisResult)],
+        results: &[CategoryResult],
+        repo_data: &RepoData,
     ) -> String {
+        let grades = &repo_data.config.grades;
         let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
-        let grade = ScoreCalculator::grade(overall_score);
-        let badge_url = crate::output::generate_badge_url(overall_score);
+        let grade = grades.grade(overall_score).label();
+        let badge_url = crate::output::generate_badge_url(overall_score, grades);
 
         let mut report = format!(
             r#"# Repository Health Report
@@ -37,26 +40,62 @@ This report provides an automated analysis of the repository's health across mul
         report.push_str("| Category | Score | Grade | Weight | Details |\n");
         report.push_str("|----------|-------|-------|--------|----------|\n");
 
-        for (name, weight, result) in results {
-            let grade = ScoreCalculator::grade_short(result.score);
+        for CategoryResult { name, weight, result, .. } in results {
+            let (score, grade, weight) = match result.assessment {
+                Assessment::Scored => (
+                    format!("{:.1}/100", result.score),
+                    grades.grade(result.score).short(),
+                    format!("{:.0}%", weight * 100.0),
+                ),
+                _ => (result.assessment.label().to_string(), "-", format!("{:.0}% (excluded)", weight * 100.0)),
+            };
             report.push_str(&format!(
-                "| {} | {:.1}/100 | {} | {:.0}% | {} |\n",
+                "| {} | {} | {} | {} | {} |\n",
                 name,
-                result.score,
+                score,
                 grade,
-                weight * 100.0,
+                weight,
                 Self::truncate(&result.details, 50)
             ));
         }
 
         report.push_str("\n---\n\n");
 
+        report.push_str(&Self::data_gaps(results, &repo_data.availability));
+        report.push_str(&Self::config_warnings(&repo_data.config_warnings));
+
+        // Language breakdown
+        let language_rows = repo_data.languages.rows();
+        if !language_rows.is_empty() {
+            report.push_str("## Languages\n\n");
+            report.push_str("| Language | Files | Bytes | Share |\n");
+            report.push_str("|----------|-------|-------|-------|\n");
+
+            for (name, files, bytes, share) in language_rows {
+                report.push_str(&format!(
+                    "| {} | {} | {} | {:.1}% |\n",
+                    name,
+                    files.map(|f| f.to_string()).unwrap_or_else(|| "-".to_string()),
+                    bytes,
+                    share
+                ));
+            }
+
+            report.push_str("\n---\n\n");
+        }
+
         // Detailed analysis
         report.push_str("## Detailed Analysis\n\n");
 
-        for (name, _, result) in results {
+        for CategoryResult { name, result, .. } in results {
             report.push_str(&format!("### {}\n", name));
-            report.push_str(&format!("**Score**: {:.1}/100\n\n", result.score));
+            match result.assessment {
+                Assessment::Scored => report.push_str(&format!("**Score**: {:.1}/100\n\n", result.score)),
+                assessment => report.push_str(&format!(
+                    "**Score**: {} (excluded from the overall score)\n\n",
+                    assessment.label()
+                )),
+            }
             report.push_str(&format!("{}\n\n", result.details));
 
             if !result.findings.is_empty() {
@@ -66,18 +105,27 @@ This report provides an automated analysis of the repository's health across mul
                         FindingStatus::Positive => "✅",
                         FindingStatus::Warning => "⚠️",
                         FindingStatus::Missing => "❌",
+                        FindingStatus::Unknown => "❔",
+                        FindingStatus::NotApplicable => "➖",
                     };
-                    report.push_str(&format!("- {} {}\n", icon, finding.message));
+                    report.push_str(&format!("- {} {}\n", icon, Self::finding_text(finding)));
                 }
-                report.push_str("\n");
+                report.push('\n');
+                report.push_str(&Self::remediations(&result.findings));
+            }
+
+            for table in &result.tables {
+                report.push_str(&Self::render_table(table));
             }
         }
 
         report.push_str("---\n\n");
 
+        report.push_str(&Self::suppressed(results));
+
         // Recommendations
         report.push_str("## Recommendations\n\n");
-        report.push_str(&Self::generate_recommendations(results));
+        report.push_str(&Self::generate_recommendations(results, grades));
 
         report.push_str("\n---\n\n");
         report.push_str("*Generated by repo-health*\n");
@@ -85,23 +133,23 @@ This report provides an automated analysis of the repository's health across mul
         report
     }
 
-    fn generate_recommendations(results: &[(String, f64, AnalysisResult)]) -> String {
+    fn generate_recommendations(results: &[CategoryResult], grades: &GradeBoundaries) -> String {
         let mut recommendations = Vec::new();
 
-        for (name, _, result) in results {
-            if result.score < 60.0 {
+        for CategoryResult { id, name, result, .. } in results.iter().filter(|category| category.result.is_scored()) {
+            if result.score < grades.needs_improvement {
                 recommendations.push(format!(
                     "- **{}**: Score is low ({:.1}/100). {}",
                     name,
                     result.score,
-                    Self::get_recommendation_text(name)
+                    Self::get_recommendation_text(id)
                 ));
-            } else if result.score < 80.0 {
+            } else if result.score < grades.good {
                 recommendations.push(format!(
                     "- **{}**: Room for improvement ({:.1}/100). {}",
                     name,
                     result.score,
-                    Self::get_recommendation_text(name)
+                    Self::get_recommendation_text(id)
                 ));
             }
         }
@@ -113,17 +161,146 @@ This report provides an automated analysis of the repository's health across mul
         }
     }
 
-    fn get_recommendation_text(category: &str) -> &'static str {
-        match category {
-            "Documentation" => "Consider adding a comprehensive README, documentation folder, and contributing guidelines.",
-            "Tests" => "Add test files and set up automated testing in your CI pipeline.",
-            "CI/CD" => "Set up GitHub Actions or another CI/CD system to automate builds and tests.",
-            "Dependencies" => "Add a dependency management file (Cargo.toml, package.json, etc.) and keep dependencies updated.",
-            "Bus Factor" => "Encourage more contributors to participate in the project to distribute knowledge.",
+    fn get_recommendation_text(id: &str) -> &'static str {
+        match id {
+            "documentation" => "Consider adding a comprehensive README, a documentation folder, API docs and a license.",
+            "tests" => "Add test files and set up automated testing in your CI pipeline.",
+            "ci-cd" => "Set up GitHub Actions or another CI/CD system to automate builds and tests.",
+            "dependencies" => "Add a dependency management file (Cargo.toml, package.json, etc.) and keep dependencies updated.",
+            "bus-factor" => "Encourage more contributors to participate in the project to distribute knowledge.",
+            "workflow-security" => "Pin actions to commit SHAs, declare least-privilege `permissions:` and never interpolate untrusted event data into `run:` scripts.",
+            "community" => "Add a code of conduct, issue forms, a pull request template, CODEOWNERS and governance documentation.",
             _ => "Review the findings above for specific improvements.",
         }
     }
 
+    /// Data that could not be fetched and the categories left out of the score because of it.
+    fn data_gaps(results: &[CategoryResult], availability: &DataAvailability) -> String {
+        let unscored: Vec<_> = results.iter().filter(|category| !category.result.is_scored()).collect();
+        if availability.gaps().next().is_none() && unscored.is_empty() {
+            return String::new();
+        }
+
+        let mut out = String::from("## Data Gaps\n\n");
+        out.push_str("Checks that depend on data which could not be fetched are marked unknown rather than failed, ");
+        out.push_str("and categories that could not be scored are left out of the overall score.\n\n");
+
+        if availability.gaps().next().is_some() {
+            out.push_str("| Data | Reason |\n");
+            out.push_str("|------|--------|\n");
+            for (source, reason) in availability.gaps() {
+                out.push_str(&format!("| {} | {} |\n", source.name(), reason.replace('|', "\\|")));
+            }
+            out.push('\n');
+        }
+
+        for CategoryResult { name, result, .. } in unscored {
+            out.push_str(&format!("- **{}**: {} — {}\n", name, result.assessment.label(), result.details));
+        }
+
+        out.push_str("\n---\n\n");
+        out
+    }
+
+    /// The message, prefixed with its rule ID and followed by its location unless the message names it.
+    fn finding_text(finding: &Finding) -> String {
+        let mut text = match &finding.rule_id {
+            Some(id) => format!("`{}` {}", id, finding.message),
+            None => finding.message.clone(),
+        };
+        if let Some(location) = finding.location().filter(|l| !finding.message.contains(l.as_str())) {
+            text.push_str(&format!(" (`{}`)", location));
+        }
+        text
+    }
+
+    /// One remediation per failed rule, most severe first.
+    fn remediations(findings: &[Finding]) -> String {
+        let mut failed: Vec<&Finding> = findings.iter()
+            .filter(|f| f.status.is_failure() && f.rule_id.is_some() && f.remediation.is_some())
+            .collect();
+        failed.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+        let mut seen = std::collections::HashSet::new();
+        let mut out = String::new();
+        for finding in failed {
+            let (Some(id), Some(remediation)) = (&finding.rule_id, &finding.remediation) else { continue };
+            if !seen.insert(id) {
+                continue;
+            }
+            if out.is_empty() {
+                out.push_str("**How to fix**:\n");
+            }
+            out.push_str(&format!("- `{}` ({}): {}", id, finding.severity.name(), remediation));
+            if let Some(url) = &finding.docs_url {
+                out.push_str(&format!(" [Details]({})", url));
+            }
+            out.push('\n');
+        }
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Repository configuration and inline suppressions that were ignored.
+    fn config_warnings(warnings: &[Finding]) -> String {
+        if warnings.is_empty() {
+            return String::new();
+        }
+
+        let mut out = String::from("## Configuration Warnings\n\n");
+        for warning in warnings {
+            out.push_str(&format!("- ⚠️ {}\n", Self::finding_text(warning)));
+        }
+        out.push_str("\n---\n\n");
+        out
+    }
+
+    /// Findings accepted by suppressions, with who accepted them, why and until when.
+    fn suppressed(results: &[CategoryResult]) -> String {
+        if results.iter().all(|category| category.suppressed.is_empty()) {
+            return String::new();
+        }
+
+        let mut out = String::from("## Suppressed Findings\n\n");
+        out.push_str("These findings were accepted and their checks are left out of the score until the suppression expires.\n\n");
+        out.push_str("| Category | Rule | Finding | Reason | Expires | Declared in |\n");
+        out.push_str("|----------|------|---------|--------|---------|-------------|\n");
+        for CategoryResult { name, suppressed, .. } in results {
+            for entry in suppressed {
+                let cells = [
+                    name.clone(),
+                    format!("`{}`", entry.suppression.rule),
+                    entry.finding.message.clone(),
+                    entry.suppression.reason.clone(),
+                    entry.suppression.expires.map(|date| date.to_string()).unwrap_or_else(|| "never".to_string()),
+                    format!("`{}`", entry.suppression.source()),
+                ];
+                let cells: Vec<String> = cells.iter().map(|cell| cell.replace('|', "\\|")).collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+        }
+
+        out.push_str("\n---\n\n");
+        out
+    }
+
+    fn render_table(table: &ReportTable) -> String {
+        let mut out = format!("**{}**:\n\n", table.title);
+        out.push_str(&format!("| {} |\n", table.headers.join(" | ")));
+        out.push_str(&format!("|{}\n", "---|".repeat(table.headers.len())));
+
+        for row in &table.rows {
+            let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+
+        out.push('\n');
+        out
+    }
+
     fn truncate(s: &str, max_len: usize) -> String {
         if s.len() <= max_len {
             s.to_string()
@@ -132,3 +309,57 @@ This report provides an automated analysis of the repository's health across mul
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::{rules, AnalysisResult};
+
+    #[test]
+    fn test_findings_show_rule_location_and_remediation() {
+        let findings = vec![
+            Finding::warning("Broken link \"guide\" in README.md:3: file not found")
+                .with_rule(&rules::BROKEN_LINK)
+                .at("README.md", Some(3)),
+            Finding::warning("Another broken link").with_rule(&rules::BROKEN_LINK).at("docs/a.md", Some(7)),
+            Finding::missing("LICENSE file not found").with_rule(&rules::LICENSE),
+            Finding::positive("README.md exists").with_rule(&rules::README),
+        ];
+
+        assert_eq!(
+            MarkdownGenerator::finding_text(&findings[0]),
+            "`DOC005` Broken link \"guide\" in README.md:3: file not found"
+        );
+        assert_eq!(MarkdownGenerator::finding_text(&findings[1]), "`DOC005` Another broken link (`docs/a.md:7`)");
+
+        let fixes = MarkdownGenerator::remediations(&findings);
+        let lines: Vec<&str> = fixes.trim_end().lines().collect();
+        assert_eq!(lines[0], "**How to fix**:");
+        assert!(lines[1].starts_with("- `DOC011` (high): Add a LICENSE file"));
+        assert!(lines[1].ends_with("[Details](https://github.com/kasaiarashi/repo-health/blob/main/docs/rules.md#doc011)"));
+        assert!(lines[2].starts_with("- `DOC005` (medium):"));
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_recommendations_follow_grade_boundaries() {
+        let category = |score| CategoryResult {
+            id: "tests".to_string(),
+            name: "Tests".to_string(),
+            weight: 1.0,
+            result: AnalysisResult {
+                score,
+                details: String::new(),
+                findings: Vec::new(),
+                tables: Vec::new(),
+                assessment: Assessment::Scored,
+            },
+            suppressed: Vec::new(),
+        };
+        let strict = GradeBoundaries { excellent: 98.0, good: 95.0, fair: 90.0, needs_improvement: 85.0 };
+
+        assert!(MarkdownGenerator::generate_recommendations(&[category(82.0)], &GradeBoundaries::default()).contains("excellent health"));
+        assert!(MarkdownGenerator::generate_recommendations(&[category(82.0)], &strict).contains("Score is low (82.0/100)"));
+        assert!(MarkdownGenerator::generate_recommendations(&[category(92.0)], &strict).contains("Room for improvement"));
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::github::TreeEntry;
+
+/// Test harness attributes that mark a function as a test.
+const TEST_ATTRIBUTES: &[&str] = &[
+    "test", "tokio::test", "async_std::test", "rstest", "test_case", "wasm_bindgen_test",
+];
+
+/// Doc-test fence attributes that still produce a Rust test.
+const RUST_FENCE_ATTRIBUTES: &[&str] = &[
+    "rust", "no_run", "should_panic", "compile_fail", "test_harness", "allow_fail",
+];
+
+/// A crate in the repository, identified by its `Cargo.toml`.
+#[derive(Debug, Clone)]
+pub struct RustCrate {
+    pub name: String,
+    /// Directory containing the manifest, empty for the repository root.
+    pub root: String,
+}
+
+/// Test counts for one crate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateTests {
+    /// `#[test]`-style functions in `src/`
+    pub unit_tests: usize,
+    /// Source files containing unit tests
+    pub files_with_unit_tests: usize,
+    /// Rust code blocks in `///` and `//!` comments
+    pub doc_tests: usize,
+    /// Files under `tests/`
+    pub integration_files: usize,
+    /// `#[test]`-style functions under `tests/`
+    pub integration_tests: usize,
+    /// Files under `benches/`
+    pub bench_files: usize,
+}
+
+/// Find crates from the manifests in the tree, naming them from fetched contents.
+pub fn discover_crates(tree: &[TreeEntry], files: &HashMap<String, String>) -> Vec<RustCrate> {
+    tree.iter()
+        .filter(|e| e.entry_type == "blob" && is_manifest(&e.path))
+        .filter_map(|e| {
+            let root = e.path.trim_end_matches("Cargo.toml").trim_end_matches('/').to_string();
+            let manifest: Option<toml::Value> = files.get(&e.path).and_then(|c| toml::from_str(c).ok());
+            let name = match manifest {
+                // Virtual workspace manifests have no package of their own
+                Some(m) if m.get("package").is_none() => return None,
+                Some(m) => m.get("package")?.get("name")?.as_str()?.to_string(),
+                None if root.is_empty() => "(root)".to_string(),
+                None => root.rsplit('/').next().unwrap_or(&root).to_string(),
+            };
+            Some(RustCrate { name, root })
+        })
+        .collect()
+}
+
+pub fn is_manifest(path: &str) -> bool {
+    path == "Cargo.toml" || path.ends_with("/Cargo.toml")
+}
+
+/// The crate owning a path: the one with the longest matching root.
+pub fn crate_of<'a>(crates: &'a [RustCrate], path: &str) -> Option<&'a RustCrate> {
+    crates.iter()
+        .filter(|c| c.root.is_empty() || path.starts_with(&format!("{}/", c.root)))
+        .max_by_key(|c| c.root.len())
+}
+
+/// Path of a file relative to its crate root.
+pub(crate) fn crate_relative<'a>(krate: &RustCrate, path: &'a str) -> &'a str {
+    if krate.root.is_empty() { path } else { &path[krate.root.len() + 1..] }
+}
+
+/// Count unit tests, doc tests, integration tests and benches per crate.
+pub fn count_tests(crates: &[RustCrate], tree: &[TreeEntry], files: &HashMap<String, String>) -> BTreeMap<String, CrateTests> {
+    let mut counts: BTreeMap<String, CrateTests> = crates.iter()
+        .map(|c| (c.name.clone(), CrateTests::default()))
+        .collect();
+
+    for entry in tree.iter().filter(|e| e.entry_type == "blob" && e.path.ends_with(".rs")) {
+        let Some(krate) = crate_of(crates, &entry.path) else { continue };
+        let relative = crate_relative(krate, &entry.path);
+        let stats = counts.entry(krate.name.clone()).or_default();
+        let source = files.get(&entry.path).map(String::as_str);
+
+        if relative.starts_with("benches/") {
+            stats.bench_files += 1;
+        } else if relative.starts_with("tests/") {
+            stats.integration_files += 1;
+            stats.integration_tests += source.map(count_test_functions).unwrap_or(0);
+        } else if let Some(source) = source {
+            let unit_tests = count_test_functions(source);
+            stats.unit_tests += unit_tests;
+            stats.files_with_unit_tests += usize::from(unit_tests > 0);
+            stats.doc_tests += count_doc_tests(source);
+        }
+    }
+
+    counts
+}
+
+/// Fetched non-test Rust sources that contain unit tests.
+pub fn inline_test_files(files: &HashMap<String, String>) -> Vec<&str> {
+    let mut paths: Vec<&str> = files.iter()
+        .filter(|(path, _)| path.ends_with(".rs") && !path.starts_with("benches/") && !path.contains("/benches/"))
+        .filter(|(path, _)| !crate::languages::Language::Rust.is_test_file(path))
+        .filter(|(_, source)| count_test_functions(source) > 0)
+        .map(|(path, _)| path.as_str())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Number of functions carrying a test harness attribute.
+///
+/// Sources that don't parse fall back to counting attribute lines, which also
+/// sees attributes inside strings.
+pub fn count_test_functions(source: &str) -> usize {
+    match syn::parse_file(source) {
+        Ok(file) => count_test_items(&file.items),
+        Err(_) => source.lines()
+            .filter_map(|line| line.trim_start().strip_prefix("#["))
+            .filter(|attr| {
+                let path = attr.split(|c: char| c == ']' || c == '(' || c.is_whitespace()).next().unwrap_or_default();
+                TEST_ATTRIBUTES.contains(&path)
+            })
+            .count(),
+    }
+}
+
+fn count_test_items(items: &[syn::Item]) -> usize {
+    items.iter()
+        .map(|item| match item {
+            syn::Item::Fn(f) if f.attrs.iter().any(is_test_attribute) => 1,
+            syn::Item::Mod(m) => m.content.as_ref().map_or(0, |(_, items)| count_test_items(items)),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn is_test_attribute(attr: &syn::Attribute) -> bool {
+    let path: Vec<String> = attr.path().segments.iter().map(|s| s.ident.to_string()).collect();
+    TEST_ATTRIBUTES.contains(&path.join("::").as_str())
+}
+
+/// Number of Rust code blocks in `///` and `//!` doc comments.
+pub fn count_doc_tests(source: &str) -> usize {
+    let mut count = 0;
+    let mut in_block = false;
+
+    for line in source.lines().map(str::trim_start) {
+        let Some(doc) = line.strip_prefix("///").or_else(|| line.strip_prefix("//!")) else {
+            in_block = false;
+            continue;
+        };
+
+        let doc = doc.trim();
+        let Some(info) = doc.strip_prefix("```") else { continue };
+
+        if in_block {
+            in_block = false;
+        } else {
+            in_block = true;
+            if is_rust_fence(info) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Whether a fence info string marks a block rustdoc will compile and run.
+fn is_rust_fence(info: &str) -> bool {
+    info.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|attr| !attr.is_empty())
+        .all(|attr| RUST_FENCE_ATTRIBUTES.contains(&attr) || attr.starts_with("edition"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_test_functions() {
+        let source = r#"
+pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn adds() {}
+
+    #[tokio::test]
+    async fn adds_async() {}
+
+    // #[test] commented out
+
+    #[rstest]
+    #[case(1)]
+    fn parametrised(#[case] n: i32) {}
+
+    const FIXTURE: &str = "
+    #[test]
+    fn not_a_test() {}
+    ";
+}
+"#;
+        assert_eq!(count_test_functions(source), 3);
+
+        // Sources syn can't parse are scanned line by line
+        assert_eq!(count_test_functions("#[test]
+fn a() {
+#[tokio::test(flavor = \"multi_thread\")]
+async fn b() {}
+#[testing]
+"), 2);
+    }
+
+    #[test]
+    fn test_count_doc_tests() {
+        let source = r#"
+/// Adds numbers.
+///
+/// ```
+/// assert_eq!(add(1, 2), 3);
+/// ```
+///
+/// ```text
+/// not a test
+/// ```
+///
+/// ```no_run,edition2021
+/// start_server();
+/// ```
+///
+/// ```ignore
+/// skipped();
+/// ```
+pub fn add() {}
+"#;
+        assert_eq!(count_doc_tests(source), 2);
+    }
+
+    #[test]
+    fn test_count_tests_per_crate() {
+        let tree = vec![
+            TreeEntry::blob("Cargo.toml"),
+            TreeEntry::blob("src/lib.rs"),
+            TreeEntry::blob("tests/api.rs"),
+            TreeEntry::blob("benches/speed.rs"),
+            TreeEntry::blob("crates/cli/Cargo.toml"),
+            TreeEntry::blob("crates/cli/src/main.rs"),
+        ];
+        let files: HashMap<String, String> = [
+            ("Cargo.toml", "[package]\nname = \"core\"\n"),
+            ("crates/cli/Cargo.toml", "[package]\nname = \"core-cli\"\n"),
+            ("src/lib.rs", "#[cfg(test)]\nmod tests {\n    #[test]\n    fn a() {}\n}\n"),
+            ("tests/api.rs", "#[test]\nfn b() {}\n#[test]\nfn c() {}\n"),
+            ("crates/cli/src/main.rs", "//! ```\n//! run();\n//! ```\nfn main() {}\n"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let crates = discover_crates(&tree, &files);
+        assert_eq!(crate_of(&crates, "crates/cli/src/main.rs").map(|c| c.name.as_str()), Some("core-cli"));
+
+        let counts = count_tests(&crates, &tree, &files);
+        assert_eq!(counts["core"].unit_tests, 1);
+        assert_eq!(counts["core"].integration_tests, 2);
+        assert_eq!(counts["core"].bench_files, 1);
+        assert_eq!(counts["core-cli"].doc_tests, 1);
+    }
+}
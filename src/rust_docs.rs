@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashMap};
+use syn::{Attribute, ImplItem, Item, Visibility};
+use crate::rust_crates::{self, RustCrate};
+
+/// Public API documentation counts for one crate.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DocCoverage {
+    /// `pub` functions, methods, structs, enums and traits in `src/`, excluding `#[doc(hidden)]`.
+    /// Items are counted whether or not their module is reachable from the crate root.
+    pub public_items: usize,
+    pub documented_items: usize,
+    /// The crate root carries `//!` documentation
+    pub crate_docs: bool,
+    /// `missing_docs` is denied in the crate root or the manifest's `[lints]`
+    pub deny_missing_docs: bool,
+    /// Sources that could not be parsed
+    pub unparsed_files: usize,
+}
+
+impl DocCoverage {
+    pub fn percent(&self) -> Option<f64> {
+        if self.public_items == 0 {
+            None
+        } else {
+            Some(self.documented_items as f64 / self.public_items as f64 * 100.0)
+        }
+    }
+}
+
+/// Measure documentation of public items per crate from the fetched sources.
+pub fn doc_coverage(crates: &[RustCrate], files: &HashMap<String, String>) -> BTreeMap<String, DocCoverage> {
+    let mut coverage: BTreeMap<String, DocCoverage> = crates.iter()
+        .map(|c| (c.name.clone(), DocCoverage::default()))
+        .collect();
+
+    for krate in crates {
+        let manifest_path = if krate.root.is_empty() { "Cargo.toml".to_string() } else { format!("{}/Cargo.toml", krate.root) };
+        if files.get(&manifest_path).is_some_and(|m| manifest_denies_missing_docs(m)) {
+            coverage.entry(krate.name.clone()).or_default().deny_missing_docs = true;
+        }
+    }
+
+    let mut paths: Vec<&String> = files.keys().filter(|p| p.ends_with(".rs")).collect();
+    paths.sort();
+
+    for path in paths {
+        let Some(krate) = rust_crates::crate_of(crates, path) else { continue };
+        let relative = rust_crates::crate_relative(krate, path);
+        if !relative.starts_with("src/") {
+            continue;
+        }
+        let stats = coverage.entry(krate.name.clone()).or_default();
+
+        let Ok(file) = syn::parse_file(&files[path]) else {
+            stats.unparsed_files += 1;
+            continue;
+        };
+
+        let lib_root = if krate.root.is_empty() { "src/lib.rs".to_string() } else { format!("{}/src/lib.rs", krate.root) };
+        let is_root = relative == "src/lib.rs" || (relative == "src/main.rs" && !files.contains_key(&lib_root));
+        if is_root {
+            stats.crate_docs |= file.attrs.iter().any(|a| a.path().is_ident("doc"));
+            stats.deny_missing_docs |= file.attrs.iter().any(denies_missing_docs);
+        }
+
+        count_items(&file.items, stats);
+    }
+
+    coverage
+}
+
+fn count_items(items: &[Item], stats: &mut DocCoverage) {
+    for item in items {
+        match item {
+            Item::Fn(f) => record(&f.vis, &f.attrs, stats),
+            Item::Struct(s) => record(&s.vis, &s.attrs, stats),
+            Item::Enum(e) => record(&e.vis, &e.attrs, stats),
+            Item::Trait(t) => record(&t.vis, &t.attrs, stats),
+            Item::Mod(m) => {
+                if let Some((_, items)) = &m.content {
+                    count_items(items, stats);
+                }
+            }
+            // Trait impls inherit the trait's documentation
+            Item::Impl(i) if i.trait_.is_none() => {
+                for item in &i.items {
+                    if let ImplItem::Fn(f) = item {
+                        record(&f.vis, &f.attrs, stats);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn record(vis: &Visibility, attrs: &[Attribute], stats: &mut DocCoverage) {
+    if !matches!(vis, Visibility::Public(_)) || attrs.iter().any(is_doc_hidden) {
+        return;
+    }
+
+    stats.public_items += 1;
+    if attrs.iter().any(|a| a.path().is_ident("doc")) {
+        stats.documented_items += 1;
+    }
+}
+
+fn is_doc_hidden(attr: &Attribute) -> bool {
+    attr.path().is_ident("doc") && has_nested(attr, "hidden")
+}
+
+fn denies_missing_docs(attr: &Attribute) -> bool {
+    (attr.path().is_ident("deny") || attr.path().is_ident("forbid")) && has_nested(attr, "missing_docs")
+}
+
+fn has_nested(attr: &Attribute, name: &str) -> bool {
+    let mut found = false;
+    if matches!(attr.meta, syn::Meta::List(_)) {
+        let _ = attr.parse_nested_meta(|meta| {
+            found |= meta.path.is_ident(name);
+            Ok(())
+        });
+    }
+    found
+}
+
+/// `[lints.rust] missing_docs = "deny"` (or `{ level = "forbid" }`).
+fn manifest_denies_missing_docs(manifest: &str) -> bool {
+    let Ok(manifest) = toml::from_str::<toml::Value>(manifest) else { return false };
+    let level = manifest.get("lints")
+        .and_then(|l| l.get("rust"))
+        .and_then(|r| r.get("missing_docs"));
+
+    let level = match level {
+        Some(toml::Value::String(level)) => Some(level.as_str()),
+        Some(toml::Value::Table(table)) => table.get("level").and_then(toml::Value::as_str),
+        _ => None,
+    };
+    matches!(level, Some("deny" | "forbid"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_coverage() {
+        let crates = vec![RustCrate { name: "app".to_string(), root: String::new() }];
+        let mut files = HashMap::new();
+        files.insert("src/lib.rs".to_string(), r#"
+//! The app crate.
+#![deny(missing_docs)]
+
+/// Documented.
+pub fn documented() {}
+pub fn undocumented() {}
+fn private() {}
+pub(crate) struct Internal;
+
+/// A widget.
+pub struct Widget;
+
+impl Widget {
+    /// Make one.
+    pub fn new() -> Self { Widget }
+    pub fn size(&self) -> usize { 0 }
+}
+
+impl Default for Widget {
+    fn default() -> Self { Widget }
+}
+
+#[doc(hidden)]
+pub fn hidden() {}
+
+pub mod nested {
+    /// Nested trait.
+    pub trait Shape {}
+    pub enum Kind { A }
+    pub trait Undocumented {}
+}
+"#.to_string());
+        files.insert("src/broken.rs".to_string(), "pub fn (".to_string());
+        files.insert("tests/it.rs".to_string(), "pub fn helper() {}".to_string());
+
+        let coverage = &doc_coverage(&crates, &files)["app"];
+        assert_eq!(coverage.public_items, 8);
+        assert_eq!(coverage.documented_items, 4);
+        assert!(coverage.crate_docs);
+        assert!(coverage.deny_missing_docs);
+        assert_eq!(coverage.unparsed_files, 1);
+        assert_eq!(coverage.percent(), Some(50.0));
+    }
+
+    #[test]
+    fn test_manifest_lints() {
+        assert!(manifest_denies_missing_docs("[package]\nname = \"a\"\n[lints.rust]\nmissing_docs = \"deny\"\n"));
+        assert!(manifest_denies_missing_docs("[lints.rust]\nmissing_docs = { level = \"forbid\", priority = 1 }\n"));
+        assert!(!manifest_denies_missing_docs("[lints.rust]\nmissing_docs = \"warn\"\n"));
+    }
+}
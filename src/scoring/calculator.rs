@@ -1,74 +1,105 @@
-use crate::analyzers::AnalysisResult;
+use crate::analyzers::CategoryResult;
+use super::GradeBoundaries;
 
 pub struct ScoreCalculator;
 
 impl ScoreCalculator {
-    pub fn calculate_overall(results: &[(String, f64, AnalysisResult)]) -> f64 {
-        let weighted_sum: f64 = results.iter()
-            .map(|(_, weight, result)| result.score * weight)
+    /// Weighted average of the scored categories; unknown and not applicable
+    /// categories are left out and the remaining weights renormalised.
+    pub fn calculate_overall(results: &[CategoryResult]) -> f64 {
+        let scored = || results.iter().filter(|category| category.result.is_scored());
+        let total_weight: f64 = scored().map(|category| category.weight).sum();
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+
+        let weighted_sum: f64 = scored()
+            .map(|category| category.result.score * category.weight)
             .sum();
 
-        weighted_sum.min(100.0).max(0.0)
+        (weighted_sum / total_weight).clamp(0.0, 100.0)
     }
 
+    /// Grade label using the default boundaries.
     pub fn grade(score: f64) -> &'static str {
-        match score {
-            s if s >= 90.0 => "A+ Excellent",
-            s if s >= 80.0 => "A Good",
-            s if s >= 70.0 => "B Fair",
-            s if s >= 60.0 => "C Needs Improvement",
-            _ => "D Poor"
-        }
+        GradeBoundaries::default().grade(score).label()
     }
 
+    /// Short grade using the default boundaries.
     pub fn grade_short(score: f64) -> &'static str {
-        match score {
-            s if s >= 90.0 => "A+",
-            s if s >= 80.0 => "A",
-            s if s >= 70.0 => "B",
-            s if s >= 60.0 => "C",
-            _ => "D"
-        }
+        GradeBoundaries::default().grade(score).short()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analyzers::{AnalysisResult, Finding};
+    use crate::analyzers::{AnalysisResult, Assessment};
+
+    fn category(name: &str, weight: f64, result: AnalysisResult) -> CategoryResult {
+        CategoryResult { id: name.to_lowercase(), name: name.to_string(), weight, result, suppressed: Vec::new() }
+    }
 
     #[test]
     fn test_calculate_overall() {
         let results = vec![
-            ("Doc".to_string(), 0.20, AnalysisResult {
+            category("Doc", 0.20, AnalysisResult {
                 score: 80.0,
                 details: "test".to_string(),
                 findings: vec![],
+                tables: vec![],
+                assessment: Assessment::Scored,
             }),
-            ("Tests".to_string(), 0.25, AnalysisResult {
+            category("Tests", 0.25, AnalysisResult {
                 score: 90.0,
                 details: "test".to_string(),
                 findings: vec![],
+                tables: vec![],
+                assessment: Assessment::Scored,
             }),
-            ("CI".to_string(), 0.20, AnalysisResult {
+            category("CI", 0.20, AnalysisResult {
                 score: 100.0,
                 details: "test".to_string(),
                 findings: vec![],
+                tables: vec![],
+                assessment: Assessment::Scored,
             }),
-            ("Deps".to_string(), 0.20, AnalysisResult {
+            category("Deps", 0.20, AnalysisResult {
                 score: 70.0,
                 details: "test".to_string(),
                 findings: vec![],
+                tables: vec![],
+                assessment: Assessment::Scored,
             }),
-            ("Bus".to_string(), 0.15, AnalysisResult {
+            category("Bus", 0.15, AnalysisResult {
                 score: 100.0,
                 details: "test".to_string(),
                 findings: vec![],
+                tables: vec![],
+                assessment: Assessment::Scored,
             }),
         ];
 
         let overall = ScoreCalculator::calculate_overall(&results);
-        assert!(overall >= 85.0 && overall <= 90.0);
+        assert!((85.0..=90.0).contains(&overall));
+    }
+
+    #[test]
+    fn test_unscored_categories_are_renormalised() {
+        let results = vec![
+            category("Doc", 0.50, AnalysisResult {
+                score: 80.0,
+                details: "test".to_string(),
+                findings: vec![],
+                tables: vec![],
+                assessment: Assessment::Scored,
+            }),
+            category("Bus", 0.25, AnalysisResult::unknown("no contributor data", vec![])),
+            category("Deps", 0.25, AnalysisResult::not_applicable("no dependencies", vec![])),
+        ];
+
+        assert!((ScoreCalculator::calculate_overall(&results) - 80.0).abs() < 1e-9);
+        assert_eq!(ScoreCalculator::calculate_overall(&results[1..]), 0.0);
     }
 
     #[test]
@@ -78,5 +109,11 @@ mod tests {
         assert_eq!(ScoreCalculator::grade(75.0), "B Fair");
         assert_eq!(ScoreCalculator::grade(65.0), "C Needs Improvement");
         assert_eq!(ScoreCalculator::grade(50.0), "D Poor");
+
+        let strict = GradeBoundaries { excellent: 95.0, good: 90.0, fair: 80.0, needs_improvement: 70.0 };
+        assert_eq!(strict.grade(92.0).short(), "A");
+        assert_eq!(strict.grade(65.0).label(), "D Poor");
+        assert!(strict.is_valid());
+        assert!(!GradeBoundaries { good: 95.0, ..strict }.is_valid());
     }
 }
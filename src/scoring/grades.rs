@@ -0,0 +1,67 @@
+use serde::Deserialize;
+
+/// Letter grade for a score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+    Excellent,
+    Good,
+    Fair,
+    NeedsImprovement,
+    Poor,
+}
+
+impl Grade {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Grade::Excellent => "A+ Excellent",
+            Grade::Good => "A Good",
+            Grade::Fair => "B Fair",
+            Grade::NeedsImprovement => "C Needs Improvement",
+            Grade::Poor => "D Poor",
+        }
+    }
+
+    pub fn short(&self) -> &'static str {
+        match self {
+            Grade::Excellent => "A+",
+            Grade::Good => "A",
+            Grade::Fair => "B",
+            Grade::NeedsImprovement => "C",
+            Grade::Poor => "D",
+        }
+    }
+}
+
+/// Minimum score for each grade; anything below `needs_improvement` is poor.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GradeBoundaries {
+    pub excellent: f64,
+    pub good: f64,
+    pub fair: f64,
+    pub needs_improvement: f64,
+}
+
+impl Default for GradeBoundaries {
+    fn default() -> Self {
+        Self { excellent: 90.0, good: 80.0, fair: 70.0, needs_improvement: 60.0 }
+    }
+}
+
+impl GradeBoundaries {
+    pub fn grade(&self, score: f64) -> Grade {
+        match score {
+            s if s >= self.excellent => Grade::Excellent,
+            s if s >= self.good => Grade::Good,
+            s if s >= self.fair => Grade::Fair,
+            s if s >= self.needs_improvement => Grade::NeedsImprovement,
+            _ => Grade::Poor,
+        }
+    }
+
+    /// Boundaries must lie within 0-100 and decrease from excellent to needs improvement.
+    pub fn is_valid(&self) -> bool {
+        let bounds = [self.excellent, self.good, self.fair, self.needs_improvement];
+        bounds.iter().all(|b| (0.0..=100.0).contains(b)) && bounds.windows(2).all(|pair| pair[0] > pair[1])
+    }
+}
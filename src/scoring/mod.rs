@@ -1,3 +1,5 @@
 mod calculator;
+mod grades;
 
 pub use calculator::ScoreCalculator;
+pub use grades::{Grade, GradeBoundaries};
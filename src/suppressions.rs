@@ -0,0 +1,329 @@
+//! Accepted findings: suppressions from `.repo-health.toml` and inline
+//! `repo-health: ignore` comments.
+//!
+//! A suppression matches failed findings by rule ID, optionally limited to paths
+//! matching a glob. Suppressed findings are reported separately and the checks
+//! they belong to are left out of their category's score until the suppression
+//! expires; after that the findings count again and a warning says so.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+use crate::analyzers::{rules, Assessment, CategoryResult, Finding};
+use crate::{Result, RepoHealthError};
+
+/// Marker for an inline suppression, e.g. `<!-- repo-health: ignore DOC005 -- intranet link -->`
+pub const INLINE_DIRECTIVE: &str = "repo-health: ignore";
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suppression {
+    /// Rule ID, e.g. `COM002`
+    pub rule: String,
+    /// Glob the finding's path must match; findings without a path never match
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Why the finding is accepted
+    pub reason: String,
+    /// Last day the suppression applies
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub expires: Option<NaiveDate>,
+    /// File and line of an inline suppression; it covers that line and the one below,
+    /// or the whole file for findings without a line, such as workflow checks
+    #[serde(skip)]
+    pub origin: Option<(String, usize)>,
+}
+
+/// A finding taken out of its category by a suppression.
+#[derive(Debug, Clone)]
+pub struct SuppressedFinding {
+    pub finding: Finding,
+    pub suppression: Suppression,
+}
+
+impl Suppression {
+    pub fn matches(&self, finding: &Finding) -> bool {
+        if finding.rule_id.as_deref() != Some(self.rule.as_str()) {
+            return false;
+        }
+        if let Some(pattern) = &self.path {
+            let Some(path) = &finding.path else { return false };
+            if !crate::glob::matches(pattern, path) {
+                return false;
+            }
+        }
+        match (&self.origin, finding.line) {
+            (Some((_, line)), Some(finding_line)) => finding_line == *line || finding_line == line + 1,
+            // Findings about a whole file can't be pinned to a line; the file's path already matched
+            (Some(_), None) => true,
+            (None, _) => true,
+        }
+    }
+
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| expires < today)
+    }
+
+    /// Where the suppression was declared.
+    pub fn source(&self) -> String {
+        match &self.origin {
+            Some((path, line)) => format!("{}:{}", path, line),
+            None => crate::config::CONFIG_FILE.to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| Err(RepoHealthError::InvalidConfig(message));
+        if !rules::ALL.iter().any(|rule| rule.id == self.rule) {
+            return invalid(format!("{}: unknown rule {} in suppression", self.source(), self.rule));
+        }
+        if self.reason.trim().is_empty() {
+            return invalid(format!("{}: suppression of {} needs a reason", self.source(), self.rule));
+        }
+        Ok(())
+    }
+}
+
+/// Inline suppressions in comments of the given files, scoped to the file they
+/// appear in, and a warning for each malformed one.
+///
+/// The directive follows `<!--`, `#` or `//` at the start of a line and lists rule
+/// IDs, optionally `until YYYY-MM-DD`, then `--` and the reason:
+/// `# repo-health: ignore WFS002 until 2026-12-31 -- vendored action`.
+pub fn find_inline(files: &[(&str, &str)]) -> (Vec<Suppression>, Vec<Finding>) {
+    let mut files = files.to_vec();
+    files.sort();
+
+    let mut suppressions = Vec::new();
+    let mut warnings = Vec::new();
+    for (path, content) in files {
+        for (index, line) in content.lines().enumerate() {
+            let Some(directive) = comment_directive(line) else { continue };
+            match parse_directive(path, index + 1, directive) {
+                Ok(parsed) => suppressions.extend(parsed),
+                Err(problem) => warnings.push(
+                    Finding::warning(format!("Ignored inline suppression: {}", problem)).at(path, Some(index + 1))
+                ),
+            }
+        }
+    }
+    (suppressions, warnings)
+}
+
+/// The text after `repo-health: ignore` when the line is a comment starting with it.
+fn comment_directive(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let comment = ["<!--", "#", "//"].iter().find_map(|marker| line.strip_prefix(marker))?;
+    comment.trim_start().strip_prefix(INLINE_DIRECTIVE)
+}
+
+fn parse_directive(path: &str, line: usize, directive: &str) -> std::result::Result<Vec<Suppression>, String> {
+    let directive = directive.trim_end();
+    let directive = directive.strip_suffix("-->").unwrap_or(directive);
+    let (spec, reason) = directive.split_once("--").ok_or("needs `-- <reason>`")?;
+
+    let mut rule_ids = Vec::new();
+    let mut expires = None;
+    let mut tokens = spec.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty());
+    while let Some(token) = tokens.next() {
+        if token == "until" {
+            let date = tokens.next().ok_or("`until` needs a date")?;
+            expires = Some(NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("invalid date {}, expected YYYY-MM-DD", date))?);
+        } else {
+            rule_ids.push(token.to_string());
+        }
+    }
+    if rule_ids.is_empty() {
+        return Err("names no rule".to_string());
+    }
+
+    rule_ids.into_iter()
+        .map(|rule| {
+            let suppression = Suppression {
+                rule,
+                path: Some(path.to_string()),
+                reason: reason.trim().to_string(),
+                expires,
+                origin: Some((path.to_string(), line)),
+            };
+            match suppression.validate() {
+                Ok(()) => Ok(suppression),
+                Err(RepoHealthError::InvalidConfig(message)) => Err(message),
+                Err(e) => Err(e.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Move the category's suppressed findings aside and score it without their checks.
+pub fn apply(category: &mut CategoryResult, suppressions: &[Suppression], today: NaiveDate) {
+    let mut kept = Vec::new();
+    let mut suppressed = Vec::new();
+    let mut expired: Vec<&Suppression> = Vec::new();
+
+    for finding in std::mem::take(&mut category.result.findings) {
+        let matching: Vec<&Suppression> = suppressions.iter()
+            .filter(|s| finding.status.is_failure() && s.matches(&finding))
+            .collect();
+        match matching.iter().find(|s| !s.is_expired(today)) {
+            Some(suppression) => {
+                let suppression = (*suppression).clone();
+                suppressed.push(SuppressedFinding { finding, suppression });
+            }
+            None => {
+                for suppression in matching {
+                    if !expired.contains(&suppression) {
+                        expired.push(suppression);
+                    }
+                }
+                kept.push(finding);
+            }
+        }
+    }
+
+    for suppression in expired {
+        let expires = suppression.expires.expect("only dated suppressions expire");
+        let message = format!(
+            "Suppression of {} in {} expired on {}: {}",
+            suppression.rule, suppression.source(), expires, suppression.reason
+        );
+        kept.push(Finding::warning(message));
+    }
+    category.result.findings = kept;
+
+    // Score the category as if the suppressed checks did not exist
+    let points: f64 = suppressed.iter().map(|s| s.finding.points).sum();
+    let max_points: f64 = suppressed.iter().map(|s| s.finding.max_points).sum();
+    category.suppressed.extend(suppressed);
+    if max_points <= 0.0 || !category.result.is_scored() {
+        return;
+    }
+    if max_points >= 100.0 {
+        category.result.assessment = Assessment::NotApplicable;
+        category.result.details = format!("All scored checks suppressed. {}", category.result.details);
+        return;
+    }
+    category.result.score = ((category.result.score - points) / (100.0 - max_points) * 100.0).clamp(0.0, 100.0);
+}
+
+/// Accepts a TOML date (`2026-12-31`) or a string in the same format.
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<NaiveDate>, D::Error> {
+    use serde::de::Error;
+
+    let text = match Option::<toml::Value>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(toml::Value::Datetime(date)) => date.to_string(),
+        Some(toml::Value::String(text)) => text,
+        Some(other) => return Err(D::Error::custom(format!("expected a date, got {}", other.type_str()))),
+    };
+    NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| D::Error::custom(format!("invalid date {}, expected YYYY-MM-DD", text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::AnalysisResult;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn suppression(rule: &str, path: Option<&str>, expires: Option<&str>) -> Suppression {
+        Suppression {
+            rule: rule.to_string(),
+            path: path.map(str::to_string),
+            reason: "accepted".to_string(),
+            expires: expires.map(date),
+            origin: None,
+        }
+    }
+
+    fn community(findings: Vec<Finding>, score: f64) -> CategoryResult {
+        CategoryResult {
+            id: "community".to_string(),
+            name: "Community".to_string(),
+            weight: 0.05,
+            result: AnalysisResult { score, details: String::new(), findings, tables: vec![], assessment: Assessment::Scored },
+            suppressed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_suppressed_checks_are_left_out_of_the_score() {
+        let mut category = community(vec![
+            Finding::missing("No support guide").with_rule(&rules::SUPPORT).scored(0.0, 10.0),
+            Finding::missing("No .github/FUNDING.yml").with_rule(&rules::FUNDING).scored(0.0, 5.0),
+            Finding::positive("Code owners defined").with_rule(&rules::CODEOWNERS),
+        ], 85.0);
+
+        let mut all = category.clone();
+
+        apply(&mut category, &[suppression("COM003", None, None)], date("2026-06-01"));
+        assert_eq!(category.suppressed.len(), 1);
+        assert_eq!(category.suppressed[0].finding.message, "No support guide");
+        assert_eq!(category.result.findings.len(), 2);
+        assert!((category.result.score - 85.0 / 90.0 * 100.0).abs() < 1e-9);
+
+        let both = [suppression("COM003", None, None), suppression("COM009", None, Some("2026-12-31"))];
+        apply(&mut all, &both, date("2026-06-01"));
+        assert_eq!(all.suppressed.len(), 2);
+        assert!((all.result.score - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expired_suppressions_become_warnings() {
+        let mut category = community(vec![
+            Finding::missing("No support guide").with_rule(&rules::SUPPORT).scored(0.0, 10.0),
+        ], 90.0);
+
+        apply(&mut category, &[suppression("COM003", None, Some("2026-01-31"))], date("2026-02-01"));
+
+        assert!(category.suppressed.is_empty());
+        assert_eq!(category.result.score, 90.0);
+        assert_eq!(
+            category.result.findings[1].message,
+            "Suppression of COM003 in .repo-health.toml expired on 2026-01-31: accepted"
+        );
+    }
+
+    #[test]
+    fn test_path_globs_and_inline_directives() {
+        let workflow = Finding::warning("unpinned").with_rule(&rules::UNPINNED_ACTION).at(".github/workflows/ci.yml", None);
+        assert!(suppression("WFS002", Some(".github/workflows/*.yml"), None).matches(&workflow));
+        assert!(!suppression("WFS002", Some("ci/**"), None).matches(&workflow));
+        assert!(!suppression("WFS002", Some("**"), None).matches(&Finding::warning("x").with_rule(&rules::UNPINNED_ACTION)));
+
+        let setup = "Intro\n<!-- repo-health: ignore DOC005 until 2027-01-01 -- intranet only -->\n[wiki](http://wiki)\n";
+        let (inline, warnings) = find_inline(&[("docs/setup.md", setup)]);
+        assert!(warnings.is_empty());
+        assert_eq!(inline.len(), 1);
+        assert_eq!(inline[0].reason, "intranet only");
+        assert_eq!(inline[0].expires, Some(date("2027-01-01")));
+        assert_eq!(inline[0].source(), "docs/setup.md:2");
+
+        let link = |line| Finding::warning("Broken link").with_rule(&rules::BROKEN_LINK).at("docs/setup.md", Some(line));
+        assert!(inline[0].matches(&link(3)));
+        assert!(!inline[0].matches(&link(9)));
+
+        // Line-less findings are covered anywhere in the file, but only in that file
+        let (inline, _) = find_inline(&[(".github/workflows/ci.yml", "on: push
+# repo-health: ignore WFS002 -- reviewed
+")]);
+        assert!(inline[0].matches(&workflow));
+        assert!(!inline[0].matches(&workflow.clone().at(".github/workflows/release.yml", None)));
+        assert!(!inline[0].matches(&workflow.clone().at(".github/workflows/ci.yml", Some(9))));
+
+        for content in ["# repo-health: ignore DOC005", "# repo-health: ignore DOC005 --", "# repo-health: ignore XYZ999 -- why"] {
+            let (inline, warnings) = find_inline(&[("a.yml", content)]);
+            assert!(inline.is_empty(), "{}", content);
+            assert_eq!(warnings[0].location().as_deref(), Some("a.yml:1"), "{}", content);
+        }
+
+        // Mentions outside a leading comment are not directives
+        let prose = "//! `repo-health: ignore` comments.\nSee repo-health: ignore DOC005 in the docs\n";
+        let (inline, warnings) = find_inline(&[("src/lib.rs", prose)]);
+        assert!(inline.is_empty() && warnings.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::config::ExtraPaths;
+use crate::github::TreeEntry;
+use crate::languages::{Language, LanguageBreakdown};
+
+/// Directories that group several modules rather than being a module themselves.
+const SOURCE_ROOTS: &[&str] = &["src", "lib", "pkg", "packages", "crates", "internal", "cmd", "app", "apps"];
+
+/// Directory names conventionally holding tests for their parent directory.
+const TEST_DIRS: &[&str] = &["tests", "test", "__tests__", "spec", "Tests"];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LayoutStats {
+    pub source_files: usize,
+    pub test_files: usize,
+    pub source_bytes: u64,
+    pub test_bytes: u64,
+}
+
+impl LayoutStats {
+    /// Test files per source file.
+    pub fn file_ratio(&self) -> f64 {
+        if self.source_files == 0 { 0.0 } else { self.test_files as f64 / self.source_files as f64 }
+    }
+
+    /// Test bytes per source byte.
+    pub fn byte_ratio(&self) -> f64 {
+        if self.source_bytes == 0 { 0.0 } else { self.test_bytes as f64 / self.source_bytes as f64 }
+    }
+
+    fn add(&mut self, is_test: bool, bytes: u64) {
+        if is_test {
+            self.test_files += 1;
+            self.test_bytes += bytes;
+        } else {
+            self.source_files += 1;
+            self.source_bytes += bytes;
+        }
+    }
+}
+
+/// How test files are distributed relative to source files.
+#[derive(Debug, Clone, Default)]
+pub struct TestLayout {
+    pub by_language: BTreeMap<Language, LayoutStats>,
+    pub by_module: BTreeMap<String, LayoutStats>,
+    source_dirs: BTreeMap<String, usize>,
+    test_dirs: BTreeSet<String>,
+}
+
+impl TestLayout {
+    /// Lay out the tree, counting files under the configured test paths as tests too.
+    pub fn from_tree(tree: &[TreeEntry], languages: &LanguageBreakdown, extra: &ExtraPaths) -> Self {
+        let mut layout = Self::default();
+
+        for entry in tree.iter().filter(|e| e.entry_type == "blob" && !crate::languages::is_vendored(&e.path)) {
+            let Some(language) = languages.language_of(&entry.path) else { continue };
+            let is_test = language.is_test_file(&entry.path) || extra.is_test(&entry.path);
+            let bytes = entry.size.unwrap_or(0);
+
+            layout.by_language.entry(language).or_default().add(is_test, bytes);
+            layout.by_module.entry(module_of(&entry.path)).or_default().add(is_test, bytes);
+
+            let dir = parent_dir(&entry.path).to_string();
+            if is_test {
+                layout.test_dirs.insert(dir);
+            } else {
+                *layout.source_dirs.entry(dir).or_default() += 1;
+            }
+        }
+
+        layout
+    }
+
+    /// Count a source file with inline unit tests (e.g. a Rust `#[cfg(test)]` module) as test-bearing.
+    pub fn add_inline_tests(&mut self, path: &str, language: Language) {
+        self.by_language.entry(language).or_default().test_files += 1;
+        self.by_module.entry(module_of(path)).or_default().test_files += 1;
+        self.test_dirs.insert(parent_dir(path).to_string());
+    }
+
+    pub fn total(&self) -> LayoutStats {
+        self.by_language.values().fold(LayoutStats::default(), |acc, s| LayoutStats {
+            source_files: acc.source_files + s.source_files,
+            test_files: acc.test_files + s.test_files,
+            source_bytes: acc.source_bytes + s.source_bytes,
+            test_bytes: acc.test_bytes + s.test_bytes,
+        })
+    }
+
+    /// Source directories with at least `min_files` files and no tests next to,
+    /// below, or in a conventional test directory above them.
+    pub fn untested_dirs(&self, min_files: usize) -> Vec<(String, usize)> {
+        self.source_dirs.iter()
+            .filter(|(_, files)| **files >= min_files)
+            .filter(|(dir, _)| !self.has_nearby_tests(dir))
+            .map(|(dir, files)| (dir.clone(), *files))
+            .collect()
+    }
+
+    fn has_nearby_tests(&self, dir: &str) -> bool {
+        let mirrored = dir.replace("/main/", "/test/");
+
+        self.test_dirs.iter().any(|test_dir| {
+            if test_dir == dir || (mirrored != dir && is_within(test_dir, &mirrored)) {
+                return true;
+            }
+
+            // A test directory directly below this directory or one of its ancestors
+            ancestors(dir).any(|ancestor| {
+                TEST_DIRS.iter().any(|name| {
+                    let candidate = if ancestor.is_empty() { name.to_string() } else { format!("{}/{}", ancestor, name) };
+                    is_within(test_dir, &candidate)
+                })
+            })
+        })
+    }
+}
+
+/// Top-level module a path belongs to, looking one level into source roots.
+pub fn module_of(path: &str) -> String {
+    let mut parts = path.split('/');
+    let first = parts.next().unwrap_or_default();
+    let second = parts.next();
+
+    match (second, parts.next()) {
+        // A file directly under the root or directly under a source root
+        (None, _) => "(root)".to_string(),
+        (Some(_), None) if SOURCE_ROOTS.contains(&first) => first.to_string(),
+        (Some(second), Some(_)) if SOURCE_ROOTS.contains(&first) => format!("{}/{}", first, second),
+        _ => first.to_string(),
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("")
+}
+
+fn ancestors(dir: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(dir), |d| if d.is_empty() { None } else { Some(parent_dir(d)) })
+}
+
+fn is_within(path: &str, dir: &str) -> bool {
+    path == dir || path.starts_with(&format!("{}/", dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_of() {
+        assert_eq!(module_of("README.md"), "(root)");
+        assert_eq!(module_of("src/main.rs"), "src");
+        assert_eq!(module_of("src/analyzers/tests.rs"), "src/analyzers");
+        assert_eq!(module_of("tests/cli.rs"), "tests");
+        assert_eq!(module_of("web/components/Button.tsx"), "web");
+    }
+
+    #[test]
+    fn test_ratios_and_untested_dirs() {
+        let tree = vec![
+            TreeEntry::blob("pkg/api/handler.go"),
+            TreeEntry::blob("pkg/api/handler_test.go"),
+            TreeEntry::blob("pkg/db/conn.go"),
+            TreeEntry::blob("pkg/db/query.go"),
+            TreeEntry::blob("web/src/app.ts"),
+            TreeEntry::blob("web/src/util.ts"),
+            TreeEntry::blob("web/__tests__/app.test.ts"),
+            TreeEntry::blob("vendor/github.com/lib/pq/conn.go"),
+            TreeEntry::blob("web/node_modules/left-pad/index.ts"),
+        ];
+        let languages = LanguageBreakdown::from_tree(&tree);
+        let layout = TestLayout::from_tree(&tree, &languages, &ExtraPaths::default());
+
+        let go = layout.by_language[&Language::Go];
+        assert_eq!((go.source_files, go.test_files), (3, 1));
+        assert!((go.file_ratio() - 1.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(layout.by_module["pkg/db"].test_files, 0);
+        assert_eq!(layout.untested_dirs(2), vec![("pkg/db".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_mirrored_java_layout() {
+        let tree = vec![
+            TreeEntry::blob("src/main/java/com/acme/App.java"),
+            TreeEntry::blob("src/test/java/com/acme/AppTest.java"),
+        ];
+        let languages = LanguageBreakdown::from_tree(&tree);
+        let layout = TestLayout::from_tree(&tree, &languages, &ExtraPaths::default());
+
+        assert!(layout.untested_dirs(1).is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Command;
+use crate::github::Author;
+use crate::identity::{self, IdentityResolver};
+use crate::test_layout::module_of;
+use crate::{RepoHealthError, Result};
+
+/// Degree-of-authorship model coefficients (Fritz et al., as used by Avelino et al.)
+const DOA_BASE: f64 = 3.293;
+const DOA_FIRST_AUTHORSHIP: f64 = 1.098;
+const DOA_DELIVERIES: f64 = 0.164;
+const DOA_ACCEPTANCES: f64 = 0.321;
+
+/// A developer is an author of a file when their normalized DOA exceeds this...
+const AUTHOR_NORMALIZED_DOA: f64 = 0.75;
+/// ...and their absolute DOA reaches this
+const AUTHOR_ABSOLUTE_DOA: f64 = 3.293;
+
+/// Separates commits in the `git log` output
+const COMMIT_MARKER: &str = "\u{1}";
+
+/// Separates the author name from the email on commit lines
+const EMAIL_SEPARATOR: char = '\u{2}';
+
+/// Change history of one file that still exists.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileHistory {
+    pub creator: String,
+    /// Commits touching the file, per author
+    pub changes: HashMap<String, u32>,
+}
+
+impl FileHistory {
+    /// Degree of authorship of `author` over this file.
+    pub fn doa(&self, author: &str) -> f64 {
+        let deliveries = self.changes.get(author).copied().unwrap_or(0) as f64;
+        let total: u32 = self.changes.values().sum();
+        let acceptances = total as f64 - deliveries;
+        let first_authorship = if self.creator == author { 1.0 } else { 0.0 };
+
+        DOA_BASE + DOA_FIRST_AUTHORSHIP * first_authorship + DOA_DELIVERIES * deliveries
+            - DOA_ACCEPTANCES * (1.0 + acceptances).ln()
+    }
+
+    /// Developers with a high enough degree of authorship to count as the file's authors.
+    pub fn authors(&self) -> Vec<String> {
+        let mut candidates: Vec<&String> = self.changes.keys().collect();
+        if !self.changes.contains_key(&self.creator) {
+            candidates.push(&self.creator);
+        }
+
+        let doas: Vec<(&String, f64)> = candidates.into_iter().map(|a| (a, self.doa(a))).collect();
+        let max = doas.iter().map(|(_, doa)| *doa).fold(f64::MIN, f64::max);
+
+        let mut authors: Vec<String> = doas.into_iter()
+            .filter(|(_, doa)| *doa >= AUTHOR_ABSOLUTE_DOA && *doa / max > AUTHOR_NORMALIZED_DOA)
+            .map(|(author, _)| author.clone())
+            .collect();
+        authors.sort();
+        authors
+    }
+}
+
+/// Authors of each current file, computed from a local checkout's history.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileOwnership {
+    pub authors: BTreeMap<String, Vec<String>>,
+}
+
+/// Result of the greedy truck factor search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TruckFactor {
+    pub value: usize,
+    /// Authors whose departure orphans more than half of the files, in removal order
+    pub key_authors: Vec<String>,
+    pub orphaned_files: usize,
+}
+
+/// Ownership of one directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryOwnership {
+    pub directory: String,
+    pub files: usize,
+    pub authors: usize,
+    /// Author of the most files, and how many files they author
+    pub top_author: Option<(String, usize)>,
+}
+
+impl FileOwnership {
+    /// Read the history of a local git checkout, excluding bots and merging aliases.
+    ///
+    /// Authors are known by their GitHub login when they commit with a `users.noreply.github.com`
+    /// address, so bot patterns and `--alias` apply to them as they do to the stats API; other
+    /// authors are known by the name `.mailmap` gives them.
+    pub fn from_checkout(path: &Path, identities: &IdentityResolver) -> Result<Self> {
+        let log = git(path, &[
+            "log", "--no-merges", "--reverse", "-M", "--name-status",
+            &format!("--format={}%aN{}%aE", COMMIT_MARKER, EMAIL_SEPARATOR),
+        ])?;
+        Ok(Self::from_log(&log, identities))
+    }
+
+    /// Fail unless one of the checkout's remotes is `owner/repo` on GitHub, so history from
+    /// another project isn't attributed to the analysed one.
+    pub fn verify_checkout(path: &Path, owner: &str, repo: &str) -> Result<()> {
+        let remotes = git(path, &["remote", "-v"])?;
+        let expected = format!("{}/{}", owner, repo).to_lowercase();
+
+        let matches = remotes.lines()
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .filter_map(github_repository)
+            .any(|repository| repository.to_lowercase() == expected);
+        if matches {
+            Ok(())
+        } else {
+            Err(RepoHealthError::CheckoutMismatch { path: path.display().to_string(), repository: format!("{}/{}", owner, repo) })
+        }
+    }
+
+    /// Build ownership from `git log --reverse --name-status` output with commit marker lines.
+    pub fn from_log(log: &str, identities: &IdentityResolver) -> Self {
+        let histories = parse_log(log, identities);
+
+        Self {
+            authors: histories.into_iter()
+                .filter(|(path, _)| !crate::languages::is_vendored(path))
+                .map(|(path, history)| (path, history.authors()))
+                .collect(),
+        }
+    }
+
+    /// Minimum number of authors whose departure leaves more than half of the files without an author.
+    pub fn truck_factor(&self) -> TruckFactor {
+        let total = self.authors.len();
+        let mut remaining: BTreeMap<&str, Vec<&str>> = self.authors.iter()
+            .map(|(path, authors)| (path.as_str(), authors.iter().map(String::as_str).collect()))
+            .collect();
+        let mut key_authors = Vec::new();
+
+        let orphaned = |remaining: &BTreeMap<&str, Vec<&str>>| remaining.values().filter(|a| a.is_empty()).count();
+
+        while orphaned(&remaining) * 2 <= total {
+            // Author of the most remaining files; ties resolved alphabetically
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for author in remaining.values().flatten() {
+                *counts.entry(author).or_default() += 1;
+            }
+            let Some((top, _)) = counts.into_iter().max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0))) else { break };
+
+            for authors in remaining.values_mut() {
+                authors.retain(|a| *a != top);
+            }
+            key_authors.push(top.to_string());
+        }
+
+        TruckFactor { value: key_authors.len(), orphaned_files: orphaned(&remaining), key_authors }
+    }
+
+    pub fn by_directory(&self) -> Vec<DirectoryOwnership> {
+        let mut directories: BTreeMap<String, Vec<&Vec<String>>> = BTreeMap::new();
+        for (path, authors) in &self.authors {
+            directories.entry(module_of(path)).or_default().push(authors);
+        }
+
+        directories.into_iter()
+            .map(|(directory, files)| {
+                let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+                for author in files.iter().copied().flatten() {
+                    *counts.entry(author).or_default() += 1;
+                }
+                let top_author = counts.iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                    .map(|(author, count)| (author.to_string(), *count));
+
+                DirectoryOwnership { directory, files: files.len(), authors: counts.len(), top_author }
+            })
+            .collect()
+    }
+}
+
+fn git(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git").arg("-C").arg(path).args(args).output()?;
+
+    if !output.status.success() {
+        return Err(RepoHealthError::AnalysisFailed(format!(
+            "git {} failed in {}: {}",
+            args[0],
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `owner/repo` from an HTTPS, SSH or `git@` GitHub remote URL.
+fn github_repository(url: &str) -> Option<&str> {
+    let path = ["https://github.com/", "http://github.com/", "ssh://git@github.com/", "git@github.com:"]
+        .iter()
+        .find_map(|prefix| url.strip_prefix(prefix))?;
+    let path = path.trim_end_matches('/');
+    Some(path.strip_suffix(".git").unwrap_or(path))
+}
+
+/// The identity an author line (`name`, optionally followed by the email) is counted under:
+/// the GitHub login of a noreply address, otherwise the name. `None` for bots.
+fn resolve_author(line: &str, identities: &IdentityResolver) -> Option<String> {
+    let (name, email) = line.split_once(EMAIL_SEPARATOR).unwrap_or((line, ""));
+    let name = name.trim();
+    let login = identity::noreply_login(email.trim()).unwrap_or(name);
+
+    let is_bot = [login, name].iter()
+        .any(|candidate| identities.is_bot(&Author { login: candidate.to_string(), account_type: None }));
+    (!is_bot).then(|| identities.canonical(login).to_string())
+}
+
+fn parse_log(log: &str, identities: &IdentityResolver) -> BTreeMap<String, FileHistory> {
+    let mut files: BTreeMap<String, FileHistory> = BTreeMap::new();
+    let mut author: Option<String> = None;
+
+    for line in log.lines() {
+        if let Some(line) = line.strip_prefix(COMMIT_MARKER) {
+            author = resolve_author(line, identities);
+            continue;
+        }
+
+        let Some(author) = author.as_ref() else { continue };
+        let mut fields = line.split('\t');
+        let (Some(status), Some(path)) = (fields.next(), fields.next()) else { continue };
+
+        match status.chars().next() {
+            Some('A') => {
+                files.insert(path.to_string(), FileHistory {
+                    creator: author.clone(),
+                    changes: HashMap::from([(author.clone(), 1)]),
+                });
+            }
+            Some('M') | Some('T') => {
+                let history = files.entry(path.to_string()).or_insert_with(|| FileHistory {
+                    creator: author.clone(),
+                    ..Default::default()
+                });
+                *history.changes.entry(author.clone()).or_default() += 1;
+            }
+            Some('D') => {
+                files.remove(path);
+            }
+            // Renames keep the file's history; copies start a new file
+            Some(kind @ ('R' | 'C')) => {
+                let Some(new_path) = fields.next() else { continue };
+                let mut history = if kind == 'R' {
+                    files.remove(path).unwrap_or_default()
+                } else {
+                    FileHistory { creator: author.clone(), ..Default::default() }
+                };
+                if history.creator.is_empty() {
+                    history.creator = author.clone();
+                }
+                *history.changes.entry(author.clone()).or_default() += 1;
+                files.insert(new_path.to_string(), history);
+            }
+            _ => {}
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, changes: &[&str]) -> String {
+        format!("{}{}\n\n{}\n", COMMIT_MARKER, author, changes.join("\n"))
+    }
+
+    #[test]
+    fn test_doa_authors() {
+        let history = FileHistory {
+            creator: "ann".to_string(),
+            changes: HashMap::from([("ann".to_string(), 1), ("bob".to_string(), 12)]),
+        };
+
+        // bob's many deliveries outweigh ann's first authorship
+        assert!(history.doa("bob") > history.doa("ann"));
+        assert_eq!(history.authors(), vec!["bob"]);
+    }
+
+    #[test]
+    fn test_truck_factor_from_log() {
+        let log = [
+            commit("Ann", &["A\tsrc/core/a.rs", "A\tsrc/core/b.rs", "A\tsrc/core/c.rs"]),
+            commit("Bob", &["A\tsrc/cli/main.rs", "A\tsrc/cli/args.rs"]),
+            commit("dependabot[bot]", &["M\tsrc/core/a.rs", "A\tCargo.lock"]),
+            commit("Ann", &["R100\tsrc/core/c.rs\tsrc/core/d.rs", "D\tsrc/core/b.rs"]),
+            commit("Cat", &["A\tREADME.md", "A\tvendor/dep/lib.rs"]),
+        ].concat();
+
+        let ownership = FileOwnership::from_log(&log, &IdentityResolver::default());
+        let paths: Vec<&str> = ownership.authors.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["README.md", "src/cli/args.rs", "src/cli/main.rs", "src/core/a.rs", "src/core/d.rs"]);
+        assert_eq!(ownership.authors["src/core/d.rs"], vec!["Ann"]);
+
+        // Ann and Bob each own two of five files; removing both orphans four
+        let truck_factor = ownership.truck_factor();
+        assert_eq!(truck_factor.value, 2);
+        assert_eq!(truck_factor.key_authors, vec!["Ann", "Bob"]);
+        assert_eq!(truck_factor.orphaned_files, 4);
+
+        let directories = ownership.by_directory();
+        assert_eq!(directories[0].directory, "(root)");
+        assert_eq!(directories[2], DirectoryOwnership {
+            directory: "src/core".to_string(),
+            files: 2,
+            authors: 1,
+            top_author: Some(("Ann".to_string(), 2)),
+        });
+    }
+
+    #[test]
+    fn test_authors_by_noreply_login() {
+        let identities = IdentityResolver::default()
+            .with_bot_pattern("release-*")
+            .with_alias("ann-work", "ann");
+        let author = |name: &str, email: &str| format!("{}{}{}", name, EMAIL_SEPARATOR, email);
+
+        assert_eq!(resolve_author(&author("Ann Smith", "1234+ann-work@users.noreply.github.com"), &identities), Some("ann".to_string()));
+        assert_eq!(resolve_author(&author("Ann Smith", "ann@example.com"), &identities), Some("Ann Smith".to_string()));
+        assert_eq!(resolve_author(&author("Release Bot", "9+release-please@users.noreply.github.com"), &identities), None);
+        assert_eq!(resolve_author(&author("dependabot[bot]", "support@github.com"), &identities), None);
+    }
+
+    #[test]
+    fn test_github_repository() {
+        assert_eq!(github_repository("https://github.com/acme/app.git"), Some("acme/app"));
+        assert_eq!(github_repository("git@github.com:acme/app.git"), Some("acme/app"));
+        assert_eq!(github_repository("ssh://git@github.com/acme/app"), Some("acme/app"));
+        assert_eq!(github_repository("https://gitlab.com/acme/app.git"), None);
+    }
+}